@@ -102,6 +102,36 @@ fn spawn_entities(c: &mut Criterion) {
         })
     });
 
+    c.bench_function("Anput - iterate entity components many archetypes", |b| {
+        use anput::{entity::Entity, world::World};
+
+        #[derive(Default, Clone, Copy, PartialEq)]
+        struct Position([f32; 2]);
+
+        #[derive(Default, Clone, Copy, PartialEq)]
+        struct Tag<const N: usize>;
+
+        let mut world = World::default();
+        for index in 0..ITERATIONS {
+            let _ = match index % 8 {
+                0 => world.spawn((Position::default(), Tag::<0>)),
+                1 => world.spawn((Position::default(), Tag::<1>)),
+                2 => world.spawn((Position::default(), Tag::<2>)),
+                3 => world.spawn((Position::default(), Tag::<3>)),
+                4 => world.spawn((Position::default(), Tag::<4>)),
+                5 => world.spawn((Position::default(), Tag::<5>)),
+                6 => world.spawn((Position::default(), Tag::<6>)),
+                _ => world.spawn((Position::default(), Tag::<7>)),
+            };
+        }
+
+        b.iter(|| {
+            for (entity, pos) in world.query::<true, (Entity, &mut Position)>() {
+                pos.0[0] += entity.id() as f32;
+            }
+        })
+    });
+
     c.bench_function("Hecs - iterate components", |b| {
         use hecs::World;
 
@@ -24,7 +24,7 @@ struct Strength {
 const FILENAME: &str = "./crates/_/examples/snapshot.save";
 
 fn main() -> Result<(), Box<dyn Error>> {
-    let mut universe = Universe::default().with_basics(10240, 10240)?;
+    let mut universe = Universe::default().with_basics_config(Default::default())?;
 
     // Register components to type registry (holds reflection info of each type).
     let registry = &mut *universe.resources.get_mut::<true, Registry>()?;
@@ -34,7 +34,7 @@ struct WorldChunk {
 }
 
 fn main() -> Result<(), Box<dyn Error>> {
-    let mut universe = Universe::default().with_basics(10240, 10240)?;
+    let mut universe = Universe::default().with_basics_config(Default::default())?;
 
     println!("--- Game started with chunk #0");
     let world_chunk = make_world_chunk(0)?;
@@ -22,36 +22,38 @@ struct Age(pub usize);
 struct Parent;
 
 fn main() -> Result<(), Box<dyn Error>> {
-    let mut universe = Universe::default().with_basics(10240, 10240)?.with_plugin(
-        GraphSchedulerPlugin::<true>::default()
-            // We define sequenced group for tree progression.
-            .plugin_setup(|plugin| {
-                // In that group we run all its systems in parallel, because we know
-                // they don't interact with same components mutably.
-                // Group will wait for all its parallelized systems to complete and
-                // only then this group completes.
-                plugin
-                    .system_setup(consume_energy, |system| {
-                        system
-                            .name("consume_energy")
-                            .local(SystemParallelize::AnyWorker)
-                    })
-                    .system_setup(consume_water, |system| {
-                        system
-                            .name("consume_water")
-                            .local(SystemParallelize::AnyWorker)
-                    })
-                    .system_setup(age, |system| {
-                        system.name("age").local(SystemParallelize::AnyWorker)
-                    })
-            })
-            // After entire progression group completes, we then run reproduction
-            // system sequenced, because reproduction needs to mutate all components
-            // to be able to spawn new trees.
-            .system_setup(reproduce, |system| {
-                system.name("reproduce").local(SystemParallelize::AnyWorker)
-            }),
-    );
+    let mut universe = Universe::default()
+        .with_basics_config(Default::default())?
+        .with_plugin(
+            GraphSchedulerPlugin::<true>::default()
+                // We define sequenced group for tree progression.
+                .plugin_setup(|plugin| {
+                    // In that group we run all its systems in parallel, because we know
+                    // they don't interact with same components mutably.
+                    // Group will wait for all its parallelized systems to complete and
+                    // only then this group completes.
+                    plugin
+                        .system_setup(consume_energy, |system| {
+                            system
+                                .name("consume_energy")
+                                .local(SystemParallelize::AnyWorker)
+                        })
+                        .system_setup(consume_water, |system| {
+                            system
+                                .name("consume_water")
+                                .local(SystemParallelize::AnyWorker)
+                        })
+                        .system_setup(age, |system| {
+                            system.name("age").local(SystemParallelize::AnyWorker)
+                        })
+                })
+                // After entire progression group completes, we then run reproduction
+                // system sequenced, because reproduction needs to mutate all components
+                // to be able to spawn new trees.
+                .system_setup(reproduce, |system| {
+                    system.name("reproduce").local(SystemParallelize::AnyWorker)
+                }),
+        );
     let jobs = Jobs::default();
     let scheduler = GraphScheduler::<true>;
 
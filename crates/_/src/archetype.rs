@@ -283,6 +283,20 @@ impl<'a, const LOCKING: bool, T: Component> ArchetypeColumnAccess<'a, LOCKING, T
             None
         }
     }
+
+    pub fn as_slice(&self) -> &[T] {
+        unsafe { std::slice::from_raw_parts(self.column.memory.cast::<T>(), self.size) }
+    }
+
+    pub fn as_mut_slice(&mut self) -> Option<&mut [T]> {
+        if self.unique {
+            Some(unsafe {
+                std::slice::from_raw_parts_mut(self.column.memory.cast::<T>(), self.size)
+            })
+        } else {
+            None
+        }
+    }
 }
 
 pub struct ArchetypeDynamicColumnAccess<'a, const LOCKING: bool> {
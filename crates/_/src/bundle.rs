@@ -1,6 +1,8 @@
 use crate::{
     archetype::{ArchetypeColumnInfo, ArchetypeEntityRowAccess},
     component::Component,
+    entity::Entity,
+    world::World,
 };
 use intuicio_core::object::{DynamicObject, TypedDynamicObject};
 use intuicio_data::{
@@ -343,6 +345,53 @@ impl<T: Component> Bundle for BundleOnce<T> {
     }
 }
 
+/// Bundle that can be rebuilt from clones of entity components.
+pub trait BundleExtract: Bundle + Sized {
+    fn extract<const LOCKING: bool>(world: &World, entity: Entity) -> Option<Self>;
+}
+
+macro_rules! impl_bundle_extract_tuple {
+    ($($type:ident),+) => {
+        impl<$($type: Component + Clone),+> BundleExtract for ($($type,)+) {
+            fn extract<const LOCKING: bool>(world: &World, entity: Entity) -> Option<Self> {
+                Some(($(world.component::<LOCKING, $type>(entity).ok()?.clone(),)+))
+            }
+        }
+    };
+}
+
+impl_bundle_extract_tuple!(A);
+impl_bundle_extract_tuple!(A, B);
+impl_bundle_extract_tuple!(A, B, C);
+impl_bundle_extract_tuple!(A, B, C, D);
+impl_bundle_extract_tuple!(A, B, C, D, E);
+impl_bundle_extract_tuple!(A, B, C, D, E, F);
+impl_bundle_extract_tuple!(A, B, C, D, E, F, G);
+impl_bundle_extract_tuple!(A, B, C, D, E, F, G, H);
+impl_bundle_extract_tuple!(A, B, C, D, E, F, G, H, I);
+impl_bundle_extract_tuple!(A, B, C, D, E, F, G, H, I, J);
+impl_bundle_extract_tuple!(A, B, C, D, E, F, G, H, I, J, K);
+impl_bundle_extract_tuple!(A, B, C, D, E, F, G, H, I, J, K, L);
+impl_bundle_extract_tuple!(A, B, C, D, E, F, G, H, I, J, K, L, M);
+impl_bundle_extract_tuple!(A, B, C, D, E, F, G, H, I, J, K, L, M, N);
+impl_bundle_extract_tuple!(A, B, C, D, E, F, G, H, I, J, K, L, M, N, O);
+impl_bundle_extract_tuple!(A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P);
+
+impl<A: BundleExtract, B: BundleExtract> BundleExtract for BundleChain<A, B> {
+    fn extract<const LOCKING: bool>(world: &World, entity: Entity) -> Option<Self> {
+        Some(Self(
+            A::extract::<LOCKING>(world, entity)?,
+            B::extract::<LOCKING>(world, entity)?,
+        ))
+    }
+}
+
+impl<T: Component + Clone> BundleExtract for BundleOnce<T> {
+    fn extract<const LOCKING: bool>(world: &World, entity: Entity) -> Option<Self> {
+        Some(Self(world.component::<LOCKING, T>(entity).ok()?.clone()))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
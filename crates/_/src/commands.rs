@@ -148,6 +148,26 @@ impl Command for DespawnManyCommand {
     }
 }
 
+pub struct DespawnRecursiveCommand<const LOCKING: bool, T: Component> {
+    entity: Entity,
+    _phantom: PhantomData<fn() -> T>,
+}
+
+impl<const LOCKING: bool, T: Component> DespawnRecursiveCommand<LOCKING, T> {
+    pub fn new(entity: Entity) -> Self {
+        Self {
+            entity,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<const LOCKING: bool, T: Component> Command for DespawnRecursiveCommand<LOCKING, T> {
+    fn execute(self, world: &mut World) {
+        world.despawn_recursive::<LOCKING, T>(self.entity).unwrap();
+    }
+}
+
 pub struct InsertCommand<T: Bundle + Send + Sync + 'static> {
     entity: Entity,
     bundle: T,
@@ -1,4 +1,4 @@
-use crate::archetype::ArchetypeEntityColumnAccess;
+use crate::{archetype::ArchetypeEntityColumnAccess, entity::Entity, world::World};
 use std::ops::{Deref, DerefMut};
 
 pub trait Component: Send + Sync + 'static {}
@@ -34,3 +34,58 @@ impl<const LOCKING: bool, T: Component> DerefMut for ComponentRefMut<'_, LOCKING
         self.inner.write().unwrap()
     }
 }
+
+/// Mutable component reference that marks component as updated on drop.
+///
+/// Mutable dereference is the signal, so component is reported as updated even
+/// if its value was not actually modified.
+pub struct AutoNotifyComponent<'a, const LOCKING: bool, T: Component> {
+    pub(crate) entity: Entity,
+    pub(crate) world: &'a World,
+    pub(crate) inner: AutoNotifyInner<'a, LOCKING, T>,
+    pub(crate) dirty: bool,
+}
+
+pub(crate) enum AutoNotifyInner<'a, const LOCKING: bool, T: Component> {
+    Ref(ComponentRefMut<'a, LOCKING, T>),
+    Column(&'a mut T),
+}
+
+impl<const LOCKING: bool, T: Component> Drop for AutoNotifyComponent<'_, LOCKING, T> {
+    fn drop(&mut self) {
+        if self.dirty {
+            self.world.update::<T>(self.entity);
+        }
+    }
+}
+
+impl<const LOCKING: bool, T: Component> AutoNotifyComponent<'_, LOCKING, T> {
+    pub fn entity(&self) -> Entity {
+        self.entity
+    }
+
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+}
+
+impl<const LOCKING: bool, T: Component> Deref for AutoNotifyComponent<'_, LOCKING, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        match &self.inner {
+            AutoNotifyInner::Ref(inner) => inner,
+            AutoNotifyInner::Column(inner) => inner,
+        }
+    }
+}
+
+impl<const LOCKING: bool, T: Component> DerefMut for AutoNotifyComponent<'_, LOCKING, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.dirty = true;
+        match &mut self.inner {
+            AutoNotifyInner::Ref(inner) => inner,
+            AutoNotifyInner::Column(inner) => inner,
+        }
+    }
+}
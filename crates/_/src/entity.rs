@@ -179,4 +179,9 @@ impl EntityDenseMap {
     pub fn iter(&self) -> impl Iterator<Item = Entity> + '_ {
         self.entities.iter().copied()
     }
+
+    /// Returns entities in the map as a contiguous slice, in storage order.
+    pub fn as_slice(&self) -> &[Entity] {
+        &self.entities
+    }
 }
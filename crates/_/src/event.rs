@@ -13,9 +13,12 @@ pub struct EventHandle<T: Clone + Send> {
     _phantom: PhantomData<fn() -> T>,
 }
 
+type EventFilter<T> = Box<dyn Fn(&T) -> bool + Send + Sync>;
+
 pub struct EventDispatcher<T: Clone + Send> {
-    senders: Vec<(usize, Sender<T>)>,
-    sinks: Vec<(usize, Weak<Mutex<VecDeque<T>>>)>,
+    senders: Vec<(usize, Sender<T>, Option<EventFilter<T>>)>,
+    #[allow(clippy::type_complexity)]
+    sinks: Vec<(usize, Weak<Mutex<VecDeque<T>>>, Option<EventFilter<T>>)>,
     id_generator: usize,
 }
 
@@ -31,13 +34,16 @@ impl<T: Clone + Send> Default for EventDispatcher<T> {
 
 impl<T: Clone + Send> EventDispatcher<T> {
     pub fn bind_sender(&mut self, sender: Sender<T>) -> EventHandle<T> {
-        let id = self.id_generator;
-        self.id_generator = self.id_generator.wrapping_add(1);
-        self.senders.push((id, sender));
-        EventHandle {
-            id,
-            _phantom: PhantomData,
-        }
+        self.bind_sender_inner(sender, None)
+    }
+
+    /// Binds sender that receives only events matching predicate.
+    pub fn bind_sender_filtered(
+        &mut self,
+        sender: Sender<T>,
+        predicate: impl Fn(&T) -> bool + Send + Sync + 'static,
+    ) -> EventHandle<T> {
+        self.bind_sender_inner(sender, Some(Box::new(predicate)))
     }
 
     pub fn bind_sender_make(&mut self) -> (EventHandle<T>, Receiver<T>) {
@@ -46,27 +52,46 @@ impl<T: Clone + Send> EventDispatcher<T> {
         (handle, receiver)
     }
 
+    pub fn bind_sender_filtered_make(
+        &mut self,
+        predicate: impl Fn(&T) -> bool + Send + Sync + 'static,
+    ) -> (EventHandle<T>, Receiver<T>) {
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let handle = self.bind_sender_filtered(sender, predicate);
+        (handle, receiver)
+    }
+
     pub fn bind_sink(&mut self, sink: &EventSink<T>) -> EventHandle<T> {
-        let id = self.id_generator;
-        self.id_generator = self.id_generator.wrapping_add(1);
-        self.sinks.push((id, Arc::downgrade(&sink.queue)));
-        EventHandle {
-            id,
-            _phantom: PhantomData,
-        }
+        self.bind_sink_inner(sink, None)
+    }
+
+    /// Binds sink that receives only events matching predicate.
+    pub fn bind_sink_filtered(
+        &mut self,
+        sink: &EventSink<T>,
+        predicate: impl Fn(&T) -> bool + Send + Sync + 'static,
+    ) -> EventHandle<T> {
+        self.bind_sink_inner(sink, Some(Box::new(predicate)))
     }
 
     pub fn bind_sink_make(&mut self) -> (EventHandle<T>, EventSink<T>) {
-        let sink = EventSink {
-            queue: Arc::new(Mutex::new(VecDeque::new())),
-        };
+        let sink = EventSink::default();
         let handle = self.bind_sink(&sink);
         (handle, sink)
     }
 
+    pub fn bind_sink_filtered_make(
+        &mut self,
+        predicate: impl Fn(&T) -> bool + Send + Sync + 'static,
+    ) -> (EventHandle<T>, EventSink<T>) {
+        let sink = EventSink::default();
+        let handle = self.bind_sink_filtered(&sink, predicate);
+        (handle, sink)
+    }
+
     pub fn unbind(&mut self, handle: EventHandle<T>) {
-        self.senders.retain(|(id, _)| *id != handle.id);
-        self.sinks.retain(|(id, _)| *id != handle.id);
+        self.senders.retain(|(id, _, _)| *id != handle.id);
+        self.sinks.retain(|(id, _, _)| *id != handle.id);
     }
 
     pub fn unbind_all(&mut self) {
@@ -75,11 +100,14 @@ impl<T: Clone + Send> EventDispatcher<T> {
     }
 
     pub fn dispatch(&self, event: &T) {
-        for (_, sender) in &self.senders {
-            let _ = sender.send(event.clone());
+        for (_, sender, filter) in &self.senders {
+            if Self::accepts(filter, event) {
+                let _ = sender.send(event.clone());
+            }
         }
-        for (_, queue) in &self.sinks {
-            if let Some(queue) = queue.upgrade()
+        for (_, queue, filter) in &self.sinks {
+            if Self::accepts(filter, event)
+                && let Some(queue) = queue.upgrade()
                 && let Ok(mut queue) = queue.lock()
             {
                 queue.push_back(event.clone());
@@ -88,11 +116,14 @@ impl<T: Clone + Send> EventDispatcher<T> {
     }
 
     pub fn dispatch_to_alive(&mut self, event: &T) {
-        self.senders
-            .retain(|(_, sender)| sender.send(event.clone()).is_ok());
-        self.sinks.retain(|(_, queue)| {
+        self.senders.retain(|(_, sender, filter)| {
+            !Self::accepts(filter, event) || sender.send(event.clone()).is_ok()
+        });
+        self.sinks.retain(|(_, queue, filter)| {
             if let Some(queue) = queue.upgrade() {
-                if let Ok(mut queue) = queue.lock() {
+                if Self::accepts(filter, event)
+                    && let Ok(mut queue) = queue.lock()
+                {
                     queue.push_back(event.clone());
                 }
                 true
@@ -101,6 +132,38 @@ impl<T: Clone + Send> EventDispatcher<T> {
             }
         });
     }
+
+    fn bind_sender_inner(
+        &mut self,
+        sender: Sender<T>,
+        filter: Option<EventFilter<T>>,
+    ) -> EventHandle<T> {
+        let id = self.id_generator;
+        self.id_generator = self.id_generator.wrapping_add(1);
+        self.senders.push((id, sender, filter));
+        EventHandle {
+            id,
+            _phantom: PhantomData,
+        }
+    }
+
+    fn bind_sink_inner(
+        &mut self,
+        sink: &EventSink<T>,
+        filter: Option<EventFilter<T>>,
+    ) -> EventHandle<T> {
+        let id = self.id_generator;
+        self.id_generator = self.id_generator.wrapping_add(1);
+        self.sinks.push((id, Arc::downgrade(&sink.queue), filter));
+        EventHandle {
+            id,
+            _phantom: PhantomData,
+        }
+    }
+
+    fn accepts(filter: &Option<EventFilter<T>>, event: &T) -> bool {
+        filter.as_ref().map(|filter| filter(event)).unwrap_or(true)
+    }
 }
 
 #[derive(Debug)]
@@ -108,6 +171,14 @@ pub struct EventSink<T> {
     queue: Arc<Mutex<VecDeque<T>>>,
 }
 
+impl<T> Default for EventSink<T> {
+    fn default() -> Self {
+        Self {
+            queue: Arc::new(Mutex::new(VecDeque::new())),
+        }
+    }
+}
+
 impl<T> EventSink<T> {
     pub fn len(&self) -> usize {
         self.queue.lock().map_or(0, |queue| queue.len())
@@ -156,4 +227,36 @@ mod tests {
         event.dispatch(&"World".to_string());
         assert!(receiver.try_recv().is_err());
     }
+
+    #[test]
+    fn test_event_filtered() {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        enum Kind {
+            Began,
+            Continue,
+            Ended,
+        }
+
+        let mut event = EventDispatcher::<Kind>::default();
+        let (_, all) = event.bind_sink_make();
+        let (_, began) = event.bind_sink_filtered_make(|kind| *kind == Kind::Began);
+        let (_, receiver) = event.bind_sender_filtered_make(|kind| *kind != Kind::Continue);
+
+        for kind in [Kind::Began, Kind::Continue, Kind::Continue, Kind::Ended] {
+            event.dispatch(&kind);
+        }
+
+        assert_eq!(all.len(), 4);
+        assert_eq!(began.iter().collect::<Vec<_>>(), vec![Kind::Began]);
+        assert_eq!(
+            receiver.try_iter().collect::<Vec<_>>(),
+            vec![Kind::Began, Kind::Ended]
+        );
+
+        drop(all);
+        event.dispatch_to_alive(&Kind::Continue);
+        assert_eq!(event.sinks.len(), 1);
+        assert_eq!(event.senders.len(), 1);
+        assert!(began.is_empty());
+    }
 }
@@ -4,6 +4,7 @@ use crate::{
     },
     component::Component,
     entity::Entity,
+    processor::{WorldProcessor, WorldProcessorEntityMapping},
     query::{
         DynamicQueryFilter, DynamicQueryItem, DynamicQueryIter, TypedQueryFetch, TypedQueryIter,
     },
@@ -12,7 +13,7 @@ use crate::{
 use intuicio_data::type_hash::TypeHash;
 use serde::{Deserialize, Serialize};
 use std::{
-    collections::VecDeque,
+    collections::{HashMap, VecDeque},
     ops::{Deref, DerefMut},
 };
 
@@ -586,10 +587,130 @@ impl<'a> Multiverse<'a> {
     }
 }
 
+/// Moves single entity with all its components from one world into another.
+///
+/// `transferred` maps entities moved between these worlds to their IDs in the
+/// target world - these might be reserved with [`World::reserve_entity`] ahead
+/// of transfer, so entities can be moved in any order. Moved entity is spawned
+/// at its mapped ID if there is one, and its returned ID in the target world.
+/// Entity references in moved components (e.g. relations) are remapped through
+/// `processor` using `transferred` mappings - references to entities missing in
+/// mappings get stripped through `processor` unlinking, components emptied that
+/// way are kept. References to moved entity held by source world entities that
+/// are not transferred get unlinked through `processor` before it gets copied,
+/// removing components that got emptied (e.g. incoming relations).
+///
+/// Only components with unlinking registered in `processor` are searched for
+/// references to moved entity. On error moved entity stays in source world
+/// and target world is left untouched, only unlinking of references to moved
+/// entity might have already happened.
+pub fn transfer_entity<const LOCKING: bool>(
+    from: &mut World,
+    to: &mut World,
+    entity: Entity,
+    transferred: &HashMap<Entity, Entity>,
+    processor: &WorldProcessor,
+) -> Result<Entity, WorldError> {
+    let mut incoming = Vec::new();
+    for archetype in from.archetypes() {
+        for column in archetype.columns() {
+            if !processor.has_entity_unlinking_raw(column.type_hash()) {
+                continue;
+            }
+            let access = archetype.dynamic_column::<LOCKING>(column.type_hash(), false)?;
+            for (index, holder) in archetype.entities().iter().enumerate() {
+                if holder == entity || transferred.contains_key(&holder) {
+                    continue;
+                }
+                let related = unsafe {
+                    processor.related_entities_raw(column.type_hash(), access.data(index)?)
+                };
+                if related.contains(&entity) {
+                    incoming.push((holder, *column));
+                }
+            }
+        }
+    }
+    let columns = from
+        .row::<LOCKING>(entity)?
+        .columns()
+        .cloned()
+        .collect::<Vec<_>>();
+    let result = if let Some(result) = transferred.get(&entity).copied() {
+        unsafe { to.spawn_uninitialized_at_raw(result, columns.clone())? };
+        result
+    } else {
+        unsafe { to.spawn_uninitialized_raw(columns.clone())?.0 }
+    };
+    if let Err(error) = unlink_transferred_entity::<LOCKING>(from, entity, incoming, processor) {
+        unsafe { to.despawn_uninitialized(result)? };
+        return Err(error);
+    }
+    let accessible = from
+        .row::<LOCKING>(entity)
+        .and_then(|_| to.row::<LOCKING>(result).map(|_| ()));
+    if let Err(error) = accessible {
+        unsafe { to.despawn_uninitialized(result)? };
+        return Err(error);
+    }
+    {
+        let access_from = from.row::<LOCKING>(entity)?;
+        let access = to.row::<LOCKING>(result)?;
+        let mappings = WorldProcessorEntityMapping::new(transferred).with(entity, result);
+        for column in &columns {
+            let type_hash = column.type_hash();
+            unsafe {
+                let data = access.data(type_hash).unwrap();
+                let data_from = access_from.data(type_hash).unwrap();
+                data.copy_from(data_from, column.layout().size());
+                for related in processor.related_entities_raw(type_hash, data) {
+                    if related != entity && !transferred.contains_key(&related) {
+                        processor.unlink_entity_raw(type_hash, data, related);
+                    }
+                }
+                processor.remap_entities_raw(type_hash, data, mappings);
+            }
+        }
+    }
+    if let Err(error) = unsafe { from.despawn_uninitialized(entity) } {
+        // Source world still owns moved components, so forget their copies.
+        unsafe { to.despawn_uninitialized(result)? };
+        return Err(error);
+    }
+    Ok(result)
+}
+
+fn unlink_transferred_entity<const LOCKING: bool>(
+    world: &mut World,
+    entity: Entity,
+    holders: Vec<(Entity, ArchetypeColumnInfo)>,
+    processor: &WorldProcessor,
+) -> Result<(), WorldError> {
+    let mut emptied = Vec::new();
+    for (holder, column) in holders {
+        let empty = unsafe {
+            let data = world.row::<LOCKING>(holder)?.data(column.type_hash())?;
+            processor.unlink_entity_raw(column.type_hash(), data, entity)
+        };
+        if empty.unwrap_or_default() {
+            emptied.push((holder, column));
+        } else {
+            world.update_raw(holder, column.type_hash());
+        }
+    }
+    for (holder, column) in emptied {
+        world.remove_raw(holder, vec![column])?;
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{query::Include, world::World};
+    use crate::{
+        query::Include,
+        world::{Relation, World},
+    };
 
     #[test]
     fn test_multiverse() {
@@ -675,4 +796,77 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn test_transfer_entity() {
+        let mut processor = WorldProcessor::default();
+        Relation::<()>::register_to_processor(&mut processor);
+
+        let mut staging = World::default();
+        let a = staging.spawn((1usize, true)).unwrap();
+        let b = staging.spawn((2usize,)).unwrap();
+        let c = staging.spawn((3usize,)).unwrap();
+        let d = staging.spawn((4usize,)).unwrap();
+        let e = staging.spawn((5usize,)).unwrap();
+        staging.relate::<true, _>((), b, a).unwrap();
+        staging.relate::<true, _>((), b, c).unwrap();
+        staging.relate::<true, _>((), d, a).unwrap();
+        staging.relate::<true, _>((), d, c).unwrap();
+        staging.relate::<true, _>((), e, a).unwrap();
+        staging.clear_changes();
+
+        let mut world = World::default();
+        world.spawn((0usize,)).unwrap();
+
+        // entities transferred together have their IDs reserved up front.
+        let mappings = HashMap::from_iter([
+            (a, world.reserve_entity().unwrap()),
+            (b, world.reserve_entity().unwrap()),
+        ]);
+        let new_b =
+            transfer_entity::<true>(&mut staging, &mut world, b, &mappings, &processor).unwrap();
+        assert_eq!(new_b, mappings[&b]);
+        assert!(!staging.has_entity(b));
+        assert_eq!(*world.component::<true, usize>(new_b).unwrap(), 2);
+
+        let new_a =
+            transfer_entity::<true>(&mut staging, &mut world, a, &mappings, &processor).unwrap();
+        assert_eq!(new_a, mappings[&a]);
+        assert!(!staging.has_entity(a));
+        assert_eq!(*world.component::<true, usize>(new_a).unwrap(), 1);
+        assert!(*world.component::<true, bool>(new_a).unwrap());
+        assert_eq!(
+            world
+                .relations_outgoing::<true, ()>(new_b)
+                .map(|(_, _, entity)| entity)
+                .collect::<Vec<_>>(),
+            vec![new_a]
+        );
+
+        // incoming relations left in source world get unlinked.
+        assert_eq!(
+            staging
+                .relations_outgoing::<true, ()>(d)
+                .map(|(_, _, entity)| entity)
+                .collect::<Vec<_>>(),
+            vec![c]
+        );
+        assert!(staging.entity_component_did_changed::<Relation<()>>(d));
+        assert!(!staging.has_entity_component::<Relation<()>>(e));
+
+        // failed transfer leaves both worlds untouched.
+        let taken = HashMap::from_iter([(c, new_a)]);
+        assert!(transfer_entity::<true>(&mut staging, &mut world, c, &taken, &processor).is_err());
+        assert_eq!(*staging.component::<true, usize>(c).unwrap(), 3);
+        assert!(staging.has_relation::<true, ()>(d, c));
+        assert_eq!(world.len(), 3);
+
+        let new_c =
+            transfer_entity::<true>(&mut staging, &mut world, c, &HashMap::new(), &processor)
+                .unwrap();
+        assert_eq!(*world.component::<true, usize>(new_c).unwrap(), 3);
+        assert!(!staging.has_entity_component::<Relation<()>>(d));
+        assert_eq!(world.len(), 4);
+        assert_eq!(staging.len(), 2);
+    }
 }
@@ -4,6 +4,9 @@ use crate::{
     component::Component,
     entity::Entity,
     processor::{WorldProcessor, WorldProcessorEntityMapping},
+    scheduler::{GraphScheduler, SystemGroupChild, SystemName, SystemOrder, SystemPriority},
+    systems::Systems,
+    universe::Universe,
     world::{Relation, World, WorldError},
 };
 use intuicio_core::{registry::Registry, types::TypeQuery};
@@ -12,7 +15,10 @@ use intuicio_framework_serde::{
     Intermediate, IntermediateResult, SerializationRegistry, from_intermediate, to_intermediate,
 };
 use serde::{Deserialize, Serialize, de::DeserializeOwned};
-use std::{collections::HashMap, error::Error};
+use std::{
+    collections::{HashMap, HashSet},
+    error::Error,
+};
 
 #[derive(Debug)]
 pub enum PrefabError {
@@ -111,7 +117,8 @@ impl Prefab {
                     return Err("Expected intermediate sequence".into());
                 };
                 for tuple in items {
-                    let Intermediate::Tuple(tuple) = tuple else {
+                    // Tuples read back from text documents are sequences.
+                    let (Intermediate::Tuple(tuple) | Intermediate::Seq(tuple)) = tuple else {
                         return Err("Expected intermediate tuple".into());
                     };
                     if tuple.len() != 2 {
@@ -308,13 +315,14 @@ impl Prefab {
                     {
                         let data = access.data(info.type_hash())?;
                         let component = &column.components[index];
-                        access.initialize_raw(type_)?;
+                        // types without initializer get deserialized into uninitialized memory.
+                        let initialized = type_.initialize(data.cast());
                         serialization
                             .dynamic_deserialize_to(
                                 info.type_hash(),
                                 data,
                                 component,
-                                true,
+                                initialized,
                                 registry,
                             )
                             .map_err(|_| PrefabError::CouldNotDeserializeType {
@@ -373,6 +381,297 @@ impl Prefab {
     }
 }
 
+/// Node of systems graph stored in [`UniversePrefab`].
+///
+/// Systems are identified by their names among their siblings, since system
+/// objects themselves can not be serialized.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PrefabSystem {
+    pub name: Option<String>,
+    pub priority: Option<usize>,
+    pub order: Option<usize>,
+    pub locals: Vec<PrefabArchetypeColumn>,
+    pub children: Vec<PrefabSystem>,
+}
+
+/// Snapshot of universe simulation, resources and systems graph.
+///
+/// Only resources and system locals with types known to both type and
+/// serialization registries are stored, the rest (like registries themselves)
+/// are skipped. Systems graph is stored as tree of named groups, with systems
+/// priority, order and locals. Systems can not be recreated from snapshot, so
+/// universe loading it has to have the same systems graph and resources
+/// installed, usually by the same plugins - snapshot is then validated against
+/// it and stored state is restored in place. Simulation components must be
+/// serializable too.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UniversePrefab {
+    pub simulation: Prefab,
+    pub resources: Vec<PrefabArchetypeColumn>,
+    pub systems: Vec<PrefabSystem>,
+}
+
+impl UniversePrefab {
+    pub fn from_universe<const LOCKING: bool>(universe: &Universe) -> Result<Self, Box<dyn Error>> {
+        let serialization = universe.resources.get::<LOCKING, SerializationRegistry>()?;
+        let registry = universe.resources.get::<LOCKING, Registry>()?;
+        let simulation =
+            Prefab::from_world::<LOCKING>(&universe.simulation, &serialization, &registry)?;
+        let resources = Self::serialize_columns::<LOCKING>(
+            universe.resources.world(),
+            universe.resources.entity(),
+            &serialization,
+            &registry,
+        )?;
+        let mut roots = GraphScheduler::<LOCKING>::collect_roots(&universe.systems)
+            .into_iter()
+            .collect::<Vec<_>>();
+        roots.sort();
+        let mut visited = HashSet::with_capacity(universe.systems.len());
+        let systems = roots
+            .into_iter()
+            .map(|entity| {
+                Self::serialize_system::<LOCKING>(
+                    &universe.systems,
+                    entity,
+                    &serialization,
+                    &registry,
+                    &mut visited,
+                )
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self {
+            simulation,
+            resources,
+            systems,
+        })
+    }
+
+    /// Replaces universe simulation with stored one, overwrites stored
+    /// resources in place and restores stored state of matching systems,
+    /// remapping entities they reference. Fails without changing universe if
+    /// its systems graph lacks any of stored systems.
+    pub fn to_universe<const LOCKING: bool>(
+        &self,
+        universe: &mut Universe,
+    ) -> Result<HashMap<Entity, Entity>, Box<dyn Error>> {
+        let mut systems = Vec::with_capacity(universe.systems.len());
+        let mut roots = GraphScheduler::<LOCKING>::collect_roots(&universe.systems)
+            .into_iter()
+            .collect::<Vec<_>>();
+        roots.sort();
+        Self::match_systems::<LOCKING>(&universe.systems, &self.systems, roots, "", &mut systems)?;
+        let resources = &universe.resources;
+        let processor = resources.get::<LOCKING, WorldProcessor>()?;
+        let serialization = resources.get::<LOCKING, SerializationRegistry>()?;
+        let registry = resources.get::<LOCKING, Registry>()?;
+        let (simulation, mappings) =
+            self.simulation
+                .to_world::<LOCKING>(&processor, &serialization, &registry, ())?;
+        Self::deserialize_columns::<LOCKING>(
+            resources.world(),
+            resources.entity(),
+            &self.resources,
+            &processor,
+            &serialization,
+            &registry,
+            &mappings,
+        )?;
+        for (entity, system) in systems {
+            Self::deserialize_columns::<LOCKING>(
+                &universe.systems,
+                entity,
+                &system.locals,
+                &processor,
+                &serialization,
+                &registry,
+                &mappings,
+            )?;
+            Self::restore_system_value::<LOCKING, _>(
+                &mut universe.systems,
+                entity,
+                system.priority.map(SystemPriority),
+            )?;
+            Self::restore_system_value::<LOCKING, _>(
+                &mut universe.systems,
+                entity,
+                system.order.map(SystemOrder),
+            )?;
+        }
+        drop(processor);
+        drop(serialization);
+        drop(registry);
+        universe.simulation = simulation;
+        Ok(mappings)
+    }
+
+    fn serialize_system<const LOCKING: bool>(
+        systems: &Systems,
+        entity: Entity,
+        serialization: &SerializationRegistry,
+        registry: &Registry,
+        visited: &mut HashSet<Entity>,
+    ) -> Result<PrefabSystem, Box<dyn Error>> {
+        if !visited.insert(entity) {
+            return Err(format!("Systems graph has cycle at system: {entity}").into());
+        }
+        let children = systems
+            .relations_outgoing::<LOCKING, SystemGroupChild>(entity)
+            .map(|(_, _, entity)| entity)
+            .collect::<Vec<_>>();
+        Ok(PrefabSystem {
+            name: systems
+                .component::<LOCKING, SystemName>(entity)
+                .ok()
+                .map(|name| name.as_str().to_owned()),
+            priority: systems
+                .component::<LOCKING, SystemPriority>(entity)
+                .ok()
+                .map(|priority| priority.0),
+            order: systems
+                .component::<LOCKING, SystemOrder>(entity)
+                .ok()
+                .map(|order| order.0),
+            locals: Self::serialize_columns::<LOCKING>(systems, entity, serialization, registry)?,
+            children: children
+                .into_iter()
+                .map(|entity| {
+                    Self::serialize_system::<LOCKING>(
+                        systems,
+                        entity,
+                        serialization,
+                        registry,
+                        visited,
+                    )
+                })
+                .collect::<Result<_, _>>()?,
+        })
+    }
+
+    fn match_systems<'a, const LOCKING: bool>(
+        systems: &Systems,
+        stored: &'a [PrefabSystem],
+        mut candidates: Vec<Entity>,
+        path: &str,
+        output: &mut Vec<(Entity, &'a PrefabSystem)>,
+    ) -> Result<(), Box<dyn Error>> {
+        for system in stored {
+            let name = system.name.as_deref();
+            let path = format!("{path}/{}", name.unwrap_or("<unnamed>"));
+            let index = candidates
+                .iter()
+                .position(|entity| {
+                    systems
+                        .component::<LOCKING, SystemName>(*entity)
+                        .ok()
+                        .map(|name| name.as_str().to_owned())
+                        .as_deref()
+                        == name
+                })
+                .ok_or_else(|| format!("Could not find system: {path}"))?;
+            let entity = candidates.remove(index);
+            output.push((entity, system));
+            let children = systems
+                .relations_outgoing::<LOCKING, SystemGroupChild>(entity)
+                .map(|(_, _, entity)| entity)
+                .collect();
+            Self::match_systems::<LOCKING>(systems, &system.children, children, &path, output)?;
+        }
+        Ok(())
+    }
+
+    fn restore_system_value<const LOCKING: bool, T: Component>(
+        systems: &mut Systems,
+        entity: Entity,
+        value: Option<T>,
+    ) -> Result<(), Box<dyn Error>> {
+        match value {
+            Some(value) => {
+                if let Ok(mut current) = systems.component_mut::<LOCKING, T>(entity) {
+                    *current = value;
+                } else {
+                    systems.insert(entity, (value,))?;
+                }
+            }
+            None => {
+                if systems.has_entity_component::<T>(entity) {
+                    systems.remove::<(T,)>(entity)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn serialize_columns<const LOCKING: bool>(
+        world: &World,
+        entity: Entity,
+        serialization: &SerializationRegistry,
+        registry: &Registry,
+    ) -> Result<Vec<PrefabArchetypeColumn>, Box<dyn Error>> {
+        let archetype = world.archetype_by_id(world.entity_archetype_id(entity)?)?;
+        let mut result = Vec::default();
+        for column in archetype.columns() {
+            let Some(type_) = registry.find_type(TypeQuery {
+                type_hash: Some(column.type_hash()),
+                ..Default::default()
+            }) else {
+                continue;
+            };
+            let access = world.dynamic_get::<LOCKING>(column.type_hash(), entity, false)?;
+            let Ok(component) = (unsafe {
+                serialization.dynamic_serialize_from(column.type_hash(), access.data(), registry)
+            }) else {
+                continue;
+            };
+            result.push(PrefabArchetypeColumn {
+                type_name: type_.type_name().to_owned(),
+                module_name: type_.module_name().map(|name| name.to_owned()),
+                components: vec![component],
+            });
+        }
+        Ok(result)
+    }
+
+    fn deserialize_columns<const LOCKING: bool>(
+        world: &World,
+        entity: Entity,
+        columns: &[PrefabArchetypeColumn],
+        processor: &WorldProcessor,
+        serialization: &SerializationRegistry,
+        registry: &Registry,
+        mappings: &HashMap<Entity, Entity>,
+    ) -> Result<(), Box<dyn Error>> {
+        for column in columns {
+            let error = || PrefabError::CouldNotDeserializeType {
+                type_name: column.type_name.to_owned(),
+                module_name: column.module_name.to_owned(),
+            };
+            let type_hash = registry
+                .find_type(TypeQuery {
+                    name: Some(column.type_name.as_str().into()),
+                    module_name: column.module_name.as_ref().map(|name| name.as_str().into()),
+                    ..Default::default()
+                })
+                .ok_or_else(error)?
+                .type_hash();
+            let component = column.components.first().ok_or_else(error)?;
+            let access = world.dynamic_get::<LOCKING>(type_hash, entity, true)?;
+            unsafe {
+                serialization
+                    .dynamic_deserialize_to(type_hash, access.data(), component, true, registry)
+                    .map_err(|_| error())?;
+                processor.remap_entities_raw(
+                    type_hash,
+                    access.data(),
+                    WorldProcessorEntityMapping::new(mappings),
+                );
+            }
+            world.update_raw(entity, type_hash);
+        }
+        Ok(())
+    }
+}
+
 pub struct PrefabRow<'a> {
     pub entity: Entity,
     pub components: Vec<PrefabComponent<'a>>,
@@ -464,4 +763,112 @@ mod tests {
             assert!(world2.has_relation::<true, ()>(b2, a2));
         }
     }
+
+    #[test]
+    fn test_universe_prefab() {
+        use crate::{
+            query::Query,
+            scheduler::{GraphScheduler, GraphSchedulerPlugin},
+            systems::SystemContext,
+            universe::{Local, Res},
+        };
+        use moirai::jobs::Jobs;
+
+        fn step(context: SystemContext) -> Result<(), Box<dyn Error>> {
+            let (world, speed, mut steps, query) = context.fetch::<(
+                &World,
+                Res<true, &f32>,
+                Local<true, &mut usize>,
+                Query<true, (&mut f32, &bool)>,
+            )>()?;
+            *steps += 1;
+            for (value, enabled) in query.query(world) {
+                if *enabled {
+                    *value += *speed;
+                }
+            }
+            Ok(())
+        }
+
+        fn make(speed: f32, system_name: &'static str) -> Universe {
+            let mut universe = Universe::default()
+                .with_basics(1024, 1024)
+                .unwrap()
+                .with_resource(speed)
+                .unwrap()
+                .with_plugin(
+                    GraphSchedulerPlugin::<true>::default().plugin_setup(|plugin| {
+                        plugin
+                            .name("group")
+                            .system_setup(step, |system| system.name(system_name).local(0usize))
+                    }),
+                );
+            GraphScheduler::<true>
+                .run(&Jobs::default(), &mut universe)
+                .unwrap();
+            universe
+        }
+
+        fn state(universe: &Universe) -> (f32, usize, Vec<(f32, bool)>) {
+            let mut values = universe
+                .simulation
+                .query::<true, (&f32, &bool)>()
+                .map(|(value, enabled)| (*value, *enabled))
+                .collect::<Vec<_>>();
+            values.sort_by(|a, b| a.0.total_cmp(&b.0));
+            let system = universe
+                .systems
+                .find_by::<true, _>(&SystemName::new("step"));
+            (
+                *universe.resources.get::<true, f32>().unwrap(),
+                *universe
+                    .systems
+                    .component::<true, usize>(system.unwrap())
+                    .unwrap(),
+                values,
+            )
+        }
+
+        let mut universe = make(1.5, "step");
+        universe.simulation.spawn((1.0f32, true)).unwrap();
+        universe.simulation.spawn((10.0f32, false)).unwrap();
+        universe.simulation.spawn((100.0f32, true)).unwrap();
+        GraphScheduler::<true>
+            .run(&Jobs::default(), &mut universe)
+            .unwrap();
+
+        let prefab = UniversePrefab::from_universe::<true>(&universe).unwrap();
+        let prefab = intuicio_framework_serde::from_str::<UniversePrefab>(
+            &intuicio_framework_serde::to_string_compact(&prefab).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(prefab.systems.len(), 1);
+        let root = &prefab.systems[0];
+        assert_eq!(root.name, None);
+        assert_eq!(root.children[0].name.as_deref(), Some("group"));
+        assert_eq!(root.children[0].children[0].name.as_deref(), Some("step"));
+        assert_eq!(root.children[0].children[0].locals.len(), 1);
+
+        let mut mismatched = make(0.0, "other");
+        let error = prefab.to_universe::<true>(&mut mismatched).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Could not find system: /<unnamed>/group/step"
+        );
+        assert_eq!(*mismatched.resources.get::<true, f32>().unwrap(), 0.0);
+
+        let mut loaded = make(0.0, "step");
+        loaded.simulation.spawn((42.0f32, true)).unwrap();
+        prefab.to_universe::<true>(&mut loaded).unwrap();
+        assert_eq!(state(&loaded), state(&universe));
+
+        let jobs = Jobs::default();
+        GraphScheduler::<true>.run(&jobs, &mut universe).unwrap();
+        GraphScheduler::<true>.run(&jobs, &mut loaded).unwrap();
+        assert_eq!(state(&loaded), state(&universe));
+        assert_eq!(
+            state(&loaded),
+            (1.5, 3, vec![(4.0, true), (10.0, false), (103.0, true)])
+        );
+    }
 }
@@ -12,6 +12,8 @@ pub struct WorldProcessor {
     remap_entities:
         HashMap<TypeHash, Box<dyn Fn(*mut u8, WorldProcessorEntityMapping) + Send + Sync>>,
     #[allow(clippy::type_complexity)]
+    unlink_entities: HashMap<TypeHash, Box<dyn Fn(*mut u8, Entity) -> bool + Send + Sync>>,
+    #[allow(clippy::type_complexity)]
     related_entities: HashMap<TypeHash, Box<dyn Fn(*const u8) -> Vec<Entity> + Send + Sync>>,
     #[allow(clippy::type_complexity)]
     format: HashMap<
@@ -64,6 +66,54 @@ impl WorldProcessor {
         }
     }
 
+    /// Registers removal of given entity references from component, where
+    /// returned `true` means component got emptied and can be removed.
+    pub fn register_entity_unlinking<T: Component>(
+        &mut self,
+        f: impl Fn(&mut T, Entity) -> bool + Send + Sync + 'static,
+    ) {
+        self.register_entity_unlinking_raw(TypeHash::of::<T>(), move |pointer, entity| {
+            f(unsafe { pointer.cast::<T>().as_mut().unwrap() }, entity)
+        });
+    }
+
+    pub fn register_entity_unlinking_raw(
+        &mut self,
+        type_hash: TypeHash,
+        f: impl Fn(*mut u8, Entity) -> bool + Send + Sync + 'static,
+    ) {
+        self.unlink_entities.insert(type_hash, Box::new(f));
+    }
+
+    pub fn unregister_entity_unlinking<T: Component>(&mut self) {
+        self.unregister_entity_unlinking_raw(TypeHash::of::<T>());
+    }
+
+    pub fn unregister_entity_unlinking_raw(&mut self, type_hash: TypeHash) {
+        self.unlink_entities.remove(&type_hash);
+    }
+
+    pub fn has_entity_unlinking_raw(&self, type_hash: TypeHash) -> bool {
+        self.unlink_entities.contains_key(&type_hash)
+    }
+
+    /// Returns `None` if component type has no unlinking registered.
+    pub fn unlink_entity<T>(&self, data: &mut T, entity: Entity) -> Option<bool> {
+        unsafe { self.unlink_entity_raw(TypeHash::of::<T>(), data as *mut T as *mut u8, entity) }
+    }
+
+    /// # Safety
+    pub unsafe fn unlink_entity_raw(
+        &self,
+        type_hash: TypeHash,
+        pointer: *mut u8,
+        entity: Entity,
+    ) -> Option<bool> {
+        self.unlink_entities
+            .get(&type_hash)
+            .map(|unlinker| unlinker(pointer, entity))
+    }
+
     pub fn register_entity_inspector<T: Component>(
         &mut self,
         f: impl Fn(&T) -> Vec<Entity> + Send + Sync + 'static,
@@ -195,17 +245,31 @@ impl WorldProcessor {
     }
 }
 
+#[derive(Clone, Copy)]
 pub struct WorldProcessorEntityMapping<'a> {
     mapping: &'a HashMap<Entity, Entity>,
+    extra: Option<(Entity, Entity)>,
 }
 
 impl<'a> WorldProcessorEntityMapping<'a> {
     pub fn new(mapping: &'a HashMap<Entity, Entity>) -> Self {
-        Self { mapping }
+        Self {
+            mapping,
+            extra: None,
+        }
+    }
+
+    /// Adds single mapping on top of borrowed ones, without copying them.
+    pub fn with(mut self, from: Entity, to: Entity) -> Self {
+        self.extra = Some((from, to));
+        self
     }
 
     pub fn remap(&self, entity: Entity) -> Entity {
-        self.mapping.get(&entity).copied().unwrap_or_default()
+        match self.extra {
+            Some((from, to)) if from == entity => to,
+            _ => self.mapping.get(&entity).copied().unwrap_or_default(),
+        }
     }
 }
 
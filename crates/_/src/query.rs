@@ -1,9 +1,10 @@
 use crate::{
     archetype::{
-        Archetype, ArchetypeColumnAccess, ArchetypeDynamicColumnAccess, ArchetypeDynamicColumnItem,
-        ArchetypeDynamicColumnIter, ArchetypeError,
+        Archetype, ArchetypeColumnAccess, ArchetypeColumnReadIter, ArchetypeColumnWriteIter,
+        ArchetypeDynamicColumnAccess, ArchetypeDynamicColumnItem, ArchetypeDynamicColumnIter,
+        ArchetypeError,
     },
-    component::{Component, ComponentRef, ComponentRefMut},
+    component::{AutoNotifyComponent, AutoNotifyInner, Component, ComponentRef, ComponentRefMut},
     entity::{Entity, EntityDenseMap},
     view::WorldView,
     world::World,
@@ -12,7 +13,9 @@ use intuicio_data::type_hash::TypeHash;
 use std::{
     collections::{HashMap, HashSet},
     error::Error,
+    iter::Copied,
     marker::PhantomData,
+    slice::Iter,
     sync::Arc,
 };
 
@@ -21,6 +24,7 @@ pub enum QueryError {
     Archetype(ArchetypeError),
     TryingToReadUnavailableType { type_hash: TypeHash },
     TryingToWriteUnavailableType { type_hash: TypeHash },
+    WorldAccessRequired { type_hash: TypeHash },
 }
 
 impl Error for QueryError {}
@@ -41,6 +45,9 @@ impl std::fmt::Display for QueryError {
             Self::TryingToWriteUnavailableType { type_hash } => {
                 write!(f, "Trying to write unavailable type: {type_hash:?}")
             }
+            Self::WorldAccessRequired { type_hash } => {
+                write!(f, "World access required to fetch type: {type_hash:?}")
+            }
         }
     }
 }
@@ -165,6 +172,22 @@ impl<'a, const LOCKING: bool, Fetch: TypedLookupFetch<'a, LOCKING>> Lookup<'a, L
         view.lookup::<'a, LOCKING, Fetch>(entities)
     }
 
+    pub fn lookup_optional(
+        &self,
+        world: &'a World,
+        entities: impl IntoIterator<Item = Entity> + 'a,
+    ) -> TypedLookupOptionalIter<'a, LOCKING, Fetch> {
+        world.lookup_optional::<'a, LOCKING, Fetch>(entities)
+    }
+
+    pub fn lookup_optional_view(
+        &self,
+        view: &'a WorldView,
+        entities: impl IntoIterator<Item = Entity> + 'a,
+    ) -> TypedLookupOptionalIter<'a, LOCKING, Fetch> {
+        view.lookup_optional::<'a, LOCKING, Fetch>(entities)
+    }
+
     pub fn lookup_access(&self, world: &'a World) -> TypedLookupAccess<'a, LOCKING, Fetch> {
         world.lookup_access::<'a, LOCKING, Fetch>()
     }
@@ -226,8 +249,21 @@ pub trait TypedQueryFetch<'a, const LOCKING: bool> {
     fn access(archetype: &'a Archetype) -> Result<Self::Access, QueryError>;
     fn fetch(access: &mut Self::Access) -> Option<Self::Value>;
 
+    /// Accesses archetype owned by given world, for fetches that need world,
+    /// e.g. to record changes. Defaults to [`Self::access`].
+    #[allow(unused_variables)]
+    fn access_world(
+        world: &'a World,
+        archetype: &'a Archetype,
+    ) -> Result<Self::Access, QueryError> {
+        Self::access(archetype)
+    }
+
     #[allow(unused_variables)]
     fn unique_access(output: &mut HashSet<TypeHash>) {}
+
+    #[allow(unused_variables)]
+    fn shared_access(output: &mut HashSet<TypeHash>) {}
 }
 
 pub trait TypedLookupFetch<'a, const LOCKING: bool> {
@@ -239,8 +275,25 @@ pub trait TypedLookupFetch<'a, const LOCKING: bool> {
     fn fetch(access: &mut Self::Access, entity: Entity) -> Option<Self::Value>;
     fn fetch_one(world: &'a World, entity: Entity) -> Option<Self::ValueOne>;
 
+    /// Accesses archetype owned by given world, for fetches that need world,
+    /// e.g. to record changes. Defaults to [`Self::try_access`].
+    #[allow(unused_variables)]
+    fn try_access_world(world: &'a World, archetype: &'a Archetype) -> Option<Self::Access> {
+        Self::try_access(archetype)
+    }
+
     #[allow(unused_variables)]
     fn unique_access(output: &mut HashSet<TypeHash>) {}
+
+    #[allow(unused_variables)]
+    fn shared_access(output: &mut HashSet<TypeHash>) {}
+}
+
+pub trait TypedQueryChunkFetch<'a, const LOCKING: bool> {
+    type Value;
+
+    fn does_accept_archetype(archetype: &Archetype) -> bool;
+    fn access(archetype: &'a Archetype) -> Result<Self::Value, QueryError>;
 }
 
 pub trait TypedRelationLookupFetch<'a> {
@@ -295,14 +348,14 @@ impl<const LOCKING: bool> TypedLookupFetch<'_, LOCKING> for () {
 
 impl<'a, const LOCKING: bool> TypedQueryFetch<'a, LOCKING> for Entity {
     type Value = Entity;
-    type Access = Box<dyn Iterator<Item = Entity> + 'a>;
+    type Access = Copied<Iter<'a, Entity>>;
 
     fn does_accept_archetype(_: &Archetype) -> bool {
         true
     }
 
     fn access(archetype: &'a Archetype) -> Result<Self::Access, QueryError> {
-        Ok(Box::new(archetype.entities().iter()))
+        Ok(archetype.entities().as_slice().iter().copied())
     }
 
     fn fetch(access: &mut Self::Access) -> Option<Self::Value> {
@@ -334,19 +387,23 @@ impl<'a, const LOCKING: bool> TypedLookupFetch<'a, LOCKING> for Entity {
 
 impl<'a, const LOCKING: bool, T: Component> TypedQueryFetch<'a, LOCKING> for &'a T {
     type Value = &'a T;
-    type Access = Box<dyn Iterator<Item = &'a T> + 'a>;
+    type Access = ArchetypeColumnReadIter<'a, T>;
 
     fn does_accept_archetype(archetype: &Archetype) -> bool {
         archetype.has_type(TypeHash::of::<T>())
     }
 
     fn access(archetype: &'a Archetype) -> Result<Self::Access, QueryError> {
-        Ok(Box::new(archetype.column_read_iter::<LOCKING, T>()?))
+        Ok(archetype.column_read_iter::<LOCKING, T>()?)
     }
 
     fn fetch(access: &mut Self::Access) -> Option<Self::Value> {
         access.next()
     }
+
+    fn shared_access(output: &mut HashSet<TypeHash>) {
+        output.insert(TypeHash::of::<T>());
+    }
 }
 
 impl<'a, const LOCKING: bool, T: Component> TypedLookupFetch<'a, LOCKING> for &'a T {
@@ -379,18 +436,22 @@ impl<'a, const LOCKING: bool, T: Component> TypedLookupFetch<'a, LOCKING> for &'
     fn fetch_one(world: &'a World, entity: Entity) -> Option<Self::ValueOne> {
         world.component::<LOCKING, T>(entity).ok()
     }
+
+    fn shared_access(output: &mut HashSet<TypeHash>) {
+        output.insert(TypeHash::of::<T>());
+    }
 }
 
 impl<'a, const LOCKING: bool, T: Component> TypedQueryFetch<'a, LOCKING> for &'a mut T {
     type Value = &'a mut T;
-    type Access = Box<dyn Iterator<Item = &'a mut T> + 'a>;
+    type Access = ArchetypeColumnWriteIter<'a, LOCKING, T>;
 
     fn does_accept_archetype(archetype: &Archetype) -> bool {
         archetype.has_type(TypeHash::of::<T>())
     }
 
     fn access(archetype: &'a Archetype) -> Result<Self::Access, QueryError> {
-        Ok(Box::new(archetype.column_write_iter::<LOCKING, T>()?))
+        Ok(archetype.column_write_iter::<LOCKING, T>()?)
     }
 
     fn fetch(access: &mut Self::Access) -> Option<Self::Value> {
@@ -460,6 +521,10 @@ impl<'a, const LOCKING: bool, T: Component> TypedQueryFetch<'a, LOCKING> for Opt
             None => Some(None),
         }
     }
+
+    fn shared_access(output: &mut HashSet<TypeHash>) {
+        output.insert(TypeHash::of::<T>());
+    }
 }
 
 impl<'a, const LOCKING: bool, T: Component> TypedLookupFetch<'a, LOCKING> for Option<&'a T> {
@@ -492,6 +557,10 @@ impl<'a, const LOCKING: bool, T: Component> TypedLookupFetch<'a, LOCKING> for Op
     fn fetch_one(world: &'a World, entity: Entity) -> Option<Self::ValueOne> {
         Some(world.component::<LOCKING, T>(entity).ok())
     }
+
+    fn shared_access(output: &mut HashSet<TypeHash>) {
+        output.insert(TypeHash::of::<T>());
+    }
 }
 
 impl<'a, const LOCKING: bool, T: Component> TypedQueryFetch<'a, LOCKING> for Option<&'a mut T> {
@@ -885,6 +954,107 @@ impl<'a, const LOCKING: bool, T: Component> TypedLookupFetch<'a, LOCKING> for Up
     }
 }
 
+/// Mutable access that marks component as updated on drop.
+///
+/// Mutable dereference is the signal, so component is reported as updated even
+/// if its value was not actually modified. Changes are recorded in the world,
+/// so fetching it from world views fails with [`QueryError::WorldAccessRequired`].
+pub struct AutoNotify<T: Component>(PhantomData<fn() -> T>);
+
+impl<'a, const LOCKING: bool, T: Component> TypedQueryFetch<'a, LOCKING> for AutoNotify<T> {
+    type Value = AutoNotifyComponent<'a, LOCKING, T>;
+    type Access = (
+        &'a World,
+        Box<dyn Iterator<Item = (Entity, &'a mut T)> + 'a>,
+    );
+
+    fn does_accept_archetype(archetype: &Archetype) -> bool {
+        archetype.has_type(TypeHash::of::<T>())
+    }
+
+    fn access(_: &'a Archetype) -> Result<Self::Access, QueryError> {
+        Err(QueryError::WorldAccessRequired {
+            type_hash: TypeHash::of::<T>(),
+        })
+    }
+
+    fn access_world(
+        world: &'a World,
+        archetype: &'a Archetype,
+    ) -> Result<Self::Access, QueryError> {
+        Ok((
+            world,
+            Box::new(
+                archetype
+                    .entities()
+                    .iter()
+                    .zip(archetype.column_write_iter::<LOCKING, T>()?),
+            ),
+        ))
+    }
+
+    fn fetch(access: &mut Self::Access) -> Option<Self::Value> {
+        access.1.next().map(|(entity, inner)| AutoNotifyComponent {
+            entity,
+            world: access.0,
+            inner: AutoNotifyInner::Column(inner),
+            dirty: false,
+        })
+    }
+
+    fn unique_access(output: &mut HashSet<TypeHash>) {
+        output.insert(TypeHash::of::<T>());
+    }
+}
+
+impl<'a, const LOCKING: bool, T: Component> TypedLookupFetch<'a, LOCKING> for AutoNotify<T> {
+    type Value = AutoNotifyComponent<'a, LOCKING, T>;
+    type ValueOne = AutoNotifyComponent<'a, LOCKING, T>;
+    type Access = (
+        &'a World,
+        &'a EntityDenseMap,
+        ArchetypeColumnAccess<'a, LOCKING, T>,
+    );
+
+    fn try_access(_: &'a Archetype) -> Option<Self::Access> {
+        None
+    }
+
+    fn try_access_world(world: &'a World, archetype: &'a Archetype) -> Option<Self::Access> {
+        if archetype.has_type(TypeHash::of::<T>()) {
+            Some((
+                world,
+                archetype.entities(),
+                archetype.column::<LOCKING, T>(true).ok()?,
+            ))
+        } else {
+            None
+        }
+    }
+
+    fn fetch(access: &mut Self::Access, entity: Entity) -> Option<Self::Value> {
+        let index = access.1.index_of(entity)?;
+        access
+            .2
+            .write(index)
+            .map(|value| unsafe { std::mem::transmute::<&mut T, &'a mut T>(value) })
+            .map(|inner| AutoNotifyComponent {
+                entity,
+                world: access.0,
+                inner: AutoNotifyInner::Column(inner),
+                dirty: false,
+            })
+    }
+
+    fn fetch_one(world: &'a World, entity: Entity) -> Option<Self::ValueOne> {
+        world.component_mut_notified::<LOCKING, T>(entity).ok()
+    }
+
+    fn unique_access(output: &mut HashSet<TypeHash>) {
+        output.insert(TypeHash::of::<T>());
+    }
+}
+
 impl<'a> TypedRelationLookupFetch<'a> for () {
     type Value = ();
     type Access = ();
@@ -1037,6 +1207,64 @@ where
     }
 }
 
+pub struct RelatedIncoming<'a, const LOCKING: bool, T, Transform>(
+    PhantomData<fn() -> &'a (T, Transform)>,
+)
+where
+    T: Component,
+    Transform: TypedRelationLookupTransform<'a, Input = Entity>;
+
+impl<'a, const LOCKING: bool, T, Transform> TypedRelationLookupFetch<'a>
+    for RelatedIncoming<'a, LOCKING, T, Transform>
+where
+    T: Component,
+    Transform: TypedRelationLookupTransform<'a, Input = Entity>,
+{
+    type Value = Transform::Output;
+    type Access = Box<dyn Iterator<Item = Self::Value> + 'a>;
+
+    fn access(world: &'a World, entity: Entity) -> Self::Access {
+        Box::new(
+            world
+                .relations_incoming::<LOCKING, T>(entity)
+                .flat_map(|(from, _, _)| Transform::transform(world, from)),
+        )
+    }
+
+    fn fetch(access: &mut Self::Access) -> Option<Self::Value> {
+        access.next()
+    }
+}
+
+pub struct TraverseIncoming<'a, const LOCKING: bool, T, Transform>(
+    PhantomData<fn() -> &'a (T, Transform)>,
+)
+where
+    T: Component,
+    Transform: TypedRelationLookupTransform<'a, Input = Entity>;
+
+impl<'a, const LOCKING: bool, T, Transform> TypedRelationLookupFetch<'a>
+    for TraverseIncoming<'a, LOCKING, T, Transform>
+where
+    T: Component,
+    Transform: TypedRelationLookupTransform<'a, Input = Entity>,
+{
+    type Value = Transform::Output;
+    type Access = Box<dyn Iterator<Item = Self::Value> + 'a>;
+
+    fn access(world: &'a World, entity: Entity) -> Self::Access {
+        Box::new(
+            world
+                .traverse_incoming::<LOCKING, T>([entity])
+                .flat_map(|(_, to)| Transform::transform(world, to)),
+        )
+    }
+
+    fn fetch(access: &mut Self::Access) -> Option<Self::Value> {
+        access.next()
+    }
+}
+
 pub struct Join<'a, A, B>(PhantomData<fn() -> &'a (A, B)>)
 where
     A: TypedRelationLookupFetch<'a>,
@@ -1197,11 +1425,24 @@ macro_rules! impl_typed_query_fetch_tuple {
                 Some(($($type::fetch($type)?,)+))
             }
 
+            fn access_world(
+                world: &'a World,
+                archetype: &'a Archetype,
+            ) -> Result<Self::Access, QueryError> {
+                Ok(($($type::access_world(world, archetype)?,)+))
+            }
+
             fn unique_access(output: &mut HashSet<TypeHash>) {
                 $(
                     $type::unique_access(output);
                 )+
             }
+
+            fn shared_access(output: &mut HashSet<TypeHash>) {
+                $(
+                    $type::shared_access(output);
+                )+
+            }
         }
     };
 }
@@ -1244,11 +1485,21 @@ macro_rules! impl_typed_lookup_fetch_tuple {
                 Some(($($type::fetch_one(world, entity)?,)+))
             }
 
+            fn try_access_world(world: &'a World, archetype: &'a Archetype) -> Option<Self::Access> {
+                Some(($($type::try_access_world(world, archetype)?,)+))
+            }
+
             fn unique_access(output: &mut HashSet<TypeHash>) {
                 $(
                     $type::unique_access(output);
                 )+
             }
+
+            fn shared_access(output: &mut HashSet<TypeHash>) {
+                $(
+                    $type::shared_access(output);
+                )+
+            }
         }
     };
 }
@@ -1270,6 +1521,145 @@ impl_typed_lookup_fetch_tuple!(A, B, C, D, E, F, G, H, I, J, K, L, M, N);
 impl_typed_lookup_fetch_tuple!(A, B, C, D, E, F, G, H, I, J, K, L, M, N, O);
 impl_typed_lookup_fetch_tuple!(A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P);
 
+impl<const LOCKING: bool> TypedQueryChunkFetch<'_, LOCKING> for () {
+    type Value = ();
+
+    fn does_accept_archetype(_: &Archetype) -> bool {
+        true
+    }
+
+    fn access(_: &Archetype) -> Result<Self::Value, QueryError> {
+        Ok(())
+    }
+}
+
+impl<'a, const LOCKING: bool> TypedQueryChunkFetch<'a, LOCKING> for Entity {
+    type Value = &'a [Entity];
+
+    fn does_accept_archetype(_: &Archetype) -> bool {
+        true
+    }
+
+    fn access(archetype: &'a Archetype) -> Result<Self::Value, QueryError> {
+        Ok(archetype.entities().as_slice())
+    }
+}
+
+impl<'a, const LOCKING: bool, T: Component> TypedQueryChunkFetch<'a, LOCKING> for &'a T {
+    type Value = ArchetypeColumnAccess<'a, LOCKING, T>;
+
+    fn does_accept_archetype(archetype: &Archetype) -> bool {
+        archetype.has_type(TypeHash::of::<T>())
+    }
+
+    fn access(archetype: &'a Archetype) -> Result<Self::Value, QueryError> {
+        Ok(archetype.column::<LOCKING, T>(false)?)
+    }
+}
+
+impl<'a, const LOCKING: bool, T: Component> TypedQueryChunkFetch<'a, LOCKING> for &'a mut T {
+    type Value = ArchetypeColumnAccess<'a, LOCKING, T>;
+
+    fn does_accept_archetype(archetype: &Archetype) -> bool {
+        archetype.has_type(TypeHash::of::<T>())
+    }
+
+    fn access(archetype: &'a Archetype) -> Result<Self::Value, QueryError> {
+        Ok(archetype.column::<LOCKING, T>(true)?)
+    }
+}
+
+impl<'a, const LOCKING: bool, T: Component> TypedQueryChunkFetch<'a, LOCKING> for Option<&'a T> {
+    type Value = Option<ArchetypeColumnAccess<'a, LOCKING, T>>;
+
+    fn does_accept_archetype(_: &Archetype) -> bool {
+        true
+    }
+
+    fn access(archetype: &'a Archetype) -> Result<Self::Value, QueryError> {
+        if archetype.has_type(TypeHash::of::<T>()) {
+            Ok(Some(archetype.column::<LOCKING, T>(false)?))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl<'a, const LOCKING: bool, T: Component> TypedQueryChunkFetch<'a, LOCKING>
+    for Option<&'a mut T>
+{
+    type Value = Option<ArchetypeColumnAccess<'a, LOCKING, T>>;
+
+    fn does_accept_archetype(_: &Archetype) -> bool {
+        true
+    }
+
+    fn access(archetype: &'a Archetype) -> Result<Self::Value, QueryError> {
+        if archetype.has_type(TypeHash::of::<T>()) {
+            Ok(Some(archetype.column::<LOCKING, T>(true)?))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl<const LOCKING: bool, T: Component> TypedQueryChunkFetch<'_, LOCKING> for Include<T> {
+    type Value = ();
+
+    fn does_accept_archetype(archetype: &Archetype) -> bool {
+        archetype.has_type(TypeHash::of::<T>())
+    }
+
+    fn access(_: &Archetype) -> Result<Self::Value, QueryError> {
+        Ok(())
+    }
+}
+
+impl<const LOCKING: bool, T: Component> TypedQueryChunkFetch<'_, LOCKING> for Exclude<T> {
+    type Value = ();
+
+    fn does_accept_archetype(archetype: &Archetype) -> bool {
+        !archetype.has_type(TypeHash::of::<T>())
+    }
+
+    fn access(_: &Archetype) -> Result<Self::Value, QueryError> {
+        Ok(())
+    }
+}
+
+macro_rules! impl_typed_query_chunk_fetch_tuple {
+    ($($type:ident),+) => {
+        impl<'a, const LOCKING: bool, $($type: TypedQueryChunkFetch<'a, LOCKING>),+> TypedQueryChunkFetch<'a, LOCKING> for ($($type,)+) {
+            type Value = ($($type::Value,)+);
+
+            fn does_accept_archetype(archetype: &Archetype) -> bool {
+                $($type::does_accept_archetype(archetype))&&+
+            }
+
+            fn access(archetype: &'a Archetype) -> Result<Self::Value, QueryError> {
+                Ok(($($type::access(archetype)?,)+))
+            }
+        }
+    };
+}
+
+impl_typed_query_chunk_fetch_tuple!(A);
+impl_typed_query_chunk_fetch_tuple!(A, B);
+impl_typed_query_chunk_fetch_tuple!(A, B, C);
+impl_typed_query_chunk_fetch_tuple!(A, B, C, D);
+impl_typed_query_chunk_fetch_tuple!(A, B, C, D, E);
+impl_typed_query_chunk_fetch_tuple!(A, B, C, D, E, F);
+impl_typed_query_chunk_fetch_tuple!(A, B, C, D, E, F, G);
+impl_typed_query_chunk_fetch_tuple!(A, B, C, D, E, F, G, H);
+impl_typed_query_chunk_fetch_tuple!(A, B, C, D, E, F, G, H, I);
+impl_typed_query_chunk_fetch_tuple!(A, B, C, D, E, F, G, H, I, J);
+impl_typed_query_chunk_fetch_tuple!(A, B, C, D, E, F, G, H, I, J, K);
+impl_typed_query_chunk_fetch_tuple!(A, B, C, D, E, F, G, H, I, J, K, L);
+impl_typed_query_chunk_fetch_tuple!(A, B, C, D, E, F, G, H, I, J, K, L, M);
+impl_typed_query_chunk_fetch_tuple!(A, B, C, D, E, F, G, H, I, J, K, L, M, N);
+impl_typed_query_chunk_fetch_tuple!(A, B, C, D, E, F, G, H, I, J, K, L, M, N, O);
+impl_typed_query_chunk_fetch_tuple!(A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P);
+
 macro_rules! impl_typed_relation_fetch_tuple {
     ($($type:ident),+) => {
         impl<'a, $($type: TypedRelationLookupFetch<'a>),+> TypedRelationLookupFetch<'a> for ($($type,)+) {
@@ -1306,6 +1696,7 @@ impl_typed_relation_fetch_tuple!(A, B, C, D, E, F, G, H, I, J, K, L, M, O);
 impl_typed_relation_fetch_tuple!(A, B, C, D, E, F, G, H, I, J, K, L, M, O, P);
 
 pub struct TypedQueryIter<'a, const LOCKING: bool, Fetch: TypedQueryFetch<'a, LOCKING>> {
+    world: Option<&'a World>,
     archetypes: Vec<&'a Archetype>,
     index: usize,
     access: Option<Fetch::Access>,
@@ -1317,6 +1708,7 @@ impl<'a, const LOCKING: bool, Fetch: TypedQueryFetch<'a, LOCKING>>
 {
     pub fn new(world: &'a World) -> Self {
         Self {
+            world: Some(world),
             archetypes: world
                 .archetypes()
                 .filter(|archetype| Fetch::does_accept_archetype(archetype))
@@ -1329,6 +1721,7 @@ impl<'a, const LOCKING: bool, Fetch: TypedQueryFetch<'a, LOCKING>>
 
     pub fn new_view(view: &'a WorldView) -> Self {
         Self {
+            world: None,
             archetypes: view
                 .archetypes()
                 .filter(|archetype| Fetch::does_accept_archetype(archetype))
@@ -1359,7 +1752,11 @@ impl<'a, const LOCKING: bool, Fetch: TypedQueryFetch<'a, LOCKING>> Iterator
                 }
                 None => {
                     if let Some(archetype) = self.archetypes.get(self.index) {
-                        self.access = Some(Fetch::access(archetype).unwrap());
+                        let access = match self.world {
+                            Some(world) => Fetch::access_world(world, archetype),
+                            None => Fetch::access(archetype),
+                        };
+                        self.access = Some(access.unwrap());
                     } else {
                         self.index += 1;
                     }
@@ -1371,6 +1768,59 @@ impl<'a, const LOCKING: bool, Fetch: TypedQueryFetch<'a, LOCKING>> Iterator
     }
 }
 
+pub struct TypedQueryChunkIter<'a, const LOCKING: bool, Fetch: TypedQueryChunkFetch<'a, LOCKING>> {
+    archetypes: Vec<&'a Archetype>,
+    index: usize,
+    _phantom: PhantomData<fn() -> Fetch>,
+}
+
+impl<'a, const LOCKING: bool, Fetch: TypedQueryChunkFetch<'a, LOCKING>>
+    TypedQueryChunkIter<'a, LOCKING, Fetch>
+{
+    pub fn new(world: &'a World) -> Self {
+        Self {
+            archetypes: world
+                .archetypes()
+                .filter(|archetype| {
+                    !archetype.is_empty() && Fetch::does_accept_archetype(archetype)
+                })
+                .collect(),
+            index: 0,
+            _phantom: PhantomData,
+        }
+    }
+
+    pub fn new_view(view: &'a WorldView) -> Self {
+        Self {
+            archetypes: view
+                .archetypes()
+                .filter(|archetype| {
+                    !archetype.is_empty() && Fetch::does_accept_archetype(archetype)
+                })
+                .collect(),
+            index: 0,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<'a, const LOCKING: bool, Fetch: TypedQueryChunkFetch<'a, LOCKING>> Iterator
+    for TypedQueryChunkIter<'a, LOCKING, Fetch>
+{
+    type Item = Result<(&'a [Entity], Fetch::Value), QueryError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let archetype = self.archetypes.get(self.index)?;
+        self.index += 1;
+        Some(Fetch::access(archetype).map(|access| (archetype.entities().as_slice(), access)))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let left = self.archetypes.len() - self.index;
+        (left, Some(left))
+    }
+}
+
 pub struct TypedLookupIter<'a, const LOCKING: bool, Fetch: TypedLookupFetch<'a, LOCKING>> {
     access: Vec<Fetch::Access>,
     entities: Box<dyn Iterator<Item = Entity> + 'a>,
@@ -1384,7 +1834,7 @@ impl<'a, const LOCKING: bool, Fetch: TypedLookupFetch<'a, LOCKING>>
         Self {
             access: world
                 .archetypes()
-                .filter_map(|archetype| Fetch::try_access(archetype))
+                .filter_map(|archetype| Fetch::try_access_world(world, archetype))
                 .collect(),
             entities: Box::new(entities.into_iter()),
             _phantom: PhantomData,
@@ -1419,6 +1869,46 @@ impl<'a, const LOCKING: bool, Fetch: TypedLookupFetch<'a, LOCKING>> Iterator
     }
 }
 
+/// Lookup iterator yielding every input entity, with `None` value for
+/// entities not matching fetch, so results stay aligned with input.
+pub struct TypedLookupOptionalIter<'a, const LOCKING: bool, Fetch: TypedLookupFetch<'a, LOCKING>> {
+    access: TypedLookupAccess<'a, LOCKING, Fetch>,
+    entities: Box<dyn Iterator<Item = Entity> + 'a>,
+}
+
+impl<'a, const LOCKING: bool, Fetch: TypedLookupFetch<'a, LOCKING>>
+    TypedLookupOptionalIter<'a, LOCKING, Fetch>
+{
+    pub fn new(world: &'a World, entities: impl IntoIterator<Item = Entity> + 'a) -> Self {
+        Self {
+            access: TypedLookupAccess::new(world),
+            entities: Box::new(entities.into_iter()),
+        }
+    }
+
+    pub fn new_view(view: &'a WorldView, entities: impl IntoIterator<Item = Entity> + 'a) -> Self {
+        Self {
+            access: TypedLookupAccess::new_view(view),
+            entities: Box::new(entities.into_iter()),
+        }
+    }
+}
+
+impl<'a, const LOCKING: bool, Fetch: TypedLookupFetch<'a, LOCKING>> Iterator
+    for TypedLookupOptionalIter<'a, LOCKING, Fetch>
+{
+    type Item = (Entity, Option<Fetch::Value>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let entity = self.entities.next()?;
+        Some((entity, self.access.access(entity)))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.entities.size_hint()
+    }
+}
+
 pub struct TypedLookupAccess<'a, const LOCKING: bool, Fetch: TypedLookupFetch<'a, LOCKING>> {
     access: Vec<Fetch::Access>,
     _phantom: PhantomData<fn() -> Fetch>,
@@ -1431,7 +1921,7 @@ impl<'a, const LOCKING: bool, Fetch: TypedLookupFetch<'a, LOCKING>>
         Self {
             access: world
                 .archetypes()
-                .filter_map(|archetype| Fetch::try_access(archetype))
+                .filter_map(|archetype| Fetch::try_access_world(world, archetype))
                 .collect(),
             _phantom: PhantomData,
         }
@@ -1492,6 +1982,7 @@ enum DynamicQueryFilterMode {
 #[derive(Debug, Default)]
 pub struct DynamicQueryFilter {
     filter: HashMap<TypeHash, DynamicQueryFilterMode>,
+    or_groups: Vec<Vec<TypeHash>>,
 }
 
 impl DynamicQueryFilter {
@@ -1525,6 +2016,7 @@ impl DynamicQueryFilter {
                         .map(|type_hash| (type_hash, DynamicQueryFilterMode::Exclude)),
                 )
                 .collect(),
+            or_groups: Default::default(),
         }
     }
 
@@ -1566,13 +2058,23 @@ impl DynamicQueryFilter {
         self
     }
 
+    pub fn or_group(mut self, includes: &[TypeHash]) -> Self {
+        if !includes.is_empty() {
+            self.or_groups.push(includes.to_vec());
+        }
+        self
+    }
+
     pub fn does_accept_archetype(&self, archetype: &Archetype) -> bool {
         self.filter.iter().all(|(type_hash, mode)| match mode {
             DynamicQueryFilterMode::Read
             | DynamicQueryFilterMode::Write
             | DynamicQueryFilterMode::Include => archetype.has_type(*type_hash),
             DynamicQueryFilterMode::Exclude => !archetype.has_type(*type_hash),
-        })
+        }) && self
+            .or_groups
+            .iter()
+            .all(|group| group.iter().any(|type_hash| archetype.has_type(*type_hash)))
     }
 
     fn columns(&self) -> Vec<(TypeHash, bool)> {
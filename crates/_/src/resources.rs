@@ -42,6 +42,14 @@ impl Resources {
         Ok(())
     }
 
+    pub(crate) fn world(&self) -> &World {
+        &self.world
+    }
+
+    pub(crate) fn entity(&self) -> Entity {
+        self.entity
+    }
+
     pub fn clear(&mut self) {
         self.world.clear();
         self.entity = self.world.spawn(((),)).unwrap();
@@ -84,6 +92,22 @@ impl Resources {
         Ok(self.world.component_mut(self.entity)?)
     }
 
+    pub fn replace<const LOCKING: bool, T: Component>(
+        &mut self,
+        value: T,
+    ) -> Result<Option<T>, Box<dyn Error>> {
+        if !self.world.has_entity_component::<T>(self.entity) {
+            self.world.insert(self.entity, (value,))?;
+            return Ok(None);
+        }
+        let old = std::mem::replace(
+            &mut *self.world.component_mut::<LOCKING, T>(self.entity)?,
+            value,
+        );
+        self.world.update::<T>(self.entity);
+        Ok(Some(old))
+    }
+
     pub fn get<const LOCKING: bool, T: Component>(
         &'_ self,
     ) -> Result<ComponentRef<'_, LOCKING, T>, Box<dyn Error>> {
@@ -96,6 +120,14 @@ impl Resources {
         Ok(self.world.component_mut(self.entity)?)
     }
 
+    /// Fetches multiple resources at once, acquiring their borrows ordered
+    /// by type hash, so concurrent batched fetches never deadlock each other.
+    pub fn get_many<'a, const LOCKING: bool, Fetch: ResourceFetchMany<'a, LOCKING>>(
+        &'a self,
+    ) -> Result<Fetch::Value, Box<dyn Error>> {
+        Fetch::fetch_many(self)
+    }
+
     pub fn query<'a, const LOCKING: bool, Fetch: TypedQueryFetch<'a, LOCKING>>(
         &'a self,
     ) -> TypedQueryIter<'a, LOCKING, Fetch> {
@@ -114,3 +146,143 @@ impl Resources {
         self.world.lookup_one::<LOCKING, Fetch>(self.entity)
     }
 }
+
+pub trait ResourceFetch<'a, const LOCKING: bool> {
+    type Value;
+
+    fn type_hash() -> TypeHash;
+
+    fn fetch(resources: &'a Resources) -> Result<Self::Value, Box<dyn Error>>;
+}
+
+impl<'a, const LOCKING: bool, T: Component> ResourceFetch<'a, LOCKING> for &'a T {
+    type Value = ComponentRef<'a, LOCKING, T>;
+
+    fn type_hash() -> TypeHash {
+        TypeHash::of::<T>()
+    }
+
+    fn fetch(resources: &'a Resources) -> Result<Self::Value, Box<dyn Error>> {
+        resources.get::<LOCKING, T>()
+    }
+}
+
+impl<'a, const LOCKING: bool, T: Component> ResourceFetch<'a, LOCKING> for &'a mut T {
+    type Value = ComponentRefMut<'a, LOCKING, T>;
+
+    fn type_hash() -> TypeHash {
+        TypeHash::of::<T>()
+    }
+
+    fn fetch(resources: &'a Resources) -> Result<Self::Value, Box<dyn Error>> {
+        resources.get_mut::<LOCKING, T>()
+    }
+}
+
+pub trait ResourceFetchMany<'a, const LOCKING: bool> {
+    type Value;
+
+    fn fetch_many(resources: &'a Resources) -> Result<Self::Value, Box<dyn Error>>;
+}
+
+macro_rules! impl_resource_fetch_many_tuple {
+    ($($type:ident : $index:tt),+) => {
+        impl<'a, const LOCKING: bool, $($type: ResourceFetch<'a, LOCKING>),+>
+            ResourceFetchMany<'a, LOCKING> for ($($type,)+)
+        {
+            type Value = ($($type::Value,)+);
+
+            #[allow(non_snake_case)]
+            fn fetch_many(resources: &'a Resources) -> Result<Self::Value, Box<dyn Error>> {
+                let mut order = [$(($index, $type::type_hash())),+];
+                order.sort_by_key(|(_, type_hash)| *type_hash);
+                if order.windows(2).any(|pair| pair[0].1 == pair[1].1) {
+                    return Err("Resources fetch contains duplicate types".into());
+                }
+                $(
+                    let mut $type = None;
+                )+
+                for (index, _) in order {
+                    match index {
+                        $(
+                            $index => $type = Some($type::fetch(resources)?),
+                        )+
+                        _ => unreachable!(),
+                    }
+                }
+                Ok(($($type.unwrap(),)+))
+            }
+        }
+    };
+}
+
+impl_resource_fetch_many_tuple!(A: 0);
+impl_resource_fetch_many_tuple!(A: 0, B: 1);
+impl_resource_fetch_many_tuple!(A: 0, B: 1, C: 2);
+impl_resource_fetch_many_tuple!(A: 0, B: 1, C: 2, D: 3);
+impl_resource_fetch_many_tuple!(A: 0, B: 1, C: 2, D: 3, E: 4);
+impl_resource_fetch_many_tuple!(A: 0, B: 1, C: 2, D: 3, E: 4, F: 5);
+impl_resource_fetch_many_tuple!(A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6);
+impl_resource_fetch_many_tuple!(A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6, H: 7);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resources_replace() {
+        let mut resources = Resources::default();
+        assert_eq!(resources.replace::<true, _>(1usize).unwrap(), None);
+        assert!(resources.added().has_component::<usize>());
+        resources.clear_changes();
+
+        assert_eq!(resources.replace::<true, _>(2usize).unwrap(), Some(1));
+        assert_eq!(*resources.get::<true, usize>().unwrap(), 2);
+        assert!(resources.did_changed::<usize>());
+        assert!(resources.updated().unwrap().has_component::<usize>());
+    }
+
+    #[test]
+    fn test_resources_get_many() {
+        let mut resources = Resources::default();
+        resources.add((1usize, 2u8, "hello".to_owned())).unwrap();
+
+        {
+            let (a, mut b, c) = resources
+                .get_many::<true, (&usize, &mut u8, &String)>()
+                .unwrap();
+            *b += *a as u8;
+            assert_eq!(c.as_str(), "hello");
+        }
+        assert_eq!(*resources.get::<true, u8>().unwrap(), 3);
+        assert!(resources.get_many::<true, (&usize, &u32)>().is_err());
+        assert!(resources.get_many::<true, (&usize, &mut usize)>().is_err());
+
+        std::thread::scope(|scope| {
+            let resources = &resources;
+            let handles = (0..4)
+                .map(|index| {
+                    scope.spawn(move || {
+                        for _ in 0..1000 {
+                            if index % 2 == 0 {
+                                let (mut a, mut b) =
+                                    resources.get_many::<true, (&mut usize, &mut u8)>().unwrap();
+                                *a += 1;
+                                *b = b.wrapping_add(1);
+                            } else {
+                                let (mut b, mut a) =
+                                    resources.get_many::<true, (&mut u8, &mut usize)>().unwrap();
+                                *a += 1;
+                                *b = b.wrapping_add(1);
+                            }
+                        }
+                    })
+                })
+                .collect::<Vec<_>>();
+            for handle in handles {
+                handle.join().unwrap();
+            }
+        });
+        assert_eq!(*resources.get::<true, usize>().unwrap(), 4001);
+    }
+}
@@ -11,14 +11,16 @@ use crate::{
 };
 use intuicio_data::managed::DynamicManaged;
 use moirai::jobs::{JobLocation, Jobs, ScopedJobs};
+use serde::{Deserialize, Serialize};
 use std::{
     borrow::Cow,
     collections::HashSet,
     error::Error,
     ops::{Deref, Range},
+    sync::Mutex,
 };
 
-#[derive(Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub struct SystemName(Cow<'static, str>);
 
 impl SystemName {
@@ -78,7 +80,7 @@ pub struct SystemOrder(pub usize);
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
 pub struct SystemGroupChild;
 
-#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum SystemParallelize {
     #[default]
     AnyWorker,
@@ -142,6 +144,50 @@ impl Iterator for SystemSubstepsIter {
     }
 }
 
+/// Execution plan of systems graph, computed without running any system.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SchedulePlan {
+    pub nodes: Vec<SchedulePlanNode>,
+}
+
+impl SchedulePlan {
+    pub fn find(&self, name: &str) -> Option<&SchedulePlanNode> {
+        self.nodes.iter().find_map(|node| node.find(name))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SchedulePlanNode {
+    pub entity: Entity,
+    pub name: Option<SystemName>,
+    pub parallelize: Option<SystemParallelize>,
+    /// Whether node is reached and its run condition passes at plan time.
+    pub will_run: bool,
+    pub children: Vec<SchedulePlanNode>,
+}
+
+impl SchedulePlanNode {
+    pub fn find(&self, name: &str) -> Option<&Self> {
+        if self.name.as_deref() == Some(name) {
+            return Some(self);
+        }
+        self.children.iter().find_map(|node| node.find(name))
+    }
+}
+
+struct SchedulerBudget {
+    duration: Duration,
+    timer: Instant,
+    priority_threshold: SystemPriority,
+    skipped: Mutex<Vec<Entity>>,
+}
+
+impl SchedulerBudget {
+    fn should_skip(&self, priority: SystemPriority) -> bool {
+        priority < self.priority_threshold && self.timer.elapsed() >= self.duration
+    }
+}
+
 #[derive(Default)]
 pub struct GraphScheduler<const LOCKING: bool>;
 
@@ -163,6 +209,90 @@ impl<const LOCKING: bool> GraphScheduler<LOCKING> {
         Ok(())
     }
 
+    /// Runs systems graph like [`Self::run`] does, but once `budget` elapses,
+    /// remaining systems (along with their subtrees) with priority lower than
+    /// `priority_threshold` are skipped. Returns skipped systems.
+    pub fn run_budgeted(
+        &self,
+        jobs: &Jobs,
+        universe: &mut Universe,
+        budget: Duration,
+        priority_threshold: SystemPriority,
+    ) -> Result<Vec<Entity>, Box<dyn Error>> {
+        let systems = Self::collect_roots(&universe.systems);
+        let mut visited = HashSet::with_capacity(universe.systems.len());
+        Self::validate_no_cycles(universe, systems.iter().copied(), &mut visited)?;
+        let budget = SchedulerBudget {
+            duration: budget,
+            timer: Instant::now(),
+            priority_threshold,
+            skipped: Default::default(),
+        };
+        self.run_group(
+            jobs,
+            universe,
+            systems.into_iter(),
+            SystemSubsteps::default(),
+            Some(&budget),
+        )?;
+        Self::maintenance(jobs, universe);
+        Ok(budget.skipped.into_inner().unwrap_or_default())
+    }
+
+    /// Walks systems graph the same way [`Self::run`] does, evaluating run
+    /// conditions but executing nothing.
+    pub fn plan(&self, universe: &Universe) -> Result<SchedulePlan, Box<dyn Error>> {
+        let roots = Self::collect_roots(&universe.systems);
+        let mut visited = HashSet::with_capacity(universe.systems.len());
+        Self::validate_no_cycles(universe, roots.iter().copied(), &mut visited)?;
+        Ok(SchedulePlan {
+            nodes: self.plan_group(universe, roots.into_iter(), true),
+        })
+    }
+
+    fn plan_group(
+        &self,
+        universe: &Universe,
+        entities: impl Iterator<Item = Entity>,
+        reached: bool,
+    ) -> Vec<SchedulePlanNode> {
+        Self::ordered(universe, entities)
+            .into_iter()
+            .map(|entity| {
+                let context = SystemContext::new(universe, entity);
+                let will_run = reached
+                    && match universe.systems.component::<LOCKING, SystemObject>(entity) {
+                        Ok(system) => system.should_run(context),
+                        Err(_) => universe
+                            .systems
+                            .component::<LOCKING, SystemRunCondition>(entity)
+                            .map(|condition| condition.evaluate(context))
+                            .unwrap_or(true),
+                    };
+                let children = universe
+                    .systems
+                    .relations_outgoing::<LOCKING, SystemGroupChild>(entity)
+                    .map(|(_, _, entity)| entity)
+                    .collect::<Vec<_>>();
+                SchedulePlanNode {
+                    entity,
+                    name: universe
+                        .systems
+                        .component::<LOCKING, SystemName>(entity)
+                        .ok()
+                        .map(|name| name.clone()),
+                    parallelize: universe
+                        .systems
+                        .component::<LOCKING, SystemParallelize>(entity)
+                        .ok()
+                        .map(|parallelize| parallelize.clone()),
+                    will_run,
+                    children: self.plan_group(universe, children.into_iter(), will_run),
+                }
+            })
+            .collect()
+    }
+
     pub fn run_systems(
         &self,
         jobs: &Jobs,
@@ -179,7 +309,7 @@ impl<const LOCKING: bool> GraphScheduler<LOCKING> {
         .entered();
         let mut visited = HashSet::with_capacity(universe.systems.len());
         Self::validate_no_cycles(universe, systems.iter().copied(), &mut visited)?;
-        self.run_group(jobs, universe, systems.into_iter(), substeps)?;
+        self.run_group(jobs, universe, systems.into_iter(), substeps, None)?;
         Ok(())
     }
 
@@ -199,7 +329,7 @@ impl<const LOCKING: bool> GraphScheduler<LOCKING> {
         .entered();
         let mut visited = HashSet::with_capacity(universe.systems.len());
         Self::validate_no_cycles(universe, std::iter::once(system), &mut visited)?;
-        self.run_group(jobs, universe, std::iter::once(system), substeps)?;
+        self.run_group(jobs, universe, std::iter::once(system), substeps, None)?;
         Ok(())
     }
 
@@ -209,8 +339,22 @@ impl<const LOCKING: bool> GraphScheduler<LOCKING> {
         universe: &'env Universe,
         entity: Entity,
         scoped_jobs: &mut ScopedJobs<'env, Result<(), String>>,
+        budget: Option<&'env SchedulerBudget>,
     ) -> Result<(), Box<dyn Error>> {
         let job = move || -> Result<(), String> {
+            if let Some(budget) = budget {
+                let priority = universe
+                    .systems
+                    .component::<LOCKING, SystemPriority>(entity)
+                    .map(|priority| *priority)
+                    .unwrap_or_default();
+                if budget.should_skip(priority) {
+                    if let Ok(mut skipped) = budget.skipped.lock() {
+                        skipped.push(entity);
+                    }
+                    return Ok(());
+                }
+            }
             if let Ok(system) = universe.systems.component::<LOCKING, SystemObject>(entity)
                 && system.should_run(SystemContext::new(universe, entity))
             {
@@ -265,7 +409,7 @@ impl<const LOCKING: bool> GraphScheduler<LOCKING> {
                     .map(|name| name.to_string()),
             )
             .entered();
-            self.run_group(jobs, universe, group_children.entities(), substeps)
+            self.run_group(jobs, universe, group_children.entities(), substeps, budget)
                 .map_err(|error| format!("{error}"))?;
             Ok(())
         };
@@ -290,7 +434,25 @@ impl<const LOCKING: bool> GraphScheduler<LOCKING> {
         universe: &Universe,
         entities: impl Iterator<Item = Entity>,
         substeps: SystemSubsteps,
+        budget: Option<&SchedulerBudget>,
     ) -> Result<(), Box<dyn Error>> {
+        let ordered = Self::ordered(universe, entities);
+        for _ in substeps.iter() {
+            let (output, result) = jobs.scope::<_, Result<(), Box<dyn Error>>>(|scope| {
+                for entity in ordered.iter().copied() {
+                    self.run_node(jobs, universe, entity, scope, budget)?;
+                }
+                Ok(())
+            });
+            result?;
+            for result in output {
+                result?;
+            }
+        }
+        Ok(())
+    }
+
+    fn ordered(universe: &Universe, entities: impl Iterator<Item = Entity>) -> Vec<Entity> {
         let mut ordered = entities
             .map(|entity| {
                 let priority = universe
@@ -314,20 +476,7 @@ impl<const LOCKING: bool> GraphScheduler<LOCKING> {
                 .reverse()
                 .then(order_a.cmp(order_b))
         });
-
-        for _ in substeps.iter() {
-            let (output, result) = jobs.scope::<_, Result<(), Box<dyn Error>>>(|scope| {
-                for (entity, _, _) in ordered.iter().copied() {
-                    self.run_node(jobs, universe, entity, scope)?;
-                }
-                Ok(())
-            });
-            result?;
-            for result in output {
-                result?;
-            }
-        }
-        Ok(())
+        ordered.into_iter().map(|(entity, _, _)| entity).collect()
     }
 
     fn validate_no_cycles(
@@ -436,6 +585,8 @@ impl<const LOCKING: bool> GraphSchedulerPlugin<LOCKING> {
         self.local(SystemInjectInto::new(name))
     }
 
+    /// Gates whole plugin group subtree on condition - when it evaluates to
+    /// false, none of group systems and nested groups run.
     pub fn condition<T: UniverseCondition>(self) -> Self {
         self.local(SystemRunCondition::new::<T>())
     }
@@ -674,4 +825,325 @@ mod tests {
         assert!(systems.has_relation::<true, SystemGroupChild>(c, d));
         assert!(systems.has_relation::<true, SystemGroupChild>(d, e));
     }
+
+    #[test]
+    fn test_systems_leaf_systems() {
+        let mut world = World::default();
+        let mut systems = Systems::default();
+        let mut resources = Resources::default();
+
+        fn noop(_: SystemContext) -> Result<(), Box<dyn Error>> {
+            Ok(())
+        }
+
+        let plugin = GraphSchedulerPlugin::<true>::default()
+            .name("a")
+            .plugin_setup(|plugin| {
+                plugin
+                    .name("b")
+                    .system_setup(noop, |system| system.name("c"))
+                    .system_setup(noop, |system| system.name("d"))
+            })
+            .system_setup(noop, |system| system.name("e"))
+            .system_setup(noop, |system| system.name("f"))
+            .system_setup(noop, |system| system.name("g").inject_into("a/f"));
+        plugin.install(&mut world, &mut systems, &mut resources);
+
+        let mut leaves = systems
+            .leaf_systems::<true>()
+            .map(|entity| {
+                systems
+                    .component::<true, SystemName>(entity)
+                    .unwrap()
+                    .to_string()
+            })
+            .collect::<Vec<_>>();
+        leaves.sort();
+        assert_eq!(leaves, vec!["c", "d", "e", "g"]);
+    }
+
+    #[test]
+    fn test_systems_find_all_with() {
+        let mut systems = Systems::default();
+
+        fn noop(_: SystemContext) -> Result<(), Box<dyn Error>> {
+            Ok(())
+        }
+
+        let mut expected = ["debug_a", "debug_b", "debug_c"]
+            .into_iter()
+            .map(|name| systems.add(noop, (SystemName::new(name),)).unwrap())
+            .collect::<Vec<_>>();
+        systems.add(noop, (SystemName::new("game"),)).unwrap();
+        systems.add(noop, ()).unwrap();
+
+        let mut found =
+            systems.find_all_with::<true, SystemName>(|name| name.as_str().starts_with("debug_"));
+        found.sort();
+        expected.sort();
+        assert_eq!(found, expected);
+        assert!(
+            systems
+                .find_all_with::<true, SystemName>(|name| name.as_str() == "missing")
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_graph_scheduler_plugin_group_condition() {
+        use crate::universe::Res;
+        use moirai::jobs::Jobs;
+
+        #[derive(Default)]
+        struct Counter(usize);
+
+        struct Enabled;
+
+        impl UniverseCondition for Enabled {
+            fn evaluate(_: SystemContext) -> bool {
+                true
+            }
+        }
+
+        struct Disabled;
+
+        impl UniverseCondition for Disabled {
+            fn evaluate(_: SystemContext) -> bool {
+                false
+            }
+        }
+
+        fn count(context: SystemContext) -> Result<(), Box<dyn Error>> {
+            context.fetch::<Res<true, &mut Counter>>()?.0 += 1;
+            Ok(())
+        }
+
+        let mut universe = Universe::default()
+            .with_resource(Counter::default())
+            .unwrap()
+            .with_plugin(
+                GraphSchedulerPlugin::<true>::default()
+                    .plugin_setup(|plugin| {
+                        plugin
+                            .name("enabled")
+                            .condition::<Enabled>()
+                            .system_setup(count, |system| system.name("a"))
+                    })
+                    .plugin_setup(|plugin| {
+                        plugin
+                            .name("disabled")
+                            .condition::<Disabled>()
+                            .system_setup(count, |system| system.name("b"))
+                            .system_setup(count, |system| system.name("c"))
+                            .plugin_setup(|plugin| {
+                                plugin
+                                    .name("nested")
+                                    .system_setup(count, |system| system.name("d"))
+                            })
+                    }),
+            );
+
+        let jobs = Jobs::default();
+        GraphScheduler::<true>.run(&jobs, &mut universe).unwrap();
+        assert_eq!(universe.resources.get::<true, Counter>().unwrap().0, 1);
+    }
+
+    #[test]
+    fn test_system_context_name() {
+        use crate::universe::Res;
+        use moirai::jobs::Jobs;
+
+        #[derive(Default)]
+        struct Names(Vec<(Entity, Option<SystemName>)>);
+
+        fn report(context: SystemContext) -> Result<(), Box<dyn Error>> {
+            context
+                .fetch::<Res<true, &mut Names>>()?
+                .0
+                .push((context.entity(), context.system_name()));
+            Ok(())
+        }
+
+        let mut universe = Universe::default()
+            .with_resource(Names::default())
+            .unwrap()
+            .with_plugin(
+                GraphSchedulerPlugin::<true>::default()
+                    .system_setup(report, |system| system.name("report")),
+            );
+        let entity = universe
+            .systems
+            .find_with::<true, SystemName>(|name| name.as_str() == "report")
+            .unwrap();
+
+        let jobs = Jobs::default();
+        GraphScheduler::<true>.run(&jobs, &mut universe).unwrap();
+        assert_eq!(
+            universe.resources.get::<true, Names>().unwrap().0,
+            vec![(entity, Some(SystemName::new("report")))]
+        );
+        assert_eq!(SystemContext::new_unknown(&universe).system_name(), None);
+    }
+
+    #[test]
+    fn test_graph_scheduler_run_budgeted() {
+        use crate::universe::Res;
+        use moirai::jobs::Jobs;
+
+        #[derive(Default)]
+        struct Executed(Vec<String>);
+
+        fn record(context: SystemContext) -> Result<(), Box<dyn Error>> {
+            let name = context.system_name().unwrap().to_string();
+            context.fetch::<Res<true, &mut Executed>>()?.0.push(name);
+            Ok(())
+        }
+
+        fn slow(context: SystemContext) -> Result<(), Box<dyn Error>> {
+            std::thread::sleep(Duration::from_millis(10));
+            record(context)
+        }
+
+        let mut universe = Universe::default()
+            .with_resource(Executed::default())
+            .unwrap()
+            .with_plugin(
+                GraphSchedulerPlugin::<true>::default()
+                    .name("root")
+                    .system_setup(slow, |system| system.name("slow").local(SystemPriority(10)))
+                    .system_setup(record, |system| {
+                        system.name("important").local(SystemPriority(5))
+                    })
+                    .system_setup(record, |system| system.name("optional"))
+                    .plugin_setup(|plugin| {
+                        plugin
+                            .name("cosmetics")
+                            .system_setup(record, |system| system.name("particles"))
+                    }),
+            );
+        let names = |universe: &Universe, entities: &[Entity]| {
+            let mut result = entities
+                .iter()
+                .map(|entity| {
+                    universe
+                        .systems
+                        .component::<true, SystemName>(*entity)
+                        .unwrap()
+                        .to_string()
+                })
+                .collect::<Vec<_>>();
+            result.sort();
+            result
+        };
+
+        let jobs = Jobs::default();
+        let skipped = GraphScheduler::<true>
+            .run_budgeted(
+                &jobs,
+                &mut universe,
+                Duration::from_millis(1),
+                SystemPriority(1),
+            )
+            .unwrap();
+        assert_eq!(names(&universe, &skipped), vec!["cosmetics", "optional"]);
+        assert_eq!(
+            universe.resources.get::<true, Executed>().unwrap().0,
+            vec!["slow", "important"]
+        );
+
+        universe
+            .resources
+            .get_mut::<true, Executed>()
+            .unwrap()
+            .0
+            .clear();
+        let skipped = GraphScheduler::<true>
+            .run_budgeted(
+                &jobs,
+                &mut universe,
+                Duration::from_secs(60),
+                SystemPriority(1),
+            )
+            .unwrap();
+        assert!(skipped.is_empty());
+        assert_eq!(
+            universe.resources.get::<true, Executed>().unwrap().0.len(),
+            4
+        );
+    }
+
+    #[test]
+    fn test_graph_scheduler_plan() {
+        use crate::universe::Res;
+        use moirai::jobs::Jobs;
+
+        #[derive(Default)]
+        struct Executed(Vec<String>);
+
+        struct Disabled;
+
+        impl UniverseCondition for Disabled {
+            fn evaluate(_: SystemContext) -> bool {
+                false
+            }
+        }
+
+        fn record(context: SystemContext) -> Result<(), Box<dyn Error>> {
+            let name = context.system_name().unwrap().to_string();
+            context.fetch::<Res<true, &mut Executed>>()?.0.push(name);
+            Ok(())
+        }
+
+        let mut universe = Universe::default()
+            .with_resource(Executed::default())
+            .unwrap()
+            .with_plugin(
+                GraphSchedulerPlugin::<true>::default()
+                    .name("root")
+                    .system_setup(record, |system| system.name("a"))
+                    .system_setup(record, |system| system.name("b"))
+                    .system_setup(record, |system| {
+                        system.name("p").local(SystemParallelize::AnyWorker)
+                    })
+                    .plugin_setup(|plugin| {
+                        plugin
+                            .name("disabled")
+                            .condition::<Disabled>()
+                            .system_setup(record, |system| system.name("c"))
+                    }),
+            );
+
+        let plan = GraphScheduler::<true>.plan(&universe).unwrap();
+        assert_eq!(plan.nodes.len(), 1);
+        assert!(plan.find("root").unwrap().will_run);
+        assert!(plan.find("a").unwrap().will_run);
+        assert!(plan.find("b").unwrap().will_run);
+        assert_eq!(
+            plan.find("p").unwrap().parallelize,
+            Some(SystemParallelize::AnyWorker)
+        );
+        assert!(!plan.find("disabled").unwrap().will_run);
+        assert!(!plan.find("c").unwrap().will_run);
+
+        let planned = plan
+            .find("root")
+            .unwrap()
+            .children
+            .iter()
+            .filter(|node| node.will_run && node.parallelize.is_none())
+            .map(|node| node.name.as_deref().unwrap().to_owned())
+            .collect::<Vec<_>>();
+        let jobs = Jobs::default();
+        GraphScheduler::<true>.run(&jobs, &mut universe).unwrap();
+        let executed = universe
+            .resources
+            .get::<true, Executed>()
+            .unwrap()
+            .0
+            .iter()
+            .filter(|name| name.as_str() != "p")
+            .cloned()
+            .collect::<Vec<_>>();
+        assert_eq!(planned, executed);
+    }
 }
@@ -2,9 +2,10 @@ use crate::{
     bundle::Bundle,
     component::Component,
     entity::Entity,
-    query::{TypedLookupAccess, TypedLookupFetch, TypedQueryFetch, TypedQueryIter},
+    query::{Include, TypedLookupAccess, TypedLookupFetch, TypedQueryFetch, TypedQueryIter},
+    scheduler::{SystemGroupChild, SystemName},
     universe::{Res, Universe, UniverseCondition, UniverseFetch},
-    world::{World, WorldError},
+    world::{Relation, World, WorldError},
 };
 use intuicio_core::{
     context::Context,
@@ -42,6 +43,14 @@ impl<'a> SystemContext<'a> {
         self.entity
     }
 
+    pub fn system_name(&self) -> Option<SystemName> {
+        self.universe
+            .systems
+            .component::<true, SystemName>(self.entity)
+            .ok()
+            .map(|name| name.clone())
+    }
+
     pub fn fetch<Fetch: UniverseFetch<'a>>(&'a self) -> Result<Fetch::Value, Box<dyn Error>> {
         Fetch::fetch(self.universe, self.entity)
     }
@@ -314,4 +323,22 @@ impl Systems {
     ) -> Option<Entity> {
         self.world.find_with::<LOCKING, T>(f)
     }
+
+    pub fn find_all_with<const LOCKING: bool, T: Component>(
+        &self,
+        f: impl Fn(&T) -> bool,
+    ) -> Vec<Entity> {
+        self.world.find_all_with::<LOCKING, T>(f)
+    }
+
+    pub fn leaf_systems<const LOCKING: bool>(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.world
+            .query::<LOCKING, (
+                Entity,
+                Option<&Relation<SystemGroupChild>>,
+                Include<SystemObject>,
+            )>()
+            .filter(|(_, children, _)| children.is_none_or(|children| children.is_empty()))
+            .map(|(entity, _, _)| entity)
+    }
 }
@@ -6,12 +6,38 @@ use crate::{
     processor::WorldProcessor,
     query::{Lookup, Query, TypedLookupFetch, TypedQueryFetch},
     resources::Resources,
+    scheduler::GraphSchedulerPlugin,
     systems::{System, SystemContext, Systems},
-    world::World,
+    world::{World, WorldChanges},
 };
 use intuicio_core::{context::Context, registry::Registry};
+use intuicio_data::type_hash::TypeHash;
 use intuicio_framework_serde::SerializationRegistry;
-use std::{error::Error, marker::PhantomData};
+use std::{borrow::Cow, error::Error, marker::PhantomData};
+
+/// Error of single tuple member failing to fetch, naming which one it was.
+#[derive(Debug)]
+pub struct UniverseFetchError {
+    pub index: usize,
+    pub member: &'static str,
+    pub error: Box<dyn Error>,
+}
+
+impl Error for UniverseFetchError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&*self.error)
+    }
+}
+
+impl std::fmt::Display for UniverseFetchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "fetch member {} ({}) failed: {}",
+            self.index, self.member, self.error
+        )
+    }
+}
 
 pub trait UniverseFetch<'a> {
     type Value;
@@ -154,8 +180,18 @@ macro_rules! impl_universe_fetch_tuple {
         impl<'a, $($type: UniverseFetch<'a>),+> UniverseFetch<'a> for ($($type,)+) {
             type Value = ($($type::Value,)+);
 
+            #[allow(unused_assignments)]
             fn fetch(universe: &'a Universe, entity: Entity) -> Result<Self::Value, Box<dyn Error>> {
-                Ok(($($type::fetch(universe, entity)?,)+))
+                let mut index = 0;
+                Ok(($({
+                    let value = $type::fetch(universe, entity).map_err(|error| UniverseFetchError {
+                        index,
+                        member: std::any::type_name::<$type>(),
+                        error,
+                    })?;
+                    index += 1;
+                    value
+                },)+))
             }
         }
     };
@@ -312,6 +348,102 @@ impl<T: Component> UniverseCondition for SystemLocalUpdated<T> {
     }
 }
 
+/// Change sets of single world accumulated across scheduler runs.
+#[derive(Default, Clone)]
+pub struct DeferredWorldChanges {
+    pub added: WorldChanges,
+    pub removed: WorldChanges,
+    pub updated: WorldChanges,
+}
+
+impl DeferredWorldChanges {
+    pub fn append(
+        &mut self,
+        added: &WorldChanges,
+        removed: &WorldChanges,
+        updated: Option<&WorldChanges>,
+    ) {
+        self.added.append(added);
+        self.removed.append(removed);
+        if let Some(updated) = updated {
+            self.updated.append(updated);
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.added.clear();
+        self.removed.clear();
+        self.updated.clear();
+    }
+
+    pub fn component_did_changed<T>(&self) -> bool {
+        self.component_did_changed_raw(TypeHash::of::<T>())
+    }
+
+    pub fn component_did_changed_raw(&self, type_hash: TypeHash) -> bool {
+        self.added.has_component_raw(type_hash)
+            || self.removed.has_component_raw(type_hash)
+            || self.updated.has_component_raw(type_hash)
+    }
+
+    pub fn entity_component_did_changed<T>(&self, entity: Entity) -> bool {
+        self.entity_component_did_changed_raw(entity, TypeHash::of::<T>())
+    }
+
+    pub fn entity_component_did_changed_raw(&self, entity: Entity, type_hash: TypeHash) -> bool {
+        self.added.has_entity_component_raw(entity, type_hash)
+            || self.removed.has_entity_component_raw(entity, type_hash)
+            || self.updated.has_entity_component_raw(entity, type_hash)
+    }
+}
+
+/// Simulation and resources change sets that survive scheduler runs.
+///
+/// When present as resource, [`Universe::clear_changes`] appends current
+/// change sets here before clearing them, so systems running at slower
+/// cadence than scheduler (fixed-step stages) do not miss changes made
+/// between their runs. It is up to these systems to clear it on their own
+/// cadence once changes are processed.
+#[derive(Default, Clone)]
+pub struct DeferredChanges {
+    pub simulation: DeferredWorldChanges,
+    pub resources: DeferredWorldChanges,
+}
+
+impl DeferredChanges {
+    pub fn clear(&mut self) {
+        self.simulation.clear();
+        self.resources.clear();
+    }
+}
+
+pub struct DeferredResourceDidChanged<T: Component>(PhantomData<fn() -> T>);
+
+impl<T: Component> UniverseCondition for DeferredResourceDidChanged<T> {
+    fn evaluate(context: SystemContext) -> bool {
+        let resources = &context.universe.resources;
+        resources.did_changed::<T>()
+            || resources
+                .get::<true, DeferredChanges>()
+                .map(|changes| changes.resources.component_did_changed::<T>())
+                .unwrap_or_default()
+    }
+}
+
+pub struct DeferredComponentDidChanged<T: Component>(PhantomData<fn() -> T>);
+
+impl<T: Component> UniverseCondition for DeferredComponentDidChanged<T> {
+    fn evaluate(context: SystemContext) -> bool {
+        context.universe.simulation.component_did_changed::<T>()
+            || context
+                .universe
+                .resources
+                .get::<true, DeferredChanges>()
+                .map(|changes| changes.simulation.component_did_changed::<T>())
+                .unwrap_or_default()
+    }
+}
+
 macro_rules! impl_universe_condition_tuple {
     ($($type:ident),+) => {
         impl<$($type: UniverseCondition),+> UniverseCondition for ($($type,)+) {
@@ -339,11 +471,51 @@ impl_universe_condition_tuple!(A, B, C, D, E, F, G, H, I, J, K, L, M, N);
 impl_universe_condition_tuple!(A, B, C, D, E, F, G, H, I, J, K, L, M, N, O);
 impl_universe_condition_tuple!(A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P);
 
+/// Capacities of basic resources installed by [`Universe::with_basics_config`].
+///
+/// Both capacities are in bytes and size scripting [`Context`] data stacks,
+/// which are allocated upfront and never grow.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UniverseBasics {
+    pub stack_capacity: usize,
+    pub registers_capacity: usize,
+}
+
+impl Default for UniverseBasics {
+    fn default() -> Self {
+        Self {
+            stack_capacity: 1024,
+            registers_capacity: 1024,
+        }
+    }
+}
+
+impl UniverseBasics {
+    pub fn stack_capacity(mut self, value: usize) -> Self {
+        self.stack_capacity = value;
+        self
+    }
+
+    pub fn registers_capacity(mut self, value: usize) -> Self {
+        self.registers_capacity = value;
+        self
+    }
+}
+
+type PluginInstaller = Box<dyn FnOnce(&mut World, &mut Systems, &mut Resources) + Send + Sync>;
+
+struct PendingPlugin {
+    path: Cow<'static, str>,
+    exists: fn(&Systems, &str) -> bool,
+    install: PluginInstaller,
+}
+
 #[derive(Default)]
 pub struct Universe {
     pub simulation: World,
     pub systems: Systems,
     pub resources: Resources,
+    pending_plugins: Vec<PendingPlugin>,
 }
 
 impl Universe {
@@ -352,26 +524,106 @@ impl Universe {
             simulation,
             resources: Default::default(),
             systems: Default::default(),
+            pending_plugins: Default::default(),
         }
     }
 
     pub fn with_plugin<T: Plugin + 'static>(mut self, plugin: T) -> Self {
         plugin.install(&mut self.simulation, &mut self.systems, &mut self.resources);
+        self.install_pending_plugins();
         self
     }
 
+    /// Installs plugin once system under given path exists in systems graph.
+    ///
+    /// If path already exists, plugin gets installed immediately, otherwise
+    /// its installation is postponed until some later installed plugin
+    /// creates that path. Call [`Self::finish`] once all plugins are added, to
+    /// report paths that never appeared instead of silently skipping plugins.
+    pub fn with_plugin_after<const LOCKING: bool, T: Plugin + 'static>(
+        mut self,
+        path: impl Into<Cow<'static, str>>,
+        plugin: T,
+    ) -> Self {
+        self.pending_plugins.push(PendingPlugin {
+            path: path.into(),
+            exists: |systems, path| {
+                GraphSchedulerPlugin::<LOCKING>::find_system_by_path(systems, path).is_some()
+            },
+            install: Box::new(|simulation, systems, resources| {
+                plugin.install(simulation, systems, resources)
+            }),
+        });
+        self.install_pending_plugins();
+        self
+    }
+
+    pub fn has_pending_plugins(&self) -> bool {
+        !self.pending_plugins.is_empty()
+    }
+
+    /// Returns systems paths that postponed plugins still wait for.
+    pub fn pending_plugin_paths(&self) -> impl Iterator<Item = &str> + '_ {
+        self.pending_plugins
+            .iter()
+            .map(|pending| pending.path.as_ref())
+    }
+
+    /// Completes universe setup, failing if any plugin added with
+    /// [`Self::with_plugin_after`] still waits for its systems path.
+    pub fn finish(self) -> Result<Self, Box<dyn Error>> {
+        if self.has_pending_plugins() {
+            return Err(format!(
+                "Plugins wait for missing systems paths: {}",
+                self.pending_plugin_paths().collect::<Vec<_>>().join(", ")
+            )
+            .into());
+        }
+        Ok(self)
+    }
+
+    fn install_pending_plugins(&mut self) {
+        while let Some(index) = self
+            .pending_plugins
+            .iter()
+            .position(|pending| (pending.exists)(&self.systems, &pending.path))
+        {
+            let pending = self.pending_plugins.remove(index);
+            (pending.install)(&mut self.simulation, &mut self.systems, &mut self.resources);
+        }
+    }
+
     pub fn with_basics(
         self,
         stack_capacity: usize,
         registers_capacity: usize,
     ) -> Result<Self, Box<dyn Error>> {
+        self.with_basics_config(UniverseBasics {
+            stack_capacity,
+            registers_capacity,
+        })
+    }
+
+    pub fn with_basics_config(self, basics: UniverseBasics) -> Result<Self, Box<dyn Error>> {
         self.with_resource(CommandBuffer::default())?
             .with_resource(Registry::default().with_basic_types())?
-            .with_resource(Context::new(stack_capacity, registers_capacity))?
+            .with_resource(Context::new(
+                basics.stack_capacity,
+                basics.registers_capacity,
+            ))?
             .with_resource(WorldProcessor::default())?
             .with_resource(SerializationRegistry::default().with_basic_types())
     }
 
+    /// Returns capacities of installed basic resources, if there are any.
+    pub fn basics(&self) -> Option<UniverseBasics> {
+        let context = self.resources.get::<true, Context>().ok()?;
+        Some(UniverseBasics {
+            stack_capacity: context.stack_capacity(),
+            registers_capacity: context.registers_capacity(),
+        })
+    }
+
     pub fn with_resource(mut self, resource: impl Component) -> Result<Self, Box<dyn Error>> {
         self.resources.add((resource,))?;
         Ok(self)
@@ -387,6 +639,18 @@ impl Universe {
     }
 
     pub fn clear_changes(&mut self) {
+        if let Ok(mut deferred) = self.resources.get_mut::<true, DeferredChanges>() {
+            deferred.simulation.append(
+                self.simulation.added(),
+                self.simulation.removed(),
+                self.simulation.updated().as_deref(),
+            );
+            deferred.resources.append(
+                self.resources.added(),
+                self.resources.removed(),
+                self.resources.updated().as_deref(),
+            );
+        }
         self.simulation.clear_changes();
         self.resources.clear_changes();
         self.systems.clear_changes();
@@ -409,9 +673,46 @@ pub trait Plugin: Send + Sync {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::scheduler::{GraphScheduler, GraphSchedulerPlugin, SystemParallelize};
+    use crate::scheduler::{GraphScheduler, SystemGroupChild, SystemName, SystemParallelize};
     use moirai::jobs::Jobs;
 
+    #[test]
+    fn test_universe_fetch_error() {
+        let universe = Universe::default().with_resource(1u8).unwrap();
+        let context = SystemContext::new(&universe, Entity::INVALID);
+        assert!(context.fetch::<(&World, Res<true, &u8>)>().is_ok());
+
+        let error = context
+            .fetch::<(&World, Res<true, &u8>, Res<true, &u32>)>()
+            .err()
+            .unwrap();
+        let message = error.to_string();
+        assert!(message.starts_with("fetch member 2 ("));
+        assert!(message.contains("Res<true, &u32>"));
+        let error = error.downcast_ref::<UniverseFetchError>().unwrap();
+        assert_eq!(error.index, 2);
+    }
+
+    #[test]
+    fn test_universe_basics() {
+        assert!(Universe::default().basics().is_none());
+
+        let universe = Universe::default()
+            .with_basics_config(Default::default())
+            .unwrap();
+        assert_eq!(universe.basics(), Some(UniverseBasics::default()));
+
+        let basics = UniverseBasics::default()
+            .stack_capacity(256)
+            .registers_capacity(512);
+        let universe = Universe::default().with_basics_config(basics).unwrap();
+        assert_eq!(universe.basics(), Some(basics));
+        let mut context = universe.resources.get_mut::<true, Context>().unwrap();
+        assert_eq!(context.stack().size(), 256);
+        assert_eq!(context.registers().size(), 512);
+        assert!(universe.resources.has::<CommandBuffer>());
+    }
+
     #[test]
     fn test_universe_parallelized_scheduler() {
         struct A(f32);
@@ -489,4 +790,176 @@ mod tests {
         let jobs = Jobs::default();
         GraphScheduler::<true>.run(&jobs, &mut universe).unwrap();
     }
+
+    #[test]
+    fn test_universe_plugin_ordering() {
+        #[derive(Default)]
+        struct Log(Vec<&'static str>);
+
+        fn pre(context: SystemContext) -> Result<(), Box<dyn Error>> {
+            context.fetch::<Res<true, &mut Log>>()?.0.push("pre");
+            Ok(())
+        }
+
+        fn solver(context: SystemContext) -> Result<(), Box<dyn Error>> {
+            context.fetch::<Res<true, &mut Log>>()?.0.push("solver");
+            Ok(())
+        }
+
+        fn post(context: SystemContext) -> Result<(), Box<dyn Error>> {
+            context.fetch::<Res<true, &mut Log>>()?.0.push("post");
+            Ok(())
+        }
+
+        let mut universe = Universe::default()
+            .with_resource(Log::default())
+            .unwrap()
+            .with_plugin_after::<true, _>(
+                "game/pre",
+                GraphSchedulerPlugin::<true>::default()
+                    .name("physics")
+                    .inject_into("game/pre")
+                    .system_setup(solver, |system| system.name("solver")),
+            );
+        assert!(universe.has_pending_plugins());
+
+        universe = universe.with_plugin(
+            GraphSchedulerPlugin::<true>::default()
+                .name("game")
+                .plugin_setup(|plugin| {
+                    plugin
+                        .name("pre")
+                        .system_setup(pre, |system| system.name("pre"))
+                })
+                .plugin_setup(|plugin| {
+                    plugin
+                        .name("post")
+                        .system_setup(post, |system| system.name("post"))
+                }),
+        );
+        assert!(!universe.has_pending_plugins());
+        let mut universe = universe.finish().unwrap();
+
+        let pre_group =
+            GraphSchedulerPlugin::<true>::find_system_by_path(&universe.systems, "game/pre")
+                .unwrap();
+        let physics = universe
+            .systems
+            .find_with::<true, SystemName>(|name| name.as_str() == "physics")
+            .unwrap();
+        assert!(
+            universe
+                .systems
+                .has_relation::<true, SystemGroupChild>(pre_group, physics)
+        );
+
+        let jobs = Jobs::default();
+        GraphScheduler::<true>.run(&jobs, &mut universe).unwrap();
+        assert_eq!(
+            universe.resources.get::<true, Log>().unwrap().0,
+            vec!["pre", "solver", "post"]
+        );
+
+        let universe = Universe::default().with_plugin_after::<true, _>(
+            "game/pr",
+            GraphSchedulerPlugin::<true>::default().name("physics"),
+        );
+        assert_eq!(
+            universe.pending_plugin_paths().collect::<Vec<_>>(),
+            vec!["game/pr"]
+        );
+        assert!(universe.finish().is_err());
+    }
+
+    #[test]
+    fn test_universe_deferred_changes() {
+        struct Value;
+
+        #[derive(Default)]
+        struct Stage {
+            fast_update: bool,
+            slow_run: bool,
+            observed: usize,
+        }
+
+        struct SlowStage;
+
+        impl UniverseCondition for SlowStage {
+            fn evaluate(context: SystemContext) -> bool {
+                context
+                    .universe
+                    .resources
+                    .get::<true, Stage>()
+                    .map(|stage| stage.slow_run)
+                    .unwrap_or_default()
+            }
+        }
+
+        fn fast(context: SystemContext) -> Result<(), Box<dyn Error>> {
+            let (world, stage, query) =
+                context.fetch::<(&World, Res<true, &Stage>, Query<true, (Entity, &Value)>)>()?;
+            if stage.fast_update {
+                for (entity, _) in query.query(world) {
+                    world.update::<Value>(entity);
+                }
+            }
+            Ok(())
+        }
+
+        fn slow(context: SystemContext) -> Result<(), Box<dyn Error>> {
+            let (mut stage, mut deferred) =
+                context.fetch::<(Res<true, &mut Stage>, Res<true, &mut DeferredChanges>)>()?;
+            stage.observed += 1;
+            deferred.clear();
+            Ok(())
+        }
+
+        let mut universe = Universe::default()
+            .with_resource(Stage::default())
+            .unwrap()
+            .with_resource(DeferredChanges::default())
+            .unwrap()
+            .with_plugin(
+                GraphSchedulerPlugin::<true>::default()
+                    .plugin_setup(|plugin| {
+                        plugin
+                            .name("fast")
+                            .system_setup(fast, |system| system.name("fast"))
+                    })
+                    .plugin_setup(|plugin| {
+                        plugin
+                            .name("slow")
+                            .condition::<SlowStage>()
+                            .system_setup(slow, |system| {
+                                system
+                                    .name("slow")
+                                    .condition::<DeferredComponentDidChanged<Value>>()
+                            })
+                    }),
+            );
+        universe.simulation.spawn((Value,)).unwrap();
+        universe.clear_changes();
+        universe
+            .resources
+            .get_mut::<true, DeferredChanges>()
+            .unwrap()
+            .clear();
+
+        let jobs = Jobs::default();
+        let scheduler = GraphScheduler::<true>;
+        let run = |universe: &mut Universe, fast_update: bool, slow_run: bool| {
+            {
+                let mut stage = universe.resources.get_mut::<true, Stage>().unwrap();
+                stage.fast_update = fast_update;
+                stage.slow_run = slow_run;
+            }
+            scheduler.run(&jobs, universe).unwrap();
+            universe.resources.get::<true, Stage>().unwrap().observed
+        };
+
+        assert_eq!(run(&mut universe, true, false), 0);
+        assert!(!universe.simulation.component_did_changed::<Value>());
+        assert_eq!(run(&mut universe, false, true), 1);
+        assert_eq!(run(&mut universe, false, true), 1);
+    }
 }
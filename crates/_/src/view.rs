@@ -5,15 +5,23 @@ use crate::{
     entity::Entity,
     query::{
         DynamicLookupAccess, DynamicLookupIter, DynamicQueryFilter, DynamicQueryIter,
-        TypedLookupAccess, TypedLookupFetch, TypedLookupIter, TypedQueryFetch, TypedQueryIter,
+        TypedLookupAccess, TypedLookupFetch, TypedLookupIter, TypedLookupOptionalIter,
+        TypedQueryChunkFetch, TypedQueryChunkIter, TypedQueryFetch, TypedQueryIter,
     },
     world::World,
 };
+use intuicio_data::type_hash::TypeHash;
 use std::{
+    collections::HashSet,
     marker::PhantomData,
     ops::{Bound, Deref, RangeBounds},
 };
 
+/// World view over archetypes containing all columns of `B` bundle.
+///
+/// Only listed columns are accessible through the view - they stay SDIR
+/// (structural) locked for as long as view exists, so view can be safely sent
+/// to other threads while world spawns or despawns entities with other columns.
 pub struct TypedWorldView<B: BundleColumns> {
     view: WorldView,
     _phantom: PhantomData<B>,
@@ -42,6 +50,28 @@ impl<B: BundleColumns> TypedWorldView<B> {
     pub fn into_inner(self) -> WorldView {
         self.view
     }
+
+    /// Splits view into two mutable leases over `A` and `C` columns.
+    ///
+    /// Returns `None` if any leased column is not part of this view or both
+    /// leases share any column.
+    pub fn split_leases<A: BundleColumns, C: BundleColumns>(
+        &self,
+    ) -> Option<(WorldViewLease<A>, WorldViewLease<C>)> {
+        let columns = B::columns_static()
+            .into_iter()
+            .map(|column| column.type_hash())
+            .collect::<HashSet<_>>();
+        let a = WorldViewLease::<A>::new(self.view.clone());
+        let c = WorldViewLease::<C>::new(self.view.clone());
+        if !a.columns.is_subset(&columns)
+            || !c.columns.is_subset(&columns)
+            || !a.columns.is_disjoint(&c.columns)
+        {
+            return None;
+        }
+        Some((a, c))
+    }
 }
 
 impl<B: BundleColumns> Clone for TypedWorldView<B> {
@@ -61,6 +91,90 @@ impl<B: BundleColumns> Deref for TypedWorldView<B> {
     }
 }
 
+/// Mutable access lease over `L` columns of world view.
+///
+/// Leases split from the same [`TypedWorldView`] access disjoint columns,
+/// so they can be sent to different workers and written to in parallel.
+/// Fetches requiring either shared or unique access to columns outside of
+/// lease are rejected.
+pub struct WorldViewLease<L: BundleColumns> {
+    view: WorldView,
+    columns: HashSet<TypeHash>,
+    _phantom: PhantomData<L>,
+}
+
+impl<L: BundleColumns> WorldViewLease<L> {
+    fn new(view: WorldView) -> Self {
+        Self {
+            view,
+            columns: L::columns_static()
+                .into_iter()
+                .map(|column| column.type_hash())
+                .collect(),
+            _phantom: PhantomData,
+        }
+    }
+
+    fn allows(
+        &self,
+        shared_access: impl FnOnce(&mut HashSet<TypeHash>),
+        unique_access: impl FnOnce(&mut HashSet<TypeHash>),
+    ) -> bool {
+        let mut output = HashSet::new();
+        shared_access(&mut output);
+        unique_access(&mut output);
+        output.is_subset(&self.columns)
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.view.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.view.is_empty()
+    }
+
+    #[inline]
+    pub fn entities(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.view.entities()
+    }
+
+    #[inline]
+    pub fn entities_work_group(
+        &self,
+        group_index: usize,
+        groups_count: usize,
+        min_items_per_group: usize,
+    ) -> impl Iterator<Item = Entity> + '_ {
+        self.view
+            .entities_work_group(group_index, groups_count, min_items_per_group)
+    }
+
+    pub fn query<'a, const LOCKING: bool, Fetch: TypedQueryFetch<'a, LOCKING>>(
+        &'a self,
+    ) -> Option<TypedQueryIter<'a, LOCKING, Fetch>> {
+        self.allows(Fetch::shared_access, Fetch::unique_access)
+            .then(|| self.view.query::<LOCKING, Fetch>())
+    }
+
+    pub fn lookup<'a, const LOCKING: bool, Fetch: TypedLookupFetch<'a, LOCKING>>(
+        &'a self,
+        entities: impl IntoIterator<Item = Entity> + 'a,
+    ) -> Option<TypedLookupIter<'a, LOCKING, Fetch>> {
+        self.allows(Fetch::shared_access, Fetch::unique_access)
+            .then(|| self.view.lookup::<LOCKING, Fetch>(entities))
+    }
+
+    pub fn lookup_access<'a, const LOCKING: bool, Fetch: TypedLookupFetch<'a, LOCKING>>(
+        &'a self,
+    ) -> Option<TypedLookupAccess<'a, LOCKING, Fetch>> {
+        self.allows(Fetch::shared_access, Fetch::unique_access)
+            .then(|| self.view.lookup_access::<LOCKING, Fetch>())
+    }
+}
+
 #[derive(Default, Clone)]
 pub struct WorldView {
     views: Vec<ArchetypeView>,
@@ -176,6 +290,16 @@ impl WorldView {
         None
     }
 
+    pub fn find_all_with<const LOCKING: bool, T: Component>(
+        &self,
+        f: impl Fn(&T) -> bool,
+    ) -> Vec<Entity> {
+        self.query::<LOCKING, (Entity, &T)>()
+            .filter(|(_, component)| f(component))
+            .map(|(entity, _)| entity)
+            .collect()
+    }
+
     pub fn entity<'a, const LOCKING: bool, Fetch: TypedLookupFetch<'a, LOCKING>>(
         &'a self,
         entity: Entity,
@@ -191,6 +315,12 @@ impl WorldView {
         TypedQueryIter::new_view(self)
     }
 
+    pub fn query_chunks<'a, const LOCKING: bool, Fetch: TypedQueryChunkFetch<'a, LOCKING>>(
+        &'a self,
+    ) -> TypedQueryChunkIter<'a, LOCKING, Fetch> {
+        TypedQueryChunkIter::new_view(self)
+    }
+
     pub fn dynamic_query<'a, const LOCKING: bool>(
         &'a self,
         filter: &DynamicQueryFilter,
@@ -205,6 +335,13 @@ impl WorldView {
         TypedLookupIter::new_view(self, entities)
     }
 
+    pub fn lookup_optional<'a, const LOCKING: bool, Fetch: TypedLookupFetch<'a, LOCKING>>(
+        &'a self,
+        entities: impl IntoIterator<Item = Entity> + 'a,
+    ) -> TypedLookupOptionalIter<'a, LOCKING, Fetch> {
+        TypedLookupOptionalIter::new_view(self, entities)
+    }
+
     pub fn lookup_access<'a, const LOCKING: bool, Fetch: TypedLookupFetch<'a, LOCKING>>(
         &'a self,
     ) -> TypedLookupAccess<'a, LOCKING, Fetch> {
@@ -372,4 +509,76 @@ mod tests {
 
         assert_eq!(sum, world.query::<true, &usize>().copied().sum());
     }
+
+    #[test]
+    fn test_typed_world_view_parallel() {
+        const N: usize = if cfg!(miri) { 10 } else { 1000 };
+        let jobs = Jobs::default();
+
+        let mut world = World::default();
+        for index in 0..N {
+            world.spawn((index, index as f32, index % 2 == 0)).unwrap();
+        }
+
+        let view = world.typed_view::<(usize, f32)>();
+        let sum = jobs
+            .broadcast(move |ctx| {
+                let entities =
+                    view.entities_work_group(ctx.work_group_index, ctx.work_groups_count, 10);
+                view.lookup::<true, (&usize, &f32)>(entities)
+                    .map(|(a, b)| *a + *b as usize)
+                    .sum::<usize>()
+            })
+            .wait()
+            .unwrap()
+            .into_iter()
+            .sum::<usize>();
+
+        assert_eq!(
+            sum,
+            world.query::<true, &usize>().copied().sum::<usize>() * 2
+        );
+        assert!(world.typed_view::<(u8,)>().is_empty());
+    }
+
+    #[test]
+    fn test_world_view_leases_parallel() {
+        is_async::<WorldViewLease<(usize,)>>();
+
+        const N: usize = if cfg!(miri) { 10 } else { 1000 };
+
+        let mut world = World::default();
+        for index in 0..N {
+            world.spawn((index, index as f32, index % 2 == 0)).unwrap();
+        }
+
+        let view = world.typed_view::<(usize, f32)>();
+        assert!(view.split_leases::<(usize,), (usize,)>().is_none());
+        assert!(view.split_leases::<(usize,), (bool,)>().is_none());
+        let (integers, floats) = view.split_leases::<(usize,), (f32,)>().unwrap();
+        assert!(integers.query::<true, &mut f32>().is_none());
+        assert!(floats.lookup_access::<true, &mut usize>().is_none());
+        assert!(integers.query::<true, &f32>().is_none());
+        assert!(integers.query::<true, &bool>().is_none());
+        assert!(floats.lookup_access::<true, Option<&usize>>().is_none());
+        assert!(integers.query::<true, (Entity, &usize)>().is_some());
+
+        let integers = spawn(move || {
+            for value in integers.query::<true, &mut usize>().unwrap() {
+                *value *= 2;
+            }
+        });
+        let floats = spawn(move || {
+            for value in floats.query::<true, &mut f32>().unwrap() {
+                *value += 1.0;
+            }
+        });
+        integers.join().unwrap();
+        floats.join().unwrap();
+
+        for (index, (integer, float)) in world.query::<true, (&usize, &f32)>().enumerate() {
+            assert_eq!(*integer, index * 2);
+            assert_eq!(*float, index as f32 + 1.0);
+        }
+    }
 }
@@ -3,22 +3,26 @@ use crate::{
         Archetype, ArchetypeColumnInfo, ArchetypeDynamicEntityColumnAccess,
         ArchetypeEntityColumnAccess, ArchetypeEntityRowAccess, ArchetypeError,
     },
-    bundle::{Bundle, BundleColumns},
-    component::{Component, ComponentRef, ComponentRefMut},
+    bundle::{Bundle, BundleColumns, BundleExtract},
+    component::{AutoNotifyComponent, AutoNotifyInner, Component, ComponentRef, ComponentRefMut},
     entity::Entity,
     processor::{WorldProcessor, WorldProcessorEntityMapping},
     query::{
-        DynamicLookupAccess, DynamicLookupIter, DynamicQueryFilter, DynamicQueryIter,
-        TypedLookupAccess, TypedLookupFetch, TypedLookupIter, TypedQueryFetch, TypedQueryIter,
+        DynamicLookupAccess, DynamicLookupIter, DynamicQueryFilter, DynamicQueryIter, QueryError,
+        TypedLookupAccess, TypedLookupFetch, TypedLookupIter, TypedLookupOptionalIter,
+        TypedQueryChunkFetch, TypedQueryChunkIter, TypedQueryFetch, TypedQueryIter,
         TypedRelationLookupFetch, TypedRelationLookupIter,
     },
+    view::TypedWorldView,
 };
 use intuicio_core::{registry::Registry, types::struct_type::NativeStructBuilder};
 use intuicio_data::type_hash::TypeHash;
+use moirai::jobs::{Jobs, ScopedJobs};
 use std::{
     collections::{HashMap, HashSet, VecDeque},
     error::Error,
     marker::PhantomData,
+    ops::{Deref, DerefMut},
     sync::{Arc, RwLock, RwLockReadGuard},
 };
 
@@ -39,6 +43,10 @@ pub enum WorldError {
     DuplicateMutableArchetypeAccess { id: u32 },
     /// Indicates that an operation involved an empty column set, which is invalid in the ECS context.
     EmptyColumnSet,
+    /// Indicates an attempt to spawn into an entity slot that is already live.
+    EntityAlreadyExists { entity: Entity },
+    /// Indicates an attempt to spawn into an entity with generation not newer than its released slot.
+    StaleEntityGeneration { entity: Entity },
 }
 
 impl WorldError {
@@ -102,6 +110,12 @@ impl std::fmt::Display for WorldError {
             Self::EmptyColumnSet => {
                 write!(f, "Trying to perform change on empty column set")
             }
+            Self::EntityAlreadyExists { entity } => {
+                write!(f, "Entity already exists: {entity}")
+            }
+            Self::StaleEntityGeneration { entity } => {
+                write!(f, "Entity generation is stale: {entity}")
+            }
         }
     }
 }
@@ -114,6 +128,8 @@ struct EntityMap {
     /// index is entity id, value is pair of generation and optional archetype id.
     table: Vec<(u32, Option<u32>)>,
     reusable: Vec<Entity>,
+    /// acquired entities waiting to be spawned into.
+    reserved: HashSet<Entity>,
     size: usize,
 }
 
@@ -147,6 +163,7 @@ impl EntityMap {
         self.id_generator = 0;
         self.table.clear();
         self.reusable.clear();
+        self.reserved.clear();
         self.size = 0;
     }
 
@@ -181,6 +198,55 @@ impl EntityMap {
         }
     }
 
+    /// Reserves a new entity without making it live, so it can be spawned
+    /// into later with [`Self::acquire_at`].
+    fn reserve(&mut self) -> Result<Entity, WorldError> {
+        let (entity, _) = self.acquire()?;
+        self.size -= 1;
+        self.reserved.insert(entity);
+        Ok(entity)
+    }
+
+    /// Acquires exact entity, either previously reserved or any free one.
+    ///
+    /// # Returns
+    /// * `Ok(&mut Option<u32>)` - A mutable reference to entity associated archetype.
+    /// * `Err(WorldError::EntityAlreadyExists)` - If the entity ID is live or reserved with other generation.
+    /// * `Err(WorldError::StaleEntityGeneration)` - If the entity ID was released with same or newer generation.
+    fn acquire_at(&mut self, entity: Entity) -> Result<&mut Option<u32>, WorldError> {
+        let id = entity.id() as usize;
+        if self.reserved.remove(&entity) {
+            self.size += 1;
+            return Ok(&mut self.table[id].1);
+        }
+        if self
+            .reserved
+            .iter()
+            .any(|reserved| reserved.id() == entity.id())
+        {
+            return Err(WorldError::EntityAlreadyExists { entity });
+        }
+        if let Some((generation, archetype)) = self.table.get(id) {
+            if archetype.is_some() {
+                return Err(WorldError::EntityAlreadyExists { entity });
+            }
+            if entity.generation() <= *generation {
+                return Err(WorldError::StaleEntityGeneration { entity });
+            }
+            self.reusable
+                .retain(|released| released.id() != entity.id());
+        } else {
+            // ids skipped over become reusable for regular spawns.
+            self.reusable
+                .extend((self.id_generator..entity.id()).filter_map(|id| Entity::new(id, 0)));
+            self.table.resize(id + 1, (0, None));
+            self.id_generator = entity.id() + 1;
+        }
+        self.table[id].0 = entity.generation();
+        self.size += 1;
+        Ok(&mut self.table[id].1)
+    }
+
     /// Releases an entity back into the reusable pool, if it exists.
     ///
     /// # Returns
@@ -440,6 +506,10 @@ impl<T: Component> Relation<T> {
                 *entity = mapping.remap(*entity);
             }
         });
+        processor.register_entity_unlinking::<Self>(|relation, entity| {
+            relation.remove(entity);
+            relation.is_empty()
+        });
         processor.register_entity_inspector::<Self>(|relation| {
             relation.iter().map(|(_, entity)| entity).collect()
         });
@@ -540,6 +610,31 @@ impl<T: Component> Relation<T> {
         }
     }
 
+    /// Keeps only connections for which predicate returns true, allowing
+    /// payloads to be modified along the way.
+    pub fn retain(&mut self, mut predicate: impl FnMut(&mut T, Entity) -> bool) {
+        self.connections = match std::mem::take(&mut self.connections) {
+            RelationConnections::Zero(a) => RelationConnections::Zero(a),
+            RelationConnections::One([mut a]) => {
+                if predicate(&mut a.0, a.1) {
+                    RelationConnections::One([a])
+                } else {
+                    RelationConnections::Zero([])
+                }
+            }
+            RelationConnections::More(mut vec) => {
+                vec.retain_mut(|(payload, entity)| predicate(payload, *entity));
+                if vec.len() == 1 {
+                    RelationConnections::One([vec.remove(0)])
+                } else if vec.is_empty() {
+                    RelationConnections::Zero([])
+                } else {
+                    RelationConnections::More(vec)
+                }
+            }
+        }
+    }
+
     /// Clears all stored relations.
     pub fn clear(&mut self) {
         self.connections = Default::default();
@@ -619,6 +714,54 @@ impl<T: Component> Relation<T> {
     }
 }
 
+/// Shared access to a single relation payload, keeping the relation component borrowed.
+pub struct RelationPayloadRef<'a, const LOCKING: bool, T: Component> {
+    relation: ComponentRef<'a, LOCKING, Relation<T>>,
+    entity: Entity,
+}
+
+impl<const LOCKING: bool, T: Component> RelationPayloadRef<'_, LOCKING, T> {
+    /// Returns the target entity of the relation connection.
+    pub fn entity(&self) -> Entity {
+        self.entity
+    }
+}
+
+impl<const LOCKING: bool, T: Component> Deref for RelationPayloadRef<'_, LOCKING, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        self.relation.payload(self.entity).unwrap()
+    }
+}
+
+/// Exclusive access to a single relation payload, keeping the relation component borrowed.
+pub struct RelationPayloadRefMut<'a, const LOCKING: bool, T: Component> {
+    relation: ComponentRefMut<'a, LOCKING, Relation<T>>,
+    entity: Entity,
+}
+
+impl<const LOCKING: bool, T: Component> RelationPayloadRefMut<'_, LOCKING, T> {
+    /// Returns the target entity of the relation connection.
+    pub fn entity(&self) -> Entity {
+        self.entity
+    }
+}
+
+impl<const LOCKING: bool, T: Component> Deref for RelationPayloadRefMut<'_, LOCKING, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        self.relation.payload(self.entity).unwrap()
+    }
+}
+
+impl<const LOCKING: bool, T: Component> DerefMut for RelationPayloadRefMut<'_, LOCKING, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.relation.payload_mut(self.entity).unwrap()
+    }
+}
+
 /// An iterator that traverses relations in a graph-like structure, tracking visited entities.
 pub struct RelationsTraverseIter<'a, const LOCKING: bool, T: Component> {
     world: &'a World,
@@ -638,11 +781,11 @@ impl<const LOCKING: bool, T: Component> Iterator for RelationsTraverseIter<'_, L
             }
             self.visited.insert(to);
             if self.incoming {
-                for (from, _, to) in self.world.relations_incomming::<LOCKING, T>(to) {
+                for (from, _, to) in self.world.relations_incoming::<LOCKING, T>(to) {
                     if self.stack.len() == self.stack.capacity() {
                         self.stack.reserve_exact(self.stack.capacity());
                     }
-                    self.stack.push_back((Some(from), to));
+                    self.stack.push_back((Some(to), from));
                 }
             } else {
                 for (from, _, to) in self.world.relations_outgoing::<LOCKING, T>(to) {
@@ -673,6 +816,21 @@ impl WorldChanges {
         self.table.clear();
     }
 
+    /// Appends changes from other set into this one.
+    ///
+    /// # Arguments
+    /// * `other` - The changes to append.
+    pub fn append(&mut self, other: &Self) {
+        for (entity, components) in other.iter() {
+            let target = self.table.entry(entity).or_default();
+            for type_hash in components {
+                if !target.contains(type_hash) {
+                    target.push(*type_hash);
+                }
+            }
+        }
+    }
+
     /// Checks if a specific entity exists in the tracked changes.
     ///
     /// # Arguments
@@ -773,6 +931,79 @@ impl WorldChanges {
     }
 }
 
+/// Represents a record of entities that moved between archetypes, tracking component types they
+/// had before first and after last move.
+///
+/// Only components insertion and removal on living entities is tracked here, spawned and
+/// despawned entities are reported by added and removed changes.
+#[derive(Default, Clone)]
+pub struct ArchetypeChanges {
+    table: HashMap<Entity, (Vec<TypeHash>, Vec<TypeHash>)>,
+}
+
+impl ArchetypeChanges {
+    /// Clears all tracked archetype changes.
+    pub fn clear(&mut self) {
+        self.table.clear();
+    }
+
+    /// Checks if there are no tracked archetype changes.
+    ///
+    /// # Returns
+    /// `true` if no entity changed its archetype, `false` otherwise.
+    pub fn is_empty(&self) -> bool {
+        self.table.is_empty()
+    }
+
+    /// Checks if a specific entity changed its archetype.
+    ///
+    /// # Arguments
+    /// * `entity` - The entity to check for.
+    ///
+    /// # Returns
+    /// `true` if the entity changed its archetype, `false` otherwise.
+    pub fn has_entity(&self, entity: Entity) -> bool {
+        self.table.contains_key(&entity)
+    }
+
+    /// Gets archetype transition of a specific entity.
+    ///
+    /// # Arguments
+    /// * `entity` - The entity to get transition of.
+    ///
+    /// # Returns
+    /// A tuple `(old_types, new_types)` if the entity changed its archetype.
+    pub fn get(&self, entity: Entity) -> Option<(&[TypeHash], &[TypeHash])> {
+        self.table
+            .get(&entity)
+            .map(|(old, new)| (old.as_slice(), new.as_slice()))
+    }
+
+    /// Iterates over all entities and their archetype transitions.
+    ///
+    /// # Returns
+    /// An iterator of tuples `(Entity, &[TypeHash], &[TypeHash])` with old and new types.
+    pub fn iter(&self) -> impl Iterator<Item = (Entity, &[TypeHash], &[TypeHash])> {
+        self.table
+            .iter()
+            .map(|(entity, (old, new))| (*entity, old.as_slice(), new.as_slice()))
+    }
+
+    fn record(&mut self, entity: Entity, old: Vec<TypeHash>, new: Vec<TypeHash>) {
+        let (old, target) = self
+            .table
+            .entry(entity)
+            .or_insert((old, Default::default()));
+        *target = new;
+        // entity getting back to its original archetype is not a change.
+        let reverted =
+            old.len() == target.len() && target.iter().all(|type_hash| old.contains(type_hash));
+        if reverted {
+            self.table.remove(&entity);
+        }
+    }
+}
+
 /// Represents the main data structure of the ECS (Entity-Component System),
 /// managing entities, components, and their organizational structure.
 pub struct World {
@@ -784,6 +1015,7 @@ pub struct World {
     added: WorldChanges,
     removed: WorldChanges,
     updated: Arc<RwLock<WorldChanges>>,
+    archetype_changes: ArchetypeChanges,
 }
 
 impl Default for World {
@@ -795,6 +1027,7 @@ impl Default for World {
             added: Default::default(),
             removed: Default::default(),
             updated: Default::default(),
+            archetype_changes: Default::default(),
         }
     }
 }
@@ -816,6 +1049,17 @@ impl World {
         self.entities.len()
     }
 
+    pub fn component_count<T: Component>(&self) -> usize {
+        self.component_count_raw(TypeHash::of::<T>())
+    }
+
+    pub fn component_count_raw(&self, type_hash: TypeHash) -> usize {
+        self.archetypes()
+            .filter(|archetype| archetype.has_type(type_hash))
+            .map(|archetype| archetype.len())
+            .sum()
+    }
+
     #[inline]
     pub fn entities(&self) -> impl Iterator<Item = Entity> + '_ {
         self.entities.iter()
@@ -848,6 +1092,13 @@ impl World {
         self.archetypes.iter_mut()
     }
 
+    pub fn archetype_for<B: BundleColumns>(&self) -> Option<&Archetype> {
+        let id = self
+            .archetypes
+            .find_by_columns_exact(&B::columns_static())?;
+        self.archetypes.get(id).ok()
+    }
+
     #[inline]
     pub(crate) fn archetype_by_id(&self, id: u32) -> Result<&Archetype, WorldError> {
         self.archetypes.get(id)
@@ -865,6 +1116,10 @@ impl World {
         self.updated.try_read().ok()
     }
 
+    pub fn archetype_changes(&self) -> &ArchetypeChanges {
+        &self.archetype_changes
+    }
+
     pub fn entity_did_changed(&self, entity: Entity) -> bool {
         self.added.has_entity(entity)
             || self.removed.has_entity(entity)
@@ -919,6 +1174,32 @@ impl World {
         }
     }
 
+    pub fn map_component<const LOCKING: bool, T: Component>(
+        &mut self,
+        mut f: impl FnMut(&mut T),
+    ) -> Result<(), WorldError> {
+        let type_hash = TypeHash::of::<T>();
+        let mut updated = self.updated.try_write().ok();
+        for archetype in self.archetypes.iter() {
+            if !archetype.has_type(type_hash) {
+                continue;
+            }
+            let mut access = archetype.column::<LOCKING, T>(true)?;
+            if let Some(slice) = access.as_mut_slice() {
+                slice.iter_mut().for_each(&mut f);
+            }
+            if let Some(updated) = updated.as_mut() {
+                for entity in archetype.entities().iter() {
+                    let components = updated.table.entry(entity).or_default();
+                    if !components.contains(&type_hash) {
+                        components.push(type_hash);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
     pub fn validate_sdir(&self) -> Result<(), ArchetypeError> {
         for archetype in self.archetypes.iter() {
             archetype.validate_sdir()?;
@@ -942,6 +1223,7 @@ impl World {
         if let Ok(mut updated) = self.updated.try_write() {
             updated.clear();
         }
+        self.archetype_changes.clear();
     }
 
     #[inline]
@@ -951,6 +1233,26 @@ impl World {
     }
 
     pub fn spawn(&mut self, bundle: impl Bundle) -> Result<Entity, WorldError> {
+        self.spawn_inner(None, bundle)
+    }
+
+    pub fn reserve_entity(&mut self) -> Result<Entity, WorldError> {
+        self.entities.reserve()
+    }
+
+    /// Spawns into exact entity, either reserved with [`Self::reserve_entity`]
+    /// or any free one, e.g. assigned by a server. Free IDs released before
+    /// require newer generation, so stale handles do not come back to life.
+    pub fn spawn_at(&mut self, entity: Entity, bundle: impl Bundle) -> Result<(), WorldError> {
+        self.spawn_inner(Some(entity), bundle)?;
+        Ok(())
+    }
+
+    fn spawn_inner(
+        &mut self,
+        reserved: Option<Entity>,
+        bundle: impl Bundle,
+    ) -> Result<Entity, WorldError> {
         let bundle_columns = bundle.columns();
         if bundle_columns.is_empty() {
             return Err(WorldError::EmptyColumnSet);
@@ -959,7 +1261,10 @@ impl World {
             .iter()
             .map(|column| column.type_hash())
             .collect::<Vec<_>>();
-        let (entity, id) = self.entities.acquire()?;
+        let (entity, id) = match reserved {
+            Some(entity) => (entity, self.entities.acquire_at(entity)?),
+            None => self.entities.acquire()?,
+        };
         let id = if let Some(archetype_id) = self.archetypes.find_by_columns_exact(&bundle_columns)
         {
             *id = Some(archetype_id);
@@ -1029,6 +1334,26 @@ impl World {
     pub unsafe fn spawn_uninitialized_raw(
         &'_ mut self,
         columns: Vec<ArchetypeColumnInfo>,
+    ) -> Result<(Entity, ArchetypeEntityRowAccess<'_>), WorldError> {
+        unsafe { self.spawn_uninitialized_inner(None, columns) }
+    }
+
+    /// # Safety
+    pub unsafe fn spawn_uninitialized_at_raw(
+        &'_ mut self,
+        entity: Entity,
+        columns: Vec<ArchetypeColumnInfo>,
+    ) -> Result<ArchetypeEntityRowAccess<'_>, WorldError> {
+        unsafe {
+            self.spawn_uninitialized_inner(Some(entity), columns)
+                .map(|(_, access)| access)
+        }
+    }
+
+    unsafe fn spawn_uninitialized_inner(
+        &'_ mut self,
+        reserved: Option<Entity>,
+        columns: Vec<ArchetypeColumnInfo>,
     ) -> Result<(Entity, ArchetypeEntityRowAccess<'_>), WorldError> {
         if columns.is_empty() {
             return Err(WorldError::EmptyColumnSet);
@@ -1037,7 +1362,10 @@ impl World {
             .iter()
             .map(|column| column.type_hash())
             .collect::<Vec<_>>();
-        let (entity, id) = self.entities.acquire()?;
+        let (entity, id) = match reserved {
+            Some(entity) => (entity, self.entities.acquire_at(entity)?),
+            None => self.entities.acquire()?,
+        };
         let id = if let Some(archetype_id) = self.archetypes.find_by_columns_exact(&columns) {
             *id = Some(archetype_id);
             archetype_id
@@ -1159,6 +1487,23 @@ impl World {
     }
 
     #[inline]
+    pub fn despawn_recursive<const LOCKING: bool, T: Component>(
+        &mut self,
+        entity: Entity,
+    ) -> Result<(), WorldError> {
+        let entities = self
+            .traverse_outgoing::<LOCKING, T>([entity])
+            .map(|(_, to)| to)
+            .collect::<Vec<_>>();
+        for entity in entities {
+            if self.has_entity(entity) {
+                self.despawn(entity)?;
+            }
+            self.unrelate_any::<LOCKING, T>(entity)?;
+        }
+        Ok(())
+    }
+
     pub fn despawn_all(&mut self) {
         #[cfg(feature = "tracing")]
         #[cfg(feature = "trace-changes")]
@@ -1189,6 +1534,10 @@ impl World {
             .columns()
             .cloned()
             .collect::<Vec<_>>();
+        let old_types = new_columns
+            .iter()
+            .map(|column| column.type_hash())
+            .collect::<Vec<_>>();
         for column in bundle_columns {
             if !new_columns
                 .iter()
@@ -1197,6 +1546,10 @@ impl World {
                 new_columns.push(column);
             }
         }
+        let new_types = new_columns
+            .iter()
+            .map(|column| column.type_hash())
+            .collect::<Vec<_>>();
         let _new_id = if let Some(new_id) = self.archetypes.find_by_columns_exact(&new_columns) {
             if new_id == old_id {
                 return Ok(());
@@ -1238,6 +1591,7 @@ impl World {
             .entry(entity)
             .or_default()
             .extend(bundle_types);
+        self.archetype_changes.record(entity, old_types, new_types);
         Ok(())
     }
 
@@ -1265,6 +1619,10 @@ impl World {
             .cloned()
             .collect::<Vec<_>>();
         let despawn = new_columns.is_empty();
+        let old_types = new_columns
+            .iter()
+            .map(|column| column.type_hash())
+            .collect::<Vec<_>>();
         for column in columns {
             if let Some(index) = new_columns
                 .iter()
@@ -1273,6 +1631,10 @@ impl World {
                 new_columns.swap_remove(index);
             }
         }
+        let new_types = new_columns
+            .iter()
+            .map(|column| column.type_hash())
+            .collect::<Vec<_>>();
         let _new_id = if let Some(new_id) = self.archetypes.find_by_columns_exact(&new_columns) {
             if new_id == old_id {
                 return Ok(());
@@ -1313,6 +1675,7 @@ impl World {
             .entry(entity)
             .or_default()
             .extend(bundle_types);
+        self.archetype_changes.record(entity, old_types, new_types);
         Ok(())
     }
 
@@ -1395,6 +1758,14 @@ impl World {
             .unwrap_or_default()
     }
 
+    pub fn entity_component_types(&self, entity: Entity) -> impl Iterator<Item = TypeHash> + '_ {
+        self.entities
+            .get(entity)
+            .and_then(|index| self.archetypes.get(index))
+            .into_iter()
+            .flat_map(|archetype| archetype.columns().map(|info| info.type_hash()))
+    }
+
     pub fn has_component<T: Component>(&self) -> bool {
         self.has_component_raw(TypeHash::of::<T>())
     }
@@ -1429,6 +1800,20 @@ impl World {
         None
     }
 
+    pub fn find_all_with<const LOCKING: bool, T: Component>(
+        &self,
+        f: impl Fn(&T) -> bool,
+    ) -> Vec<Entity> {
+        self.query::<LOCKING, (Entity, &T)>()
+            .filter(|(_, component)| f(component))
+            .map(|(entity, _)| entity)
+            .collect()
+    }
+
+    pub fn bundle_of<const LOCKING: bool, B: BundleExtract>(&self, entity: Entity) -> Option<B> {
+        B::extract::<LOCKING>(self, entity)
+    }
+
     pub fn component<const LOCKING: bool, T: Component>(
         &'_ self,
         entity: Entity,
@@ -1447,6 +1832,42 @@ impl World {
         })
     }
 
+    pub fn component_mut_notified<const LOCKING: bool, T: Component>(
+        &'_ self,
+        entity: Entity,
+    ) -> Result<AutoNotifyComponent<'_, LOCKING, T>, WorldError> {
+        Ok(AutoNotifyComponent {
+            entity,
+            world: self,
+            inner: AutoNotifyInner::Ref(self.component_mut::<LOCKING, T>(entity)?),
+            dirty: false,
+        })
+    }
+
+    pub fn component_or_default<const LOCKING: bool, T: Component + Clone + Default>(
+        &self,
+        entity: Entity,
+    ) -> T {
+        self.component::<LOCKING, T>(entity)
+            .map(|value| value.clone())
+            .unwrap_or_default()
+    }
+
+    pub fn swap_components<const LOCKING: bool, T: Component>(
+        &mut self,
+        a: Entity,
+        b: Entity,
+    ) -> Result<(), WorldError> {
+        let data_a = unsafe { self.get::<LOCKING, T>(a, true)?.data() };
+        let data_b = unsafe { self.get::<LOCKING, T>(b, true)?.data() };
+        if data_a != data_b {
+            unsafe { std::ptr::swap(data_a.cast::<T>(), data_b.cast::<T>()) };
+            self.update::<T>(a);
+            self.update::<T>(b);
+        }
+        Ok(())
+    }
+
     pub fn get<const LOCKING: bool, T: Component>(
         &'_ self,
         entity: Entity,
@@ -1495,6 +1916,80 @@ impl World {
         TypedQueryIter::new(self)
     }
 
+    /// Fetches components of single entity, borrowing world exclusively so
+    /// mutable references can safely outlive column access.
+    pub fn query_one_mut<'a, const LOCKING: bool, Fetch: TypedLookupFetch<'a, LOCKING>>(
+        &'a mut self,
+        entity: Entity,
+    ) -> Option<Fetch::Value> {
+        let this = &*self;
+        let archetype = this.archetypes.get(this.entities.get(entity).ok()?).ok()?;
+        let mut access = Fetch::try_access_world(this, archetype)?;
+        Fetch::fetch(&mut access, entity)
+    }
+
+    /// Folds query results in parallel, where each work group folds its own
+    /// share of matching archetypes starting from `identity` value and partial
+    /// results get combined at the end. Number of work groups varies, so value
+    /// made by `identity` must be neutral for both `fold` and `combine`, e.g.
+    /// zero for sums, otherwise result would depend on work groups count.
+    /// Only read-only fetches are allowed, so fetches requesting unique access
+    /// fail before any work gets scheduled.
+    pub fn par_fold<'a, const LOCKING: bool, Fetch: TypedQueryFetch<'a, LOCKING>, A>(
+        &'a self,
+        jobs: &Jobs,
+        identity: impl Fn() -> A + Send + Sync,
+        fold: impl Fn(A, Fetch::Value) -> A + Send + Sync,
+        combine: impl Fn(A, A) -> A,
+    ) -> Result<A, QueryError>
+    where
+        A: Send + 'static,
+    {
+        let mut unique_access = HashSet::new();
+        Fetch::unique_access(&mut unique_access);
+        if let Some(type_hash) = unique_access.into_iter().next() {
+            return Err(QueryError::TryingToWriteUnavailableType { type_hash });
+        }
+        let archetypes = self
+            .archetypes()
+            .filter(|archetype| !archetype.is_empty() && Fetch::does_accept_archetype(archetype))
+            .collect::<Vec<_>>();
+        if archetypes.is_empty() {
+            return Ok(identity());
+        }
+        let (partials, _) = ScopedJobs::<Result<A, QueryError>>::execute(jobs, |scope| {
+            scope.broadcast(|ctx| {
+                archetypes
+                    .iter()
+                    .skip(ctx.work_group_index)
+                    .step_by(ctx.work_groups_count.max(1))
+                    .try_fold(identity(), |mut accumulator, archetype| {
+                        let mut access = Fetch::access_world(self, archetype)?;
+                        while let Some(value) = Fetch::fetch(&mut access) {
+                            accumulator = fold(accumulator, value);
+                        }
+                        Ok(accumulator)
+                    })
+            });
+        });
+        Ok(partials
+            .into_iter()
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .reduce(combine)
+            .unwrap_or_else(identity))
+    }
+
+    pub fn typed_view<B: BundleColumns>(&self) -> TypedWorldView<B> {
+        TypedWorldView::new(self)
+    }
+
+    pub fn query_chunks<'a, const LOCKING: bool, Fetch: TypedQueryChunkFetch<'a, LOCKING>>(
+        &'a self,
+    ) -> TypedQueryChunkIter<'a, LOCKING, Fetch> {
+        TypedQueryChunkIter::new(self)
+    }
+
     pub fn dynamic_query<'a, const LOCKING: bool>(
         &'a self,
         filter: &DynamicQueryFilter,
@@ -1509,6 +2004,13 @@ impl World {
         TypedLookupIter::new(self, entities)
     }
 
+    pub fn lookup_optional<'a, const LOCKING: bool, Fetch: TypedLookupFetch<'a, LOCKING>>(
+        &'a self,
+        entities: impl IntoIterator<Item = Entity> + 'a,
+    ) -> TypedLookupOptionalIter<'a, LOCKING, Fetch> {
+        TypedLookupOptionalIter::new(self, entities)
+    }
+
     pub fn lookup_access<'a, const LOCKING: bool, Fetch: TypedLookupFetch<'a, LOCKING>>(
         &'a self,
     ) -> TypedLookupAccess<'a, LOCKING, Fetch> {
@@ -1628,6 +2130,30 @@ impl World {
         Ok(())
     }
 
+    pub fn retain_relations<const LOCKING: bool, T: Component>(
+        &mut self,
+        mut predicate: impl FnMut(Entity, &mut T, Entity) -> bool,
+    ) -> Result<(), WorldError> {
+        let mut to_update = Vec::new();
+        let mut to_remove = Vec::new();
+        for (from, relation) in self.query::<LOCKING, (Entity, &mut Relation<T>)>() {
+            relation.retain(|payload, to| predicate(from, payload, to));
+            if relation.is_empty() {
+                to_remove.push(from);
+            } else {
+                to_update.push(from);
+            }
+        }
+        // predicate could rewrite payloads of retained relations too.
+        for entity in to_update {
+            self.update::<Relation<T>>(entity);
+        }
+        for entity in to_remove {
+            self.remove::<(Relation<T>,)>(entity)?;
+        }
+        Ok(())
+    }
+
     pub fn unrelate_all<const LOCKING: bool, T: Component>(
         &mut self,
         entity: Entity,
@@ -1659,6 +2185,38 @@ impl World {
             .unwrap_or_default()
     }
 
+    pub fn relation_payload<const LOCKING: bool, T: Component>(
+        &'_ self,
+        from: Entity,
+        to: Entity,
+    ) -> Option<RelationPayloadRef<'_, LOCKING, T>> {
+        let relation = self.component::<LOCKING, Relation<T>>(from).ok()?;
+        if relation.has(to) {
+            Some(RelationPayloadRef {
+                relation,
+                entity: to,
+            })
+        } else {
+            None
+        }
+    }
+
+    pub fn relation_payload_mut<const LOCKING: bool, T: Component>(
+        &'_ self,
+        from: Entity,
+        to: Entity,
+    ) -> Option<RelationPayloadRefMut<'_, LOCKING, T>> {
+        let relation = self.component_mut::<LOCKING, Relation<T>>(from).ok()?;
+        if relation.has(to) {
+            Some(RelationPayloadRefMut {
+                relation,
+                entity: to,
+            })
+        } else {
+            None
+        }
+    }
+
     pub fn relations<const LOCKING: bool, T: Component>(
         &self,
     ) -> impl Iterator<Item = (Entity, &T, Entity)> + '_ {
@@ -1707,20 +2265,36 @@ impl World {
             })
     }
 
+    #[deprecated(note = "Use `relations_incoming` instead")]
     pub fn relations_incomming<const LOCKING: bool, T: Component>(
         &self,
         to: Entity,
     ) -> impl Iterator<Item = (Entity, &T, Entity)> + '_ {
-        self.query::<LOCKING, (Entity, &Relation<T>)>()
-            .flat_map(move |(from, relation)| {
-                relation
-                    .iter()
-                    .filter(move |(_, entity)| *entity == to)
+        self.relations_incoming::<LOCKING, T>(to)
+    }
+
+    #[deprecated(note = "Use `relations_incoming_mut` instead")]
+    pub fn relations_incomming_mut<const LOCKING: bool, T: Component>(
+        &self,
+        to: Entity,
+    ) -> impl Iterator<Item = (Entity, &mut T, Entity)> + '_ {
+        self.relations_incoming_mut::<LOCKING, T>(to)
+    }
+
+    pub fn relations_incoming<const LOCKING: bool, T: Component>(
+        &self,
+        to: Entity,
+    ) -> impl Iterator<Item = (Entity, &T, Entity)> + '_ {
+        self.query::<LOCKING, (Entity, &Relation<T>)>()
+            .flat_map(move |(from, relation)| {
+                relation
+                    .iter()
+                    .filter(move |(_, entity)| *entity == to)
                     .map(move |(payload, to)| (from, payload, to))
             })
     }
 
-    pub fn relations_incomming_mut<const LOCKING: bool, T: Component>(
+    pub fn relations_incoming_mut<const LOCKING: bool, T: Component>(
         &self,
         to: Entity,
     ) -> impl Iterator<Item = (Entity, &mut T, Entity)> + '_ {
@@ -1759,6 +2333,51 @@ impl World {
         }
     }
 
+    pub fn has_relation_cycle<const LOCKING: bool, T: Component>(&self) -> bool {
+        self.relation_cycle::<LOCKING, T>().is_some()
+    }
+
+    pub fn relation_cycle<const LOCKING: bool, T: Component>(&self) -> Option<Vec<Entity>> {
+        let mut graph = HashMap::<Entity, Vec<Entity>>::new();
+        for (from, _, to) in self.relations::<LOCKING, T>() {
+            graph.entry(from).or_default().push(to);
+        }
+        // `false` marks entities on current path, `true` fully visited ones.
+        let mut visited = HashMap::<Entity, bool>::with_capacity(graph.len());
+        for root in graph.keys().copied() {
+            if visited.contains_key(&root) {
+                continue;
+            }
+            visited.insert(root, false);
+            let mut path = vec![(root, 0)];
+            while let Some((entity, index)) = path.last_mut() {
+                let entity = *entity;
+                let next = graph
+                    .get(&entity)
+                    .and_then(|targets| targets.get(*index))
+                    .copied();
+                *index += 1;
+                let Some(next) = next else {
+                    visited.insert(entity, true);
+                    path.pop();
+                    continue;
+                };
+                match visited.get(&next) {
+                    Some(false) => {
+                        let start = path.iter().position(|(entity, _)| *entity == next)?;
+                        return Some(path[start..].iter().map(|(entity, _)| *entity).collect());
+                    }
+                    Some(true) => {}
+                    None => {
+                        visited.insert(next, false);
+                        path.push((next, 0));
+                    }
+                }
+            }
+        }
+        None
+    }
+
     pub fn relation_lookup<'a, const LOCKING: bool, Fetch: TypedRelationLookupFetch<'a>>(
         &'a self,
         entity: Entity,
@@ -1771,10 +2390,15 @@ impl World {
 mod tests {
     use super::*;
     use crate::{
-        commands::{CommandBuffer, DespawnCommand},
-        query::{Exclude, Include, Update},
+        commands::{CommandBuffer, DespawnCommand, DespawnRecursiveCommand},
+        query::{
+            AutoNotify, Exclude, Include, Lookup, QueryError, RelatedIncoming, TraverseIncoming,
+            Update,
+        },
+        view::WorldView,
     };
     use std::{
+        collections::HashSet,
         sync::{Arc, RwLock},
         thread::spawn,
         time::{Duration, Instant},
@@ -1979,6 +2603,668 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_swap_components() {
+        let mut world = World::default();
+        let a = world.spawn((1u8, 10u16)).unwrap();
+        let b = world.spawn((2u8, 20u16)).unwrap();
+        let c = world.spawn((3u8, 30u16, true)).unwrap();
+        let d = world.spawn((4u8,)).unwrap();
+        world.clear_changes();
+
+        world.swap_components::<true, u8>(a, b).unwrap();
+        assert_eq!(*world.component::<true, u8>(a).unwrap(), 2);
+        assert_eq!(*world.component::<true, u8>(b).unwrap(), 1);
+        assert_eq!(*world.component::<true, u16>(a).unwrap(), 10);
+        assert_eq!(*world.component::<true, u16>(b).unwrap(), 20);
+        assert!(world.entity_component_did_changed::<u8>(a));
+        assert!(world.entity_component_did_changed::<u8>(b));
+
+        world.swap_components::<true, u16>(a, c).unwrap();
+        assert_eq!(*world.component::<true, u16>(a).unwrap(), 30);
+        assert_eq!(*world.component::<true, u16>(c).unwrap(), 10);
+
+        world.swap_components::<true, u8>(a, a).unwrap();
+        assert_eq!(*world.component::<true, u8>(a).unwrap(), 2);
+
+        assert!(world.swap_components::<true, u16>(a, d).is_err());
+        assert!(world.swap_components::<true, u16>(d, a).is_err());
+        assert_eq!(*world.component::<true, u16>(a).unwrap(), 30);
+    }
+
+    #[test]
+    fn test_archetype_for() {
+        let mut world = World::default();
+        let a = world.spawn((1u8, 10u16)).unwrap();
+        let b = world.spawn((2u8, 20u16)).unwrap();
+        world.spawn((3u8, 30u16, true)).unwrap();
+        world.spawn((4u8,)).unwrap();
+
+        let archetype = world.archetype_for::<(u16, u8)>().unwrap();
+        assert_eq!(archetype.entities().as_slice(), &[a, b]);
+        assert!(world.archetype_for::<(u8, bool)>().is_none());
+    }
+
+    #[test]
+    fn test_lookup_optional() {
+        let mut world = World::default();
+        let a = world.spawn((1u8, 10u16)).unwrap();
+        let b = world.spawn((2u8,)).unwrap();
+        let c = world.spawn((3u8, 30u16)).unwrap();
+        let d = world.spawn((4u8,)).unwrap();
+        world.despawn(d).unwrap();
+
+        let result = world
+            .lookup_optional::<true, &u16>([a, b, c, d])
+            .map(|(entity, value)| (entity, value.copied()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            result,
+            vec![(a, Some(10)), (b, None), (c, Some(30)), (d, None)]
+        );
+    }
+
+    #[test]
+    fn test_component_or_default() {
+        let mut world = World::default();
+        let a = world.spawn((1u8, 10u16)).unwrap();
+        let b = world.spawn((2u8,)).unwrap();
+
+        assert_eq!(world.component_or_default::<true, u16>(a), 10);
+        assert_eq!(world.component_or_default::<true, u16>(b), 0);
+        world.despawn(a).unwrap();
+        assert_eq!(world.component_or_default::<true, u8>(a), 0);
+    }
+
+    #[test]
+    fn test_world_despawn_recursive() {
+        struct Child;
+
+        let mut world = World::default();
+        let root = world.spawn((0usize,)).unwrap();
+        let mut parents = vec![root];
+        for index in 1..10usize {
+            let entity = world.spawn((index,)).unwrap();
+            world
+                .relate::<true, _>(Child, parents[(index - 1) / 2], entity)
+                .unwrap();
+            parents.push(entity);
+        }
+        let other = world.spawn((100usize,)).unwrap();
+        world.relate::<true, _>(Child, other, parents[3]).unwrap();
+        world.relate::<true, _>(Child, other, root).unwrap();
+
+        let mut commands = CommandBuffer::default();
+        for (entity, value) in world.query::<true, (Entity, &usize)>() {
+            if *value == 1 {
+                commands.command(DespawnRecursiveCommand::<true, Child>::new(entity));
+            }
+        }
+        commands.execute(&mut world);
+
+        // subtree of `1`: 1, 3, 4, 7, 8, 9.
+        let mut values = world.query::<true, &usize>().copied().collect::<Vec<_>>();
+        values.sort();
+        assert_eq!(values, vec![0, 2, 5, 6, 100]);
+        assert_eq!(
+            world
+                .relations_outgoing::<true, Child>(other)
+                .map(|(_, _, to)| to)
+                .collect::<Vec<_>>(),
+            vec![root]
+        );
+        assert!(!world.has_relation::<true, Child>(root, parents[1]));
+        assert!(world.has_relation::<true, Child>(root, parents[2]));
+    }
+
+    #[test]
+    fn test_world_retain_relations() {
+        let mut world = World::default();
+        let a = world.spawn((0u8,)).unwrap();
+        let b = world.spawn((1u8,)).unwrap();
+        let c = world.spawn((2u8,)).unwrap();
+        let d = world.spawn((3u8,)).unwrap();
+        world.relate::<true, _>(1usize, a, b).unwrap();
+        world.relate::<true, _>(2usize, a, c).unwrap();
+        world.relate::<true, _>(3usize, a, d).unwrap();
+        world.relate::<true, _>(4usize, b, c).unwrap();
+        world.relate::<true, _>(5usize, d, a).unwrap();
+        world.despawn(c).unwrap();
+        world.clear_changes();
+
+        let alive = world.entities().collect::<HashSet<_>>();
+        world
+            .retain_relations::<true, usize>(|_, payload, to| {
+                *payload *= 10;
+                alive.contains(&to)
+            })
+            .unwrap();
+
+        let mut relations = world
+            .relations::<true, usize>()
+            .map(|(from, payload, to)| (from, *payload, to))
+            .collect::<Vec<_>>();
+        relations.sort();
+        let mut expected = vec![(a, 10, b), (a, 30, d), (d, 50, a)];
+        expected.sort();
+        assert_eq!(relations, expected);
+        assert!(!world.has_entity_component::<Relation<usize>>(b));
+        assert!(world.has_entity_component::<Relation<usize>>(a));
+        assert!(world.component_did_changed::<Relation<usize>>());
+        assert!(world.entity_component_did_changed::<Relation<usize>>(a));
+        assert!(world.entity_component_did_changed::<Relation<usize>>(b));
+        assert!(world.entity_component_did_changed::<Relation<usize>>(d));
+        assert!(world.removed().has_entity_component::<Relation<usize>>(b));
+    }
+
+    #[test]
+    fn test_world_relations_incoming() {
+        struct Child;
+
+        let mut world = World::default();
+        let a = world.spawn((0u8,)).unwrap();
+        let b = world.spawn((1u8,)).unwrap();
+        let c = world.spawn((2u8,)).unwrap();
+        let d = world.spawn((3u8,)).unwrap();
+        world.relate::<true, _>(Child, a, b).unwrap();
+        world.relate::<true, _>(Child, a, c).unwrap();
+        world.relate::<true, _>(Child, c, d).unwrap();
+
+        assert_eq!(
+            world
+                .relations_incoming::<true, Child>(d)
+                .map(|(from, _, to)| (from, to))
+                .collect::<Vec<_>>(),
+            vec![(c, d)]
+        );
+        assert_eq!(
+            world
+                .traverse_incoming::<true, Child>([d])
+                .collect::<Vec<_>>(),
+            vec![(Entity::INVALID, d), (d, c), (c, a)]
+        );
+        assert_eq!(
+            world
+                .relation_lookup::<true, RelatedIncoming<true, Child, Lookup<true, &u8>>>(d)
+                .map(|value| *value)
+                .collect::<Vec<_>>(),
+            vec![2]
+        );
+        assert_eq!(
+            world
+                .relation_lookup::<true, TraverseIncoming<true, Child, Lookup<true, &u8>>>(d)
+                .map(|value| *value)
+                .collect::<Vec<_>>(),
+            vec![3, 2, 0]
+        );
+        assert_eq!(
+            world
+                .relation_lookup::<true, RelatedIncoming<true, Child, Lookup<true, &u8>>>(a)
+                .count(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_auto_notify() {
+        let mut world = World::default();
+        let a = world.spawn((1usize,)).unwrap();
+        let b = world.spawn((2usize,)).unwrap();
+        world.clear_changes();
+
+        {
+            let value = world.component_mut_notified::<true, usize>(a).unwrap();
+            assert_eq!(*value, 1);
+            assert!(!value.is_dirty());
+        }
+        assert!(!world.entity_component_did_changed::<usize>(a));
+
+        *world.component_mut_notified::<true, usize>(b).unwrap() += 10;
+        assert_eq!(*world.component::<true, usize>(b).unwrap(), 12);
+        assert!(world.entity_component_did_changed::<usize>(b));
+        assert!(!world.entity_component_did_changed::<usize>(a));
+        assert!(world.component_mut_notified::<true, u8>(a).is_err());
+    }
+
+    #[test]
+    fn test_auto_notify_fetch() {
+        let mut world = World::default();
+        let a = world.spawn((1usize,)).unwrap();
+        let b = world.spawn((2usize, 0u8)).unwrap();
+        let c = world.spawn((3usize,)).unwrap();
+        world.clear_changes();
+
+        for (entity, mut value) in world.query::<true, (Entity, AutoNotify<usize>)>() {
+            assert_eq!(value.entity(), entity);
+            if entity != c {
+                *value *= 10;
+            }
+        }
+        assert!(world.entity_component_did_changed::<usize>(a));
+        assert!(world.entity_component_did_changed::<usize>(b));
+        assert!(!world.entity_component_did_changed::<usize>(c));
+        assert_eq!(*world.component::<true, usize>(a).unwrap(), 10);
+        assert_eq!(*world.component::<true, usize>(b).unwrap(), 20);
+        world.clear_changes();
+
+        for mut value in world.lookup::<true, AutoNotify<usize>>([b, c]) {
+            if value.entity() == c {
+                *value += 1;
+            }
+        }
+        assert!(!world.entity_component_did_changed::<usize>(b));
+        assert!(world.entity_component_did_changed::<usize>(c));
+        assert_eq!(*world.component::<true, usize>(c).unwrap(), 4);
+        world.clear_changes();
+
+        *world.lookup_one::<true, AutoNotify<usize>>(a).unwrap() += 1;
+        assert!(world.entity_component_did_changed::<usize>(a));
+        assert_eq!(*world.component::<true, usize>(a).unwrap(), 11);
+
+        let view = WorldView::new::<(usize,)>(&world);
+        assert!(matches!(
+            <AutoNotify<usize> as TypedQueryFetch<true>>::access(view.archetypes().next().unwrap()),
+            Err(QueryError::WorldAccessRequired { .. })
+        ));
+    }
+
+    #[test]
+    fn test_world_query_entity_pairs() {
+        let mut world = World::default();
+        let entities = (0..30)
+            .map(|index| match index % 3 {
+                0 => world.spawn((0usize,)).unwrap(),
+                1 => world.spawn((0usize, 1u8)).unwrap(),
+                _ => world.spawn((0usize, 2u16)).unwrap(),
+            })
+            .collect::<Vec<_>>();
+
+        for (entity, value) in world.query::<true, (Entity, &mut usize)>() {
+            *value = entity.id() as usize;
+        }
+
+        for entity in entities {
+            assert_eq!(
+                *world.component::<true, usize>(entity).unwrap(),
+                entity.id() as usize
+            );
+        }
+        assert_eq!(
+            world
+                .query::<true, (Entity, &usize)>()
+                .filter(|(entity, value)| entity.id() as usize == **value)
+                .count(),
+            30
+        );
+    }
+
+    #[test]
+    fn test_world_counts() {
+        let mut world = World::default();
+        assert_eq!(world.len(), 0);
+        assert_eq!(world.component_count::<u8>(), 0);
+
+        for _ in 0..3 {
+            world.spawn((1u8,)).unwrap();
+        }
+        for _ in 0..4 {
+            world.spawn((1u8, 2u16)).unwrap();
+        }
+        let entity = world.spawn((2u16, 3u32)).unwrap();
+
+        assert_eq!(world.len(), 8);
+        assert_eq!(world.component_count::<u8>(), 7);
+        assert_eq!(world.component_count::<u16>(), 5);
+        assert_eq!(world.component_count::<u32>(), 1);
+        assert_eq!(world.component_count::<u64>(), 0);
+
+        world.despawn(entity).unwrap();
+        assert_eq!(world.len(), 7);
+        assert_eq!(world.component_count::<u32>(), 0);
+        assert_eq!(world.component_count_raw(TypeHash::of::<u16>()), 4);
+    }
+
+    #[test]
+    fn test_world_relation_cycle() {
+        struct Parent;
+
+        let mut world = World::default();
+        let [a, b, c, d] = [0u8, 1, 2, 3].map(|value| world.spawn((value,)).unwrap());
+        world.relate::<true, _>(Parent, a, b).unwrap();
+        world.relate::<true, _>(Parent, a, c).unwrap();
+        world.relate::<true, _>(Parent, b, d).unwrap();
+        world.relate::<true, _>(Parent, c, d).unwrap();
+        assert!(!world.has_relation_cycle::<true, Parent>());
+        assert!(world.relation_cycle::<true, Parent>().is_none());
+
+        world.relate::<true, _>(Parent, d, a).unwrap();
+        assert!(world.has_relation_cycle::<true, Parent>());
+        let mut cycle = world.relation_cycle::<true, Parent>().unwrap();
+        assert_eq!(cycle.len(), 3);
+        let start = cycle.iter().position(|entity| *entity == a).unwrap();
+        cycle.rotate_left(start);
+        assert!(cycle == vec![a, b, d] || cycle == vec![a, c, d]);
+
+        world.unrelate::<true, Parent>(d, a).unwrap();
+        world.relate::<true, _>(Parent, c, c).unwrap();
+        assert_eq!(world.relation_cycle::<true, Parent>(), Some(vec![c]));
+    }
+
+    #[test]
+    fn test_world_bundle_of() {
+        let mut world = World::default();
+        let entity = world.spawn((1u8, 2u16, "hello".to_owned())).unwrap();
+
+        let bundle = world.bundle_of::<true, (u8, String)>(entity).unwrap();
+        assert_eq!(bundle, (1u8, "hello".to_owned()));
+        assert!(world.bundle_of::<true, (u8, u32)>(entity).is_none());
+
+        let mut other = World::default();
+        let cloned = other.spawn(bundle).unwrap();
+        assert_eq!(*other.component::<true, u8>(cloned).unwrap(), 1);
+        assert_eq!(
+            other.component::<true, String>(cloned).unwrap().as_str(),
+            "hello"
+        );
+        assert!(!other.has_entity_component::<u16>(cloned));
+    }
+
+    #[test]
+    fn test_world_par_fold() {
+        let jobs = Jobs::default();
+        let mut world = World::default();
+        for index in 0..1000usize {
+            match index % 3 {
+                0 => world.spawn((index,)).unwrap(),
+                1 => world.spawn((index, true)).unwrap(),
+                _ => world.spawn((index, 1.0f32, false)).unwrap(),
+            };
+        }
+        world.spawn((42u8,)).unwrap();
+
+        let sum = world
+            .par_fold::<true, &usize, _>(
+                &jobs,
+                || 0,
+                |accumulator, value| accumulator + *value,
+                |a, b| a + b,
+            )
+            .unwrap();
+        assert_eq!(sum, world.query::<true, &usize>().copied().sum::<usize>());
+
+        let count = world
+            .par_fold::<true, (&usize, &bool), _>(
+                &jobs,
+                || 0,
+                |accumulator, (_, flag)| accumulator + *flag as usize,
+                |a, b| a + b,
+            )
+            .unwrap();
+        assert_eq!(count, 333);
+
+        let empty = world.par_fold::<true, &i64, _>(&jobs, || 7, |a, _| a + 1, |a, b| a + b);
+        assert_eq!(empty.unwrap(), 7);
+
+        assert!(
+            world
+                .par_fold::<true, &mut usize, _>(&jobs, || 0, |a, _| a + 1, |a, b| a + b)
+                .is_err()
+        );
+
+        let _lock = world
+            .component_mut::<false, usize>(world.query::<true, (Entity, &bool)>().next().unwrap().0)
+            .unwrap();
+        assert!(
+            world
+                .par_fold::<false, &usize, _>(&jobs, || 0, |a, _| a + 1, |a, b| a + b)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_world_query_one_mut() {
+        let mut world = World::default();
+        let a = world.spawn((1u8, 2u16, true)).unwrap();
+        let b = world.spawn((3u8, 4u16)).unwrap();
+
+        let (x, y) = world.query_one_mut::<true, (&mut u8, &mut u16)>(a).unwrap();
+        *x += 10;
+        *y += 20;
+        let (x, flag) = world.query_one_mut::<true, (&mut u8, &bool)>(a).unwrap();
+        *x *= 2;
+        assert!(*flag);
+
+        assert_eq!(*world.component::<true, u8>(a).unwrap(), 22);
+        assert_eq!(*world.component::<true, u16>(a).unwrap(), 22);
+        assert_eq!(*world.component::<true, u8>(b).unwrap(), 3);
+        assert_eq!(*world.component::<true, u16>(b).unwrap(), 4);
+
+        assert!(world.query_one_mut::<true, &mut bool>(b).is_none());
+        world.despawn(a).unwrap();
+        assert!(world.query_one_mut::<true, &mut u8>(a).is_none());
+    }
+
+    #[test]
+    fn test_world_spawn_at() {
+        let mut world = World::default();
+        let a = world.spawn((1u8,)).unwrap();
+        let reserved = world.reserve_entity().unwrap();
+        assert_ne!(a, reserved);
+        assert!(!world.has_entity(reserved));
+        assert_eq!(world.len(), 1);
+
+        // reserved ids are not handed out by regular spawns.
+        let b = world.spawn((2u8,)).unwrap();
+        assert_ne!(b, reserved);
+
+        world.spawn_at(reserved, (3u8, true)).unwrap();
+        assert!(world.has_entity(reserved));
+        assert_eq!(*world.component::<true, u8>(reserved).unwrap(), 3);
+        assert_eq!(world.len(), 3);
+        assert_eq!(
+            world.spawn_at(reserved, (4u8,)),
+            Err(WorldError::EntityAlreadyExists { entity: reserved })
+        );
+        assert_eq!(
+            world.spawn_at(a, (4u8,)),
+            Err(WorldError::EntityAlreadyExists { entity: a })
+        );
+
+        world.despawn(reserved).unwrap();
+        assert_eq!(
+            world.spawn_at(reserved, (4u8,)),
+            Err(WorldError::StaleEntityGeneration { entity: reserved })
+        );
+        let respawned = Entity::new(reserved.id(), reserved.generation() + 5).unwrap();
+        world.spawn_at(respawned, (4u8,)).unwrap();
+        assert!(!world.has_entity(reserved));
+        assert_eq!(*world.component::<true, u8>(respawned).unwrap(), 4);
+
+        // free ids never handed out before can be spawned into directly.
+        let remote = Entity::new(100, 3).unwrap();
+        world.spawn_at(remote, (5u8,)).unwrap();
+        assert!(world.has_entity(remote));
+        assert_eq!(*world.component::<true, u8>(remote).unwrap(), 5);
+        assert_eq!(
+            world.spawn_at(remote, (6u8,)),
+            Err(WorldError::EntityAlreadyExists { entity: remote })
+        );
+        let other = world.reserve_entity().unwrap();
+        let taken = Entity::new(other.id(), other.generation() + 1).unwrap();
+        assert_eq!(
+            world.spawn_at(taken, (6u8,)),
+            Err(WorldError::EntityAlreadyExists { entity: taken })
+        );
+        world.spawn_at(other, (6u8,)).unwrap();
+
+        // skipped ids get reused by regular spawns without clashing.
+        let spawned = (0..100)
+            .map(|index| world.spawn((index as u8,)).unwrap())
+            .collect::<HashSet<_>>();
+        assert_eq!(spawned.len(), 100);
+        assert!(!spawned.contains(&remote));
+        assert!(spawned.iter().all(|entity| entity.id() != remote.id()));
+        assert_eq!(world.len(), 105);
+        assert_eq!(*world.component::<true, u8>(remote).unwrap(), 5);
+    }
+
+    #[test]
+    fn test_world_entity_component_types() {
+        let mut world = World::default();
+        let entity = world.spawn((1u8, 2u16, 3u32)).unwrap();
+        let types = world.entity_component_types(entity).collect::<HashSet<_>>();
+        assert_eq!(
+            types,
+            HashSet::from([
+                TypeHash::of::<u8>(),
+                TypeHash::of::<u16>(),
+                TypeHash::of::<u32>()
+            ])
+        );
+
+        world.despawn(entity).unwrap();
+        assert_eq!(world.entity_component_types(entity).count(), 0);
+    }
+
+    #[test]
+    fn test_world_archetype_changes() {
+        let mut world = World::default();
+        let a = world.spawn((1u8,)).unwrap();
+        let b = world.spawn((2u8, 3u16)).unwrap();
+        assert!(world.archetype_changes().is_empty());
+
+        world.insert(a, (4u16,)).unwrap();
+        world.remove::<(u16,)>(b).unwrap();
+        let (old, new) = world.archetype_changes().get(a).unwrap();
+        assert_eq!(old, &[TypeHash::of::<u8>()]);
+        assert_eq!(new, &[TypeHash::of::<u8>(), TypeHash::of::<u16>()]);
+        let (old, new) = world.archetype_changes().get(b).unwrap();
+        assert_eq!(old, &[TypeHash::of::<u8>(), TypeHash::of::<u16>()]);
+        assert_eq!(new, &[TypeHash::of::<u8>()]);
+        assert_eq!(world.archetype_changes().iter().count(), 2);
+
+        world.remove::<(u16,)>(a).unwrap();
+        assert!(!world.archetype_changes().has_entity(a));
+        assert!(world.archetype_changes().has_entity(b));
+
+        world.clear_changes();
+        assert!(world.archetype_changes().is_empty());
+    }
+
+    #[test]
+    fn test_world_map_component() {
+        let mut world = World::default();
+        let entities = (0..10)
+            .map(|index| {
+                if index % 2 == 0 {
+                    world.spawn((index as usize,)).unwrap()
+                } else {
+                    world.spawn((index as usize, true)).unwrap()
+                }
+            })
+            .collect::<Vec<_>>();
+        let other = world.spawn((false,)).unwrap();
+        world.clear_changes();
+
+        world
+            .map_component::<true, usize>(|value| *value *= 2)
+            .unwrap();
+
+        for (index, entity) in entities.into_iter().enumerate() {
+            assert_eq!(*world.component::<true, usize>(entity).unwrap(), index * 2);
+            assert!(world.entity_component_did_changed::<usize>(entity));
+            assert!(!world.entity_component_did_changed::<bool>(entity));
+        }
+        assert!(!world.entity_component_did_changed::<bool>(other));
+    }
+
+    #[test]
+    fn test_world_query_chunks() {
+        let mut world = World::default();
+        for index in 0..5u8 {
+            world.spawn((index, index as u16 * 10)).unwrap();
+        }
+        for index in 5..8u8 {
+            world.spawn((index, index as u16 * 10, true)).unwrap();
+        }
+        world.spawn((100u8,)).unwrap();
+
+        let expected = world
+            .query::<true, (Entity, &u8, &u16)>()
+            .map(|(entity, a, b)| (entity, *a, *b))
+            .collect::<Vec<_>>();
+
+        let mut provided = Vec::new();
+        let mut chunks = 0;
+        for chunk in world.query_chunks::<true, (&u8, &mut u16)>() {
+            let (entities, (a, mut b)) = chunk.unwrap();
+            let b = b.as_mut_slice().unwrap();
+            assert_eq!(entities.len(), a.as_slice().len());
+            assert_eq!(entities.len(), b.len());
+            for value in b.iter_mut() {
+                *value += 1;
+            }
+            provided.extend(
+                entities
+                    .iter()
+                    .zip(a.as_slice())
+                    .zip(b.iter())
+                    .map(|((entity, a), b)| (*entity, *a, *b - 1)),
+            );
+            chunks += 1;
+        }
+        assert_eq!(chunks, 2);
+        assert_eq!(provided, expected);
+        assert!(
+            world
+                .query::<true, (&u8, &u16)>()
+                .all(|(a, b)| *b == *a as u16 * 10 + 1)
+        );
+
+        let locked = world.query::<true, (Entity, &bool)>().next().unwrap().0;
+        let _lock = world.component_mut::<false, u16>(locked).unwrap();
+        let count = world
+            .query_chunks::<false, (&u8, &u16)>()
+            .map(|chunk| chunk.map(|(entities, _)| entities.len()).ok())
+            .collect::<Vec<_>>();
+        assert_eq!(count.len(), 2);
+        assert!(count.contains(&Some(5)));
+        assert!(count.contains(&None));
+    }
+
+    #[test]
+    fn test_dynamic_query_or_group() {
+        let mut world = World::default();
+        world.spawn((0u8, 1u16)).unwrap();
+        world.spawn((2u8, 3u32)).unwrap();
+        world.spawn((4u8, 5u16, 6u32)).unwrap();
+        world.spawn((7u8, 8u64)).unwrap();
+
+        let filter = DynamicQueryFilter::default()
+            .read::<u8>()
+            .or_group(&[TypeHash::of::<u16>(), TypeHash::of::<u32>()]);
+        let mut values = world
+            .dynamic_query::<true>(&filter)
+            .map(|item| *item.read::<u8>().unwrap().read::<u8>().unwrap())
+            .collect::<Vec<_>>();
+        values.sort();
+        assert_eq!(values, vec![0, 2, 4]);
+
+        let filter = filter.or_group(&[TypeHash::of::<u64>()]);
+        assert_eq!(world.dynamic_query::<true>(&filter).count(), 0);
+
+        let filter = DynamicQueryFilter::default()
+            .read::<u8>()
+            .exclude::<u32>()
+            .or_group(&[TypeHash::of::<u16>(), TypeHash::of::<u64>()]);
+        let mut values = world
+            .dynamic_query::<true>(&filter)
+            .map(|item| *item.read::<u8>().unwrap().read::<u8>().unwrap())
+            .collect::<Vec<_>>();
+        values.sort();
+        assert_eq!(values, vec![0, 7]);
+    }
+
     #[test]
     fn test_world_lookup() {
         const N: usize = if cfg!(miri) { 10 } else { 1000 };
@@ -2137,28 +3423,28 @@ mod tests {
 
         assert_eq!(
             world
-                .relations_incomming::<true, Parent>(a)
+                .relations_incoming::<true, Parent>(a)
                 .map(|(entity, _, _)| entity)
                 .collect::<Vec<_>>(),
             vec![b, c]
         );
         assert_eq!(
             world
-                .relations_incomming::<true, Parent>(b)
+                .relations_incoming::<true, Parent>(b)
                 .map(|(entity, _, _)| entity)
                 .collect::<Vec<_>>(),
             vec![]
         );
         assert_eq!(
             world
-                .relations_incomming::<true, Parent>(c)
+                .relations_incoming::<true, Parent>(c)
                 .map(|(entity, _, _)| entity)
                 .collect::<Vec<_>>(),
             vec![d]
         );
         assert_eq!(
             world
-                .relations_incomming::<true, Parent>(d)
+                .relations_incoming::<true, Parent>(d)
                 .map(|(entity, _, _)| entity)
                 .collect::<Vec<_>>(),
             vec![]
@@ -2201,7 +3487,7 @@ mod tests {
         );
 
         for (entity, _) in world.query::<true, (Entity, Include<Root>)>() {
-            for (other, _, _) in world.relations_incomming::<true, Parent>(entity) {
+            for (other, _, _) in world.relations_incoming::<true, Parent>(entity) {
                 let mut v = world.get::<true, bool>(other, true).unwrap();
                 let v = v.write().unwrap();
                 *v = !*v;
@@ -2219,6 +3505,31 @@ mod tests {
         assert!(world.query::<true, &Relation<Parent>>().count() == 0);
     }
 
+    #[test]
+    fn test_relation_payload() {
+        #[derive(Debug, PartialEq)]
+        struct Weight(f32);
+
+        let mut world = World::default();
+        let a = world.spawn((0u8,)).unwrap();
+        let b = world.spawn((1u8,)).unwrap();
+        let c = world.spawn((2u8,)).unwrap();
+        world.relate::<true, _>(Weight(0.5), a, b).unwrap();
+
+        let payload = world.relation_payload::<true, Weight>(a, b).unwrap();
+        assert_eq!(payload.entity(), b);
+        assert_eq!(*payload, Weight(0.5));
+        drop(payload);
+        assert!(world.relation_payload::<true, Weight>(a, c).is_none());
+        assert!(world.relation_payload::<true, Weight>(b, a).is_none());
+
+        world.relation_payload_mut::<true, Weight>(a, b).unwrap().0 = 2.0;
+        assert_eq!(
+            *world.relation_payload::<true, Weight>(a, b).unwrap(),
+            Weight(2.0)
+        );
+    }
+
     #[test]
     fn test_world_async() {
         const N: usize = if cfg!(miri) { 10 } else { 1000 };
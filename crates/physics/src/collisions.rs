@@ -2,7 +2,7 @@ use crate::{
     PhysicsAccessView, PhysicsSimulation, Scalar,
     components::{
         AngularVelocity, BodyAccessInfo, BodyMaterial, BodyParentRelation, BodyParticleRelation,
-        LinearVelocity, Mass, PhysicsBody, PhysicsParticle, Position, Rotation,
+        Kinematic, LinearVelocity, Mass, PhysicsBody, PhysicsParticle, Position, Rotation,
     },
     density_fields::{DensityField, DensityFieldBox},
     queries::shape::{ShapeOverlapCell, ShapeOverlapQuery},
@@ -29,7 +29,7 @@ use std::{
     hash::Hash,
     ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Range},
 };
-use vek::{Aabb, Vec3};
+use vek::{Aabb, Quaternion, Vec3};
 
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[repr(transparent)]
@@ -77,6 +77,19 @@ impl CollisionMask {
     pub fn is_subset_of(&self, other: Self) -> bool {
         (self.0 & other.0) == self.0
     }
+
+    /// Iterates over indices of enabled flags, in ascending order.
+    pub fn iter_flags(&self) -> impl Iterator<Item = u128> {
+        let mut bits = self.0;
+        std::iter::from_fn(move || {
+            if bits == 0 {
+                return None;
+            }
+            let index = bits.trailing_zeros() as u128;
+            bits &= bits - 1;
+            Some(index)
+        })
+    }
 }
 
 impl BitAnd for CollisionMask {
@@ -235,12 +248,79 @@ pub struct ContactEvent {
     pub other_density_field: Entity,
 }
 
+/// Dynamic predicate deciding whether detected contact should be kept.
+///
+/// When present as resource, contacts collection consults it after
+/// collision profiles and detection settings let contact through, and drops
+/// contacts it rejects. World is provided so predicate can read bodies
+/// state, e.g. letting bodies moving upward pass through one-way platforms.
+pub struct ContactFilter {
+    #[allow(clippy::type_complexity)]
+    filter: Box<dyn Fn(&World, EntityPair, &DensityFieldContact) -> bool + Send + Sync>,
+}
+
+impl ContactFilter {
+    pub fn new(
+        filter: impl Fn(&World, EntityPair, &DensityFieldContact) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            filter: Box::new(filter),
+        }
+    }
+
+    pub fn allows(&self, world: &World, pair: EntityPair, contact: &DensityFieldContact) -> bool {
+        (self.filter)(world, pair, contact)
+    }
+}
+
+/// Global queue of contact events dispatched to all bodies.
+///
+/// Events accumulate across steps until consumers drain them, so none get
+/// lost when simulation steps multiple times per frame.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ContactEvents {
+    events: Vec<ContactEvent>,
+}
+
+impl ContactEvents {
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.events.clear();
+    }
+
+    pub fn push(&mut self, event: ContactEvent) {
+        self.events.push(event);
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &ContactEvent> {
+        self.events.iter()
+    }
+
+    pub fn drain(&mut self) -> impl Iterator<Item = ContactEvent> + '_ {
+        self.events.drain(..)
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct ContactDetection {
     pub enabled: bool,
     pub density_threshold: Option<Scalar>,
     pub voxelization_size_limit: Option<Scalar>,
     pub depth_limit: usize,
+    pub hysteresis: Option<ContactHysteresis>,
+    pub adaptive_depth: Option<AdaptiveContactDepth>,
+    /// Allows contacts between different density fields of the same body,
+    /// which are skipped by default so compound body shapes do not collide
+    /// with each other. Useful for intentional self-collision of soft bodies.
+    #[serde(default)]
+    pub self_collision: bool,
 }
 
 impl Default for ContactDetection {
@@ -250,6 +330,67 @@ impl Default for ContactDetection {
             density_threshold: None,
             voxelization_size_limit: None,
             depth_limit: usize::MAX,
+            hysteresis: None,
+            adaptive_depth: None,
+            self_collision: false,
+        }
+    }
+}
+
+/// Raises contact detection depth limit for fast approaching bodies.
+///
+/// Every `speed_per_level` of relative linear velocity between bodies adds
+/// one extra subdivision level on top of [`ContactDetection::depth_limit`],
+/// up to `max_depth_limit`, so voxelization budget is spent on pairs that
+/// need finer cells to resolve deep penetration.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct AdaptiveContactDepth {
+    pub speed_per_level: Scalar,
+    pub max_depth_limit: usize,
+}
+
+impl AdaptiveContactDepth {
+    pub fn new(speed_per_level: Scalar, max_depth_limit: usize) -> Self {
+        Self {
+            speed_per_level,
+            max_depth_limit,
+        }
+    }
+
+    pub fn depth_limit(&self, depth_limit: usize, relative_speed: Scalar) -> usize {
+        if self.speed_per_level <= Scalar::EPSILON || depth_limit >= self.max_depth_limit {
+            return depth_limit;
+        }
+        let levels = (relative_speed.abs() / self.speed_per_level) as usize;
+        depth_limit.saturating_add(levels).min(self.max_depth_limit)
+    }
+}
+
+/// Separate density thresholds for beginning and keeping contacts.
+///
+/// Contact has to overlap above `enter_density_threshold` to begin, and ends
+/// only once overlap drops below `exit_density_threshold`, which prevents
+/// contacts at the boundary from flickering between steps. When set, it takes
+/// precedence over [`ContactDetection::density_threshold`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ContactHysteresis {
+    pub enter_density_threshold: Scalar,
+    pub exit_density_threshold: Scalar,
+}
+
+impl ContactHysteresis {
+    pub fn new(enter_density_threshold: Scalar, exit_density_threshold: Scalar) -> Self {
+        Self {
+            enter_density_threshold,
+            exit_density_threshold,
+        }
+    }
+
+    pub fn density_threshold(&self, was_in_contact: bool) -> Scalar {
+        if was_in_contact {
+            self.exit_density_threshold
+        } else {
+            self.enter_density_threshold
         }
     }
 }
@@ -292,6 +433,111 @@ impl PointDistance for DensityFieldSpatialObject {
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
+struct DensityFieldAabbCacheParticle {
+    entity: Entity,
+    position: Option<Vec3<Scalar>>,
+    rotation: Option<Quaternion<Scalar>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct DensityFieldAabbCacheEntry {
+    body: Entity,
+    particles: Vec<DensityFieldAabbCacheParticle>,
+    aabb: Aabb<Scalar>,
+}
+
+/// Opt-in cache of density field AABBs, put on density field entity.
+///
+/// Cached AABB is keyed on particles related to parent body through
+/// [`BodyParticleRelation`], so it works for bodies being their own particle
+/// as well as for bodies with separate particle entities. Spatial extraction
+/// reuses cached AABB as long as the field was not reported changed, body
+/// particles stay the same, and their `Position` and `Rotation` were neither
+/// reported changed nor differ from cached ones (solvers move particles without
+/// reporting changes). Fields whose AABB depends on anything else should be
+/// invalidated manually when that changes.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct DensityFieldAabbCache {
+    entries: Vec<DensityFieldAabbCacheEntry>,
+}
+
+impl DensityFieldAabbCache {
+    pub fn invalidate(&mut self) {
+        self.entries.clear();
+    }
+
+    pub fn invalidate_body(&mut self, body: Entity) {
+        self.entries.retain(|entry| entry.body != body);
+    }
+
+    pub fn cached(&self, body: Entity) -> Option<Aabb<Scalar>> {
+        self.entries
+            .iter()
+            .find(|entry| entry.body == body)
+            .map(|entry| entry.aabb)
+    }
+
+    fn get_or_compute<const LOCKING: bool>(
+        &mut self,
+        world: &World,
+        body: Entity,
+        compute: impl FnOnce() -> Aabb<Scalar>,
+    ) -> Aabb<Scalar> {
+        let mut changed =
+            world.entity_component_did_changed::<Relation<BodyParticleRelation>>(body);
+        let particles = world
+            .component::<LOCKING, Relation<BodyParticleRelation>>(body)
+            .ok()
+            .map(|relation| {
+                relation
+                    .iter()
+                    .map(|(_, entity)| {
+                        changed = changed
+                            || world.entity_component_did_changed::<Position>(entity)
+                            || world.entity_component_did_changed::<Rotation>(entity);
+                        DensityFieldAabbCacheParticle {
+                            entity,
+                            position: world
+                                .component::<LOCKING, Position>(entity)
+                                .ok()
+                                .map(|position| position.current),
+                            rotation: world
+                                .component::<LOCKING, Rotation>(entity)
+                                .ok()
+                                .map(|rotation| rotation.current),
+                        }
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        if !changed
+            && let Some(entry) = self
+                .entries
+                .iter()
+                .find(|entry| entry.body == body && entry.particles == particles)
+        {
+            return entry.aabb;
+        }
+        let aabb = compute();
+        self.invalidate_body(body);
+        self.entries.push(DensityFieldAabbCacheEntry {
+            body,
+            particles,
+            aabb,
+        });
+        aabb
+    }
+}
+
+/// Extracts spatial objects from density field entities.
+///
+/// `CollisionProfile` is read from the density field entity itself, not from
+/// its parent body, so fields of a compound body can carry their own profiles
+/// (e.g. one sub-shape blocks while another only overlaps). Fields without a
+/// profile use the default one.
+///
+/// Fields with [`DensityFieldAabbCache`] reuse their cached AABB when clean.
 pub struct DensityFieldSpatialExtractor;
 
 impl SpatialExtractor for DensityFieldSpatialExtractor {
@@ -307,25 +553,39 @@ impl SpatialExtractor for DensityFieldSpatialExtractor {
                 &DensityFieldBox,
                 Option<&CollisionProfile>,
                 &Relation<BodyParentRelation>,
+                Option<&mut DensityFieldAabbCache>,
             )>()
-            .flat_map(move |(entity, density_field, collision_profile, parents)| {
-                let view = view.clone();
-                parents.iter().map(move |(_, parent)| {
-                    let info = BodyAccessInfo {
-                        entity: parent,
-                        view: view.clone(),
-                    };
-                    let aabb = density_field.aabb(&info);
-                    (
-                        entity,
-                        DensityFieldSpatialObject {
-                            body_entity: parent,
-                            aabb,
-                            collision_profile: collision_profile.cloned().unwrap_or_default(),
-                        },
-                    )
-                })
-            })
+            .flat_map(
+                move |(entity, density_field, collision_profile, parents, mut cache)| {
+                    let view = view.clone();
+                    if let Some(cache) = cache.as_deref_mut()
+                        && world.entity_component_did_changed::<DensityFieldBox>(entity)
+                    {
+                        cache.invalidate();
+                    }
+                    parents.iter().map(move |(_, parent)| {
+                        let info = BodyAccessInfo {
+                            entity: parent,
+                            view: view.clone(),
+                        };
+                        let aabb = if let Some(cache) = cache.as_deref_mut() {
+                            cache.get_or_compute::<LOCKING>(world, parent, || {
+                                density_field.aabb(&info)
+                            })
+                        } else {
+                            density_field.aabb(&info)
+                        };
+                        (
+                            entity,
+                            DensityFieldSpatialObject {
+                                body_entity: parent,
+                                aabb,
+                                collision_profile: collision_profile.cloned().unwrap_or_default(),
+                            },
+                        )
+                    })
+                },
+            )
     }
 }
 
@@ -347,6 +607,57 @@ pub struct DensityFieldContact<'a> {
     pub movement_since_last_step: Vec3<Scalar>,
 }
 
+impl<'a> DensityFieldContact<'a> {
+    /// Orients contact from perspective of given density field, so `self_*`
+    /// entities match it regardless of internal pair ordering.
+    pub fn oriented(self, density_field: Entity) -> Option<OrientedDensityFieldContact<'a>> {
+        let self_index = self
+            .density_fields
+            .iter()
+            .position(|entity| *entity == density_field)?;
+        Some(OrientedDensityFieldContact {
+            contact: self,
+            self_index,
+        })
+    }
+}
+
+/// Density field contact viewed from one of its density fields.
+///
+/// Cells keep per-field data in internal order, so use [`Self::self_index`]
+/// and [`Self::other_index`] to access their `density` and `normal` arrays.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OrientedDensityFieldContact<'a> {
+    pub contact: DensityFieldContact<'a>,
+    self_index: usize,
+}
+
+impl OrientedDensityFieldContact<'_> {
+    pub fn self_index(&self) -> usize {
+        self.self_index
+    }
+
+    pub fn other_index(&self) -> usize {
+        1 - self.self_index
+    }
+
+    pub fn self_body(&self) -> Entity {
+        self.contact.bodies[self.self_index()]
+    }
+
+    pub fn other_body(&self) -> Entity {
+        self.contact.bodies[self.other_index()]
+    }
+
+    pub fn self_density_field(&self) -> Entity {
+        self.contact.density_fields[self.self_index()]
+    }
+
+    pub fn other_density_field(&self) -> Entity {
+        self.contact.density_fields[self.other_index()]
+    }
+}
+
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct EntityPair([Entity; 2]);
 
@@ -392,6 +703,10 @@ pub struct ContactsCache {
     saved_contact_center_of_mass: HashMap<EntityPair, Vec3<Scalar>>,
     contacts_began: HashSet<EntityPair>,
     contacts_ended: HashSet<EntityPair>,
+    generation: u64,
+    /// Displacement blocking contacts applied to their bodies during last
+    /// solver step, used to warm-start solving contacts that persist.
+    resolved_displacements: HashMap<EntityPair, [(Entity, Vec3<Scalar>); 2]>,
 }
 
 impl ContactsCache {
@@ -399,11 +714,20 @@ impl ContactsCache {
         self.overlapping_contacts.len() + self.blocking_contacts.len()
     }
 
+    /// Monotonically increasing counter bumped whenever set of contacts
+    /// changes, so consumers can skip work when it stays the same.
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
     pub fn is_empty(&self) -> bool {
         self.overlapping_contacts.is_empty() && self.blocking_contacts.is_empty()
     }
 
     pub fn clear(&mut self) {
+        if !self.is_empty() {
+            self.generation += 1;
+        }
         self.cells.clear();
         self.overlapping_contacts.clear();
         self.blocking_contacts.clear();
@@ -412,6 +736,7 @@ impl ContactsCache {
         self.saved_contact_center_of_mass.clear();
         self.contacts_began.clear();
         self.contacts_ended.clear();
+        self.resolved_displacements.clear();
     }
 
     pub fn begin_contacts_update(&mut self) {
@@ -467,6 +792,10 @@ impl ContactsCache {
                         .filter(|pair| !self.blocking_contacts.contains_key(pair)),
                 ),
         );
+
+        if !self.contacts_began.is_empty() || !self.contacts_ended.is_empty() {
+            self.generation += 1;
+        }
     }
 
     pub fn contacts_began(&self) -> impl Iterator<Item = EntityPair> + '_ {
@@ -565,6 +894,28 @@ impl ContactsCache {
             .or_else(|| self.blocking_contact_between(a, b))
     }
 
+    /// Returns triangle soup of cells forming any contact between density
+    /// fields, useful for contact visualization and debug meshing.
+    pub fn contact_triangles(
+        &'_ self,
+        a: Entity,
+        b: Entity,
+    ) -> impl Iterator<Item = [Vec3<Scalar>; 3]> + '_ {
+        self.any_contact_between(a, b)
+            .into_iter()
+            .flat_map(|contact| contact.cells.iter().flat_map(|cell| cell.triangles()))
+    }
+
+    /// Returns any contact between density fields, oriented so `a` is its
+    /// `self` side.
+    pub fn oriented_contact_between(
+        &'_ self,
+        a: Entity,
+        b: Entity,
+    ) -> Option<OrientedDensityFieldContact<'_>> {
+        self.any_contact_between(a, b)?.oriented(a)
+    }
+
     pub fn overlapping_contacts_of(
         &'_ self,
         entity: Entity,
@@ -635,120 +986,172 @@ impl ContactsCache {
 }
 
 pub fn collect_contacts<const LOCKING: bool>(context: SystemContext) -> Result<(), Box<dyn Error>> {
-    let (world, mut contacts, spatial, density_field_lookup, shape_query_local) = context
-        .fetch::<(
-            &World,
-            Res<LOCKING, &mut ContactsCache>,
-            Res<LOCKING, &SpatialPartitioning<DensityFieldSpatialExtractor>>,
-            // density field lookup
-            Lookup<LOCKING, (&DensityFieldBox, &ContactDetection)>,
-            Local<LOCKING, &ShapeOverlapQuery>,
-        )>()?;
+    let (
+        world,
+        mut contacts,
+        filter,
+        spatial,
+        density_field_lookup,
+        velocity_lookup,
+        shape_query_local,
+    ) = context.fetch::<(
+        &World,
+        Res<LOCKING, &mut ContactsCache>,
+        Res<LOCKING, Option<&ContactFilter>>,
+        Res<LOCKING, &SpatialPartitioning<DensityFieldSpatialExtractor>>,
+        // density field lookup
+        Lookup<LOCKING, (&DensityFieldBox, &ContactDetection)>,
+        // body velocity lookup
+        Lookup<LOCKING, &LinearVelocity>,
+        Local<LOCKING, &ShapeOverlapQuery>,
+    )>()?;
 
     contacts.begin_contacts_update();
 
     let view = PhysicsAccessView::new(world);
     let mut lookup_access = density_field_lookup.lookup_access(world);
-    let tree = spatial.tree();
+    let mut velocity_lookup_access = velocity_lookup.lookup_access(world);
+
+    for (a, b) in spatial.overlapping_geom_pairs() {
+        let pair = EntityPair::new(a.data, b.data);
+        // fields with multiple parents produce multiple spatial objects.
+        if contacts.blocking_contacts.contains_key(&pair)
+            || contacts.overlapping_contacts.contains_key(&pair)
+        {
+            continue;
+        }
 
-    for a in tree.iter() {
-        for b in tree.locate_in_envelope_intersecting(&a.envelope()) {
-            if a.data == b.data {
-                continue;
-            }
-            let pair = EntityPair::new(a.data, b.data);
-            if contacts.blocking_contacts.contains_key(&pair)
-                || contacts.overlapping_contacts.contains_key(&pair)
-            {
-                continue;
-            }
+        let is_overlapping = a
+            .geom()
+            .collision_profile
+            .does_overlap_permissive(&b.geom().collision_profile);
+        let is_blocking = a
+            .geom()
+            .collision_profile
+            .does_block(&b.geom().collision_profile);
+        if !is_overlapping && !is_blocking {
+            continue;
+        }
 
-            let is_overlapping = a
-                .geom()
-                .collision_profile
-                .does_overlap_permissive(&b.geom().collision_profile);
-            let is_blocking = a
-                .geom()
-                .collision_profile
-                .does_block(&b.geom().collision_profile);
-            if !is_overlapping && !is_blocking {
-                continue;
-            }
+        let Some((field_a, detection_a)) = lookup_access.access(a.data) else {
+            continue;
+        };
+        let Some((field_b, detection_b)) = lookup_access.access(b.data) else {
+            continue;
+        };
+        if !detection_a.enabled || !detection_b.enabled {
+            continue;
+        }
+        if a.geom().body_entity == b.geom().body_entity
+            && !detection_a.self_collision
+            && !detection_b.self_collision
+        {
+            continue;
+        }
 
-            let Some((field_a, detection_a)) = lookup_access.access(a.data) else {
-                continue;
-            };
-            let Some((field_b, detection_b)) = lookup_access.access(b.data) else {
-                continue;
-            };
-            if !detection_a.enabled || !detection_b.enabled {
-                continue;
+        let fields: [&dyn DensityField; 2] = [&**field_a, &**field_b];
+        let infos = [
+            &BodyAccessInfo {
+                entity: a.geom().body_entity,
+                view: view.clone(),
+            },
+            &BodyAccessInfo {
+                entity: b.geom().body_entity,
+                view: view.clone(),
+            },
+        ];
+        let mut query = shape_query_local.clone();
+        query.region_limit = if let Some(region_limit) = query.region_limit {
+            Some(
+                a.geom()
+                    .aabb
+                    .intersection(b.geom().aabb)
+                    .intersection(region_limit),
+            )
+        } else {
+            Some(a.geom().aabb.intersection(b.geom().aabb))
+        };
+        let was_in_contact = contacts.saved_blocking_contacts.contains_key(&pair)
+            || contacts.saved_overlapping_contacts.contains_key(&pair);
+        if let Some(value) = [detection_a, detection_b]
+            .into_iter()
+            .filter_map(|detection| detection.hysteresis)
+            .map(|hysteresis| hysteresis.density_threshold(was_in_contact))
+            .reduce(Scalar::min)
+        {
+            query.density_threshold = value;
+        }
+        for detection in [detection_a, detection_b] {
+            if detection.hysteresis.is_none()
+                && let Some(value) = detection.density_threshold
+            {
+                query.density_threshold = query.density_threshold.min(value);
             }
-
-            let fields: [&dyn DensityField; 2] = [&**field_a, &**field_b];
-            let infos = [
-                &BodyAccessInfo {
-                    entity: a.geom().body_entity,
-                    view: view.clone(),
-                },
-                &BodyAccessInfo {
-                    entity: b.geom().body_entity,
-                    view: view.clone(),
-                },
-            ];
-            let mut query = shape_query_local.clone();
-            query.region_limit = if let Some(region_limit) = query.region_limit {
-                Some(
-                    a.geom()
-                        .aabb
-                        .intersection(b.geom().aabb)
-                        .intersection(region_limit),
-                )
+            if let Some(value) = detection.voxelization_size_limit {
+                query.voxelization_size_limit = query.voxelization_size_limit.min(value);
+            }
+        }
+        let relative_speed =
+            if detection_a.adaptive_depth.is_some() || detection_b.adaptive_depth.is_some() {
+                let [velocity_a, velocity_b] = [a, b].map(|object| {
+                    velocity_lookup_access
+                        .access(object.geom().body_entity)
+                        .map(|velocity| velocity.value)
+                        .unwrap_or_default()
+                });
+                velocity_a.distance(velocity_b)
             } else {
-                Some(a.geom().aabb.intersection(b.geom().aabb))
+                0.0
             };
-            for detection in [detection_a, detection_b] {
-                if let Some(value) = detection.density_threshold {
-                    query.density_threshold = query.density_threshold.min(value);
-                }
-                if let Some(value) = detection.voxelization_size_limit {
-                    query.voxelization_size_limit = query.voxelization_size_limit.min(value);
-                }
-            }
-            query.depth_limit = query
-                .depth_limit
-                .min(detection_a.depth_limit)
-                .min(detection_b.depth_limit);
-            let start = contacts.cells.len();
-            let Some(overlap_region) = query.query_field_pair(fields, infos, &mut contacts.cells)
-            else {
-                continue;
+        for detection in [detection_a, detection_b] {
+            let depth_limit = detection
+                .adaptive_depth
+                .map(|adaptive| adaptive.depth_limit(detection.depth_limit, relative_speed))
+                .unwrap_or(detection.depth_limit);
+            query.depth_limit = query.depth_limit.min(depth_limit);
+        }
+        let start = contacts.cells.len();
+        let Some(overlap_region) = query.query_field_pair(fields, infos, &mut contacts.cells)
+        else {
+            continue;
+        };
+        let end = contacts.cells.len();
+        if end > start {
+            let center_of_mass = contacts.cells[start..end]
+                .iter()
+                .map(|cell| cell.region.center())
+                .sum::<Vec3<Scalar>>()
+                / (end - start) as Scalar;
+            let prev_center_of_mass = contacts
+                .saved_contact_center_of_mass
+                .get(&pair)
+                .copied()
+                .unwrap_or(center_of_mass);
+            let contact = Contact {
+                cells_range: start..end,
+                bodies: [a.geom().body_entity, b.geom().body_entity],
+                density_fields: [a.data, b.data],
+                overlap_region,
+                movement_since_last_step: center_of_mass - prev_center_of_mass,
             };
-            let end = contacts.cells.len();
-            if end > start {
-                let center_of_mass = contacts.cells[start..end]
-                    .iter()
-                    .map(|cell| cell.region.center())
-                    .sum::<Vec3<Scalar>>()
-                    / (end - start) as Scalar;
-                let prev_center_of_mass = contacts
-                    .saved_contact_center_of_mass
-                    .get(&pair)
-                    .copied()
-                    .unwrap_or(center_of_mass);
-                let contact = Contact {
-                    cells_range: start..end,
-                    bodies: [a.geom().body_entity, b.geom().body_entity],
-                    density_fields: [a.data, b.data],
-                    overlap_region,
-                    movement_since_last_step: center_of_mass - prev_center_of_mass,
+            if let Some(filter) = filter.as_ref() {
+                let density_field_contact = DensityFieldContact {
+                    cells: &contacts.cells[start..end],
+                    bodies: contact.bodies,
+                    density_fields: contact.density_fields,
+                    overlap_region: contact.overlap_region,
+                    movement_since_last_step: contact.movement_since_last_step,
                 };
-                if is_blocking {
-                    contacts.blocking_contacts.insert(pair, contact);
-                } else {
-                    contacts.overlapping_contacts.insert(pair, contact);
+                if !filter.allows(world, pair, &density_field_contact) {
+                    contacts.cells.truncate(start);
+                    continue;
                 }
             }
+            if is_blocking {
+                contacts.blocking_contacts.insert(pair, contact);
+            } else {
+                contacts.overlapping_contacts.insert(pair, contact);
+            }
         }
     }
 
@@ -760,15 +1163,38 @@ pub fn collect_contacts<const LOCKING: bool>(context: SystemContext) -> Result<(
 pub fn dispatch_contact_events<const LOCKING: bool>(
     context: SystemContext,
 ) -> Result<(), Box<dyn Error>> {
-    let (world, contacts, events_lookup) = context.fetch::<(
+    let (world, contacts, mut global_events, events_lookup) = context.fetch::<(
         &World,
         Res<LOCKING, &ContactsCache>,
+        Res<LOCKING, Option<&mut ContactEvents>>,
         // body lookup.
         Lookup<LOCKING, &EventDispatcher<ContactEvent>>,
     )>()?;
 
     let mut events_lookup = events_lookup.lookup_access(world);
 
+    let mut dispatch = |contact: &Contact, kind: ContactEventKind, blocking: bool| {
+        let body_events = contact.bodies.map(|entity| events_lookup.access(entity));
+
+        for (index, body_event) in body_events.into_iter().enumerate() {
+            let other_index = 1 - index;
+            let event = ContactEvent {
+                kind,
+                blocking,
+                self_body: contact.bodies[index],
+                other_body: contact.bodies[other_index],
+                self_density_field: contact.density_fields[index],
+                other_density_field: contact.density_fields[other_index],
+            };
+            if let Some(body_event) = body_event {
+                body_event.dispatch(&event);
+            }
+            if let Some(global_events) = global_events.as_mut() {
+                global_events.push(event);
+            }
+        }
+    };
+
     for (contact, blocking, began) in contacts
         .blocking_contacts
         .values()
@@ -791,51 +1217,12 @@ pub fn dispatch_contact_events<const LOCKING: bool>(
             )
         }))
     {
-        let body_events = contact.bodies.map(|entity| events_lookup.access(entity));
-
-        if began {
-            if let Some(event) = body_events[0] {
-                event.dispatch(&ContactEvent {
-                    kind: ContactEventKind::Began,
-                    blocking,
-                    self_body: contact.bodies[0],
-                    other_body: contact.bodies[1],
-                    self_density_field: contact.density_fields[0],
-                    other_density_field: contact.density_fields[1],
-                });
-            }
-            if let Some(event) = body_events[1] {
-                event.dispatch(&ContactEvent {
-                    kind: ContactEventKind::Began,
-                    blocking,
-                    self_body: contact.bodies[1],
-                    other_body: contact.bodies[0],
-                    self_density_field: contact.density_fields[1],
-                    other_density_field: contact.density_fields[0],
-                });
-            }
+        let kind = if began {
+            ContactEventKind::Began
         } else {
-            if let Some(event) = body_events[0] {
-                event.dispatch(&ContactEvent {
-                    kind: ContactEventKind::Continue,
-                    blocking,
-                    self_body: contact.bodies[0],
-                    other_body: contact.bodies[1],
-                    self_density_field: contact.density_fields[0],
-                    other_density_field: contact.density_fields[1],
-                });
-            }
-            if let Some(event) = body_events[1] {
-                event.dispatch(&ContactEvent {
-                    kind: ContactEventKind::Continue,
-                    blocking,
-                    self_body: contact.bodies[1],
-                    other_body: contact.bodies[0],
-                    self_density_field: contact.density_fields[1],
-                    other_density_field: contact.density_fields[0],
-                });
-            }
-        }
+            ContactEventKind::Continue
+        };
+        dispatch(contact, kind, blocking);
     }
 
     for (contact, blocking) in contacts
@@ -851,28 +1238,7 @@ pub fn dispatch_contact_events<const LOCKING: bool>(
                 .map(|(_, contact)| (contact, false)),
         )
     {
-        let body_events = contact.bodies.map(|entity| events_lookup.access(entity));
-
-        if let Some(event) = body_events[0] {
-            event.dispatch(&ContactEvent {
-                kind: ContactEventKind::Ended,
-                blocking,
-                self_body: contact.bodies[0],
-                other_body: contact.bodies[1],
-                self_density_field: contact.density_fields[0],
-                other_density_field: contact.density_fields[1],
-            });
-        }
-        if let Some(event) = body_events[1] {
-            event.dispatch(&ContactEvent {
-                kind: ContactEventKind::Ended,
-                blocking,
-                self_body: contact.bodies[1],
-                other_body: contact.bodies[0],
-                self_density_field: contact.density_fields[1],
-                other_density_field: contact.density_fields[0],
-            });
-        }
+        dispatch(contact, ContactEventKind::Ended, blocking);
     }
 
     Ok(())
@@ -1011,145 +1377,370 @@ pub struct RepulsiveCollisionSolver<const LOCKING: bool>;
 
 impl<const LOCKING: bool> System for RepulsiveCollisionSolver<LOCKING> {
     fn run(&self, context: SystemContext) -> Result<(), Box<dyn Error>> {
-        let (world, simulation, contacts, body_lookup, particle_lookup, callbacks) = context
-            .fetch::<(
-                &World,
-                Res<LOCKING, &PhysicsSimulation>,
-                Res<LOCKING, &ContactsCache>,
-                // body lookup
-                Lookup<
-                    LOCKING,
-                    (
-                        Option<&Relation<BodyParticleRelation>>,
-                        Option<&Mass>,
-                        Option<&BodyMaterial>,
-                        Include<PhysicsBody>,
-                    ),
-                >,
-                // particle lookup
-                Lookup<
-                    LOCKING,
-                    (
-                        &mut Position,
-                        Option<&mut Rotation>,
-                        &mut LinearVelocity,
-                        Option<&mut AngularVelocity>,
-                        Include<PhysicsParticle>,
-                    ),
-                >,
-                Local<LOCKING, &RepulsiveCollisionCallbacks>,
-            )>()?;
+        let (
+            world,
+            simulation,
+            mut contacts,
+            body_lookup,
+            particle_lookup,
+            density_field_lookup,
+            callbacks,
+        ) = context.fetch::<(
+            &World,
+            Res<LOCKING, &PhysicsSimulation>,
+            Res<LOCKING, &mut ContactsCache>,
+            // body lookup
+            Lookup<
+                LOCKING,
+                (
+                    Option<&Relation<BodyParticleRelation>>,
+                    Option<&Mass>,
+                    Option<&BodyMaterial>,
+                    Option<&Kinematic>,
+                    Include<PhysicsBody>,
+                ),
+            >,
+            // particle lookup
+            Lookup<
+                LOCKING,
+                (
+                    &mut Position,
+                    Option<&mut Rotation>,
+                    &mut LinearVelocity,
+                    Option<&mut AngularVelocity>,
+                    Include<PhysicsParticle>,
+                ),
+            >,
+            // density field lookup
+            Lookup<LOCKING, &DensityFieldBox>,
+            Local<LOCKING, &RepulsiveCollisionCallbacks>,
+        )>()?;
 
         if contacts.is_empty() {
+            contacts.resolved_displacements.clear();
             return Ok(());
         }
 
         let inverse_delta_time = simulation.inverse_delta_time();
         let mut body_lookup_access = body_lookup.lookup_access(world);
+        let mut blocking_contacts = contacts.blocking_contacts().collect::<Vec<_>>();
+        if simulation.deterministic_accumulation {
+            blocking_contacts.sort_by_key(|contact| EntityPair::from_array(contact.density_fields));
+        }
+        // fields reporting material at contact point override body material.
+        // sampled before particle access, since fields might read particles.
+        let field_materials = {
+            let mut density_field_lookup_access = density_field_lookup.lookup_access(world);
+            let view = PhysicsAccessView::new(world);
+            blocking_contacts
+                .iter()
+                .map(|contact| {
+                    let point = contact
+                        .cells
+                        .iter()
+                        .map(|cell| cell.region.center())
+                        .sum::<Vec3<Scalar>>()
+                        / contact.cells.len().max(1) as Scalar;
+                    [0, 1].map(|index| {
+                        density_field_lookup_access
+                            .access(contact.density_fields[index])
+                            .and_then(|field| {
+                                field.material_at_point(
+                                    point,
+                                    &BodyAccessInfo::new(contact.bodies[index], view.clone()),
+                                )
+                            })
+                    })
+                })
+                .collect::<Vec<_>>()
+        };
         let mut particle_lookup_access = particle_lookup.lookup_access(world);
-
-        for contact in contacts.blocking_contacts() {
-            let body_access = contact
-                .bodies
-                .map(|entity| body_lookup_access.access(entity));
-            let Some((relations_a, mass_a, material_a, _)) = body_access[0] else {
-                continue;
-            };
-            let Some((relations_b, mass_b, material_b, _)) = body_access[1] else {
-                continue;
-            };
-            if (mass_a.is_none() && mass_b.is_none())
-                || (relations_a.is_none() && relations_b.is_none())
-            {
-                continue;
+        let mut sorted_cells = Vec::new();
+        let mut corrections = Vec::new();
+        let iterations = simulation.solver_iterations.max(1);
+        let warm_starting = simulation.warm_starting > Scalar::EPSILON;
+        let contacts_count = blocking_contacts.len();
+        // separation along contact normals that first pass asks for.
+        let mut targets = vec![0.0; contacts_count];
+        let mut normals = vec![[Vec3::<Scalar>::zero(); 2]; contacts_count];
+        // displacement each contact applied to its bodies during this step.
+        let mut applied = vec![[Vec3::<Scalar>::zero(); 2]; contacts_count];
+        // bodies displacement accumulated by all passes so far.
+        let mut displacements = HashMap::<Entity, Vec3<Scalar>>::new();
+
+        // when warm starting, first pass only measures what contacts ask for.
+        let passes = iterations + warm_starting as usize;
+        for pass in 0..passes {
+            let measuring = warm_starting && pass == 0;
+            let last_iteration = pass + 1 == passes;
+            // warm start pushes bodies apart by portion of displacement their
+            // persisting contacts resolved last step, up to what contact asks.
+            if warm_starting && pass == 1 {
+                for (contact_index, contact) in blocking_contacts.iter().enumerate() {
+                    let pair =
+                        EntityPair::new(contact.density_fields[0], contact.density_fields[1]);
+                    let Some(previous) = contacts.resolved_displacements.get(&pair) else {
+                        continue;
+                    };
+                    let warm = [0, 1].map(|body_index| {
+                        previous
+                            .iter()
+                            .find(|(entity, _)| *entity == contact.bodies[body_index])
+                            .map(|(_, displacement)| *displacement * simulation.warm_starting)
+                            .unwrap_or_default()
+                    });
+                    let separation = warm[0].dot(normals[contact_index][0])
+                        + warm[1].dot(normals[contact_index][1]);
+                    if targets[contact_index] <= Scalar::EPSILON || separation <= Scalar::EPSILON {
+                        continue;
+                    }
+                    let scale = (targets[contact_index] / separation).min(1.0);
+                    for (body_index, body) in contact.bodies.into_iter().enumerate() {
+                        let Some((relations, _, _, None, _)) = body_lookup_access.access(body)
+                        else {
+                            continue;
+                        };
+                        let displacement = warm[body_index] * scale;
+                        for entity in relations
+                            .into_iter()
+                            .flat_map(|relation| relation.entities())
+                        {
+                            if let Some((position, _, linear_velocity, _, _)) =
+                                particle_lookup_access.access(entity)
+                            {
+                                position.current += displacement;
+                                linear_velocity.value += displacement * inverse_delta_time;
+                            }
+                        }
+                        applied[contact_index][body_index] += displacement;
+                        *displacements.entry(body).or_default() += displacement;
+                    }
+                }
             }
-
-            let inverse_mass_a = mass_a.map(|mass| mass.inverse()).unwrap_or_default();
-            let inverse_mass_b = mass_b.map(|mass| mass.inverse()).unwrap_or_default();
-            let inverse_mass = [inverse_mass_a, inverse_mass_b];
-
-            let material_a = material_a.copied().unwrap_or_default();
-            let material_b = material_b.copied().unwrap_or_default();
-            let material = [material_a, material_b];
-
-            let weight_a = inverse_mass_a / (inverse_mass_a + inverse_mass_b);
-            let weight_b = 1.0 - weight_a;
-            let weight = [weight_a, weight_b];
-
-            for (entity, body_index) in relations_a
-                .into_iter()
-                .flat_map(|relation| relation.iter())
-                .map(|(_, entity)| (entity, 0))
-                .chain(
-                    relations_b
-                        .into_iter()
-                        .flat_map(|relation| relation.iter())
-                        .map(|(_, entity)| (entity, 1)),
-                )
+            for (contact_index, (contact, field_material)) in blocking_contacts
+                .iter()
+                .copied()
+                .zip(field_materials.iter())
+                .enumerate()
             {
-                let Some((position, rotation, linear_velocity, angular_velocity, _)) =
-                    particle_lookup_access.access(entity)
-                else {
+                let contact = if simulation.deterministic_accumulation {
+                    sorted_cells.clear();
+                    sorted_cells.extend_from_slice(contact.cells);
+                    sorted_cells.sort_by(ShapeOverlapCell::stable_cmp);
+                    DensityFieldContact {
+                        cells: &sorted_cells,
+                        ..contact
+                    }
+                } else {
+                    contact
+                };
+                let body_access = contact
+                    .bodies
+                    .map(|entity| body_lookup_access.access(entity));
+                let Some((relations_a, mass_a, material_a, kinematic_a, _)) = body_access[0] else {
+                    continue;
+                };
+                let Some((relations_b, mass_b, material_b, kinematic_b, _)) = body_access[1] else {
                     continue;
                 };
+                if (mass_a.is_none() && mass_b.is_none())
+                    || (relations_a.is_none() && relations_b.is_none())
+                {
+                    continue;
+                }
 
-                let mut linear_correction = Vec3::<Scalar>::zero();
-                let mut angular_correction = Vec3::<Scalar>::zero();
-                let contact_normal = contact
-                    .cells
-                    .iter()
-                    .map(|cell| cell.normal[body_index])
-                    .sum::<Vec3<Scalar>>()
-                    .try_normalized()
+                let kinematic = [kinematic_a.is_some(), kinematic_b.is_some()];
+                let inverse_mass_a = mass_a
+                    .filter(|_| !kinematic[0])
+                    .map(|mass| mass.inverse())
+                    .unwrap_or_default();
+                let inverse_mass_b = mass_b
+                    .filter(|_| !kinematic[1])
+                    .map(|mass| mass.inverse())
                     .unwrap_or_default();
+                if inverse_mass_a + inverse_mass_b <= Scalar::EPSILON {
+                    continue;
+                }
+                let inverse_mass = [inverse_mass_a, inverse_mass_b];
+
+                let material = [material_a, material_b];
+                let material = [0, 1].map(|index| {
+                    field_material[index]
+                        .or_else(|| material[index].copied())
+                        .unwrap_or_default()
+                });
+
+                let weight_a = inverse_mass_a / (inverse_mass_a + inverse_mass_b);
+                let weight_b = 1.0 - weight_a;
+                let weight = [weight_a, weight_b];
+
+                let contact_normals = [0, 1].map(|body_index| {
+                    contact
+                        .cells
+                        .iter()
+                        .map(|cell| cell.normal[body_index])
+                        .sum::<Vec3<Scalar>>()
+                        .try_normalized()
+                        .unwrap_or_default()
+                });
+
+                corrections.clear();
+                for (entity, body_index) in relations_a
+                    .into_iter()
+                    .flat_map(|relation| relation.iter())
+                    .map(|(_, entity)| (entity, 0))
+                    .chain(
+                        relations_b
+                            .into_iter()
+                            .flat_map(|relation| relation.iter())
+                            .map(|(_, entity)| (entity, 1)),
+                    )
+                {
+                    if kinematic[body_index] {
+                        continue;
+                    }
+                    let Some((position, rotation, _, _, _)) = particle_lookup_access.access(entity)
+                    else {
+                        continue;
+                    };
+
+                    let mut linear_correction = Vec3::<Scalar>::zero();
+                    let mut angular_correction = Vec3::<Scalar>::zero();
+                    callbacks.run_corrections(RepulsiveCollisionCorrection {
+                        linear_correction: &mut linear_correction,
+                        angular_correction: &mut angular_correction,
+                        contact_normal: contact_normals[body_index],
+                        position,
+                        rotation: rotation.as_deref(),
+                        contact,
+                        body_index,
+                        weight,
+                        inverse_mass,
+                        callbacks: &callbacks,
+                    });
+                    corrections.push((entity, body_index, linear_correction, angular_correction));
+                }
 
-                callbacks.run_corrections(RepulsiveCollisionCorrection {
-                    linear_correction: &mut linear_correction,
-                    angular_correction: &mut angular_correction,
-                    contact_normal,
-                    position,
-                    rotation: rotation.as_deref(),
-                    contact,
-                    body_index,
-                    weight,
-                    inverse_mass,
-                    callbacks: &callbacks,
+                let mean_corrections = [0, 1].map(|body_index| {
+                    let (sum, count) = corrections
+                        .iter()
+                        .filter(|(_, index, _, _)| *index == body_index)
+                        .fold((Vec3::<Scalar>::zero(), 0), |(sum, count), item| {
+                            (sum + item.2, count + 1)
+                        });
+                    if count > 0 {
+                        sum / count as Scalar
+                    } else {
+                        Vec3::zero()
+                    }
                 });
+                if pass == 0 {
+                    targets[contact_index] = mean_corrections[0].dot(contact_normals[0])
+                        + mean_corrections[1].dot(contact_normals[1]);
+                    normals[contact_index] = contact_normals;
+                }
+                if measuring {
+                    continue;
+                }
+                // linearized penetration left after bodies got displaced.
+                let factor = if pass == 0 {
+                    1.0
+                } else if targets[contact_index].abs() > Scalar::EPSILON {
+                    let separation = [0, 1]
+                        .map(|index| {
+                            displacements
+                                .get(&contact.bodies[index])
+                                .copied()
+                                .unwrap_or_default()
+                                .dot(contact_normals[index])
+                        })
+                        .into_iter()
+                        .sum::<Scalar>();
+                    (1.0 - separation / targets[contact_index]).max(0.0)
+                } else {
+                    0.0
+                };
 
-                position.current += linear_correction;
-                linear_velocity.value += linear_correction * inverse_delta_time;
+                for (entity, body_index, linear_correction, angular_correction) in
+                    corrections.iter().copied()
+                {
+                    let Some((position, rotation, linear_velocity, angular_velocity, _)) =
+                        particle_lookup_access.access(entity)
+                    else {
+                        continue;
+                    };
+                    let linear_correction = linear_correction * factor;
+                    let angular_correction = angular_correction * factor;
+                    let contact_normal = contact_normals[body_index];
 
-                if let Some(rotation) = rotation {
-                    let angle = angular_correction.magnitude();
-                    if angle > Scalar::EPSILON {
-                        let axis = angular_correction / angle;
-                        let delta = quat_from_axis_angle(axis, angle);
-                        rotation.current = (rotation.current * delta).normalized();
+                    position.current += linear_correction;
+                    linear_velocity.value += linear_correction * inverse_delta_time;
 
-                        if let Some(angular_velocity) = angular_velocity {
+                    if let Some(rotation) = rotation {
+                        let angle = angular_correction.magnitude();
+                        if angle > Scalar::EPSILON {
                             let axis = angular_correction / angle;
-                            angular_velocity.value += axis * (angle * inverse_delta_time);
+                            let delta = quat_from_axis_angle(axis, angle);
+                            rotation.current = (rotation.current * delta).normalized();
+
+                            if let Some(angular_velocity) = angular_velocity {
+                                let axis = angular_correction / angle;
+                                angular_velocity.value += axis * (angle * inverse_delta_time);
+                            }
                         }
                     }
+
+                    // velocity response happens once, after positions got resolved.
+                    if !last_iteration {
+                        continue;
+                    }
+
+                    let relative_velocity = linear_velocity.value
+                        - contact.movement_since_last_step * inverse_delta_time;
+                    let normal_velocity = relative_velocity.dot(contact_normal);
+                    let tangent_velocity = relative_velocity - contact_normal * normal_velocity;
+
+                    let restitution = material[body_index].restitution;
+                    let impulse = -normal_velocity * (1.0 - restitution);
+                    linear_velocity.value += contact_normal * impulse;
+                    // TODO: angular velocity.
+
+                    let friction = material[body_index].friction;
+                    let friction_direction = -tangent_velocity.try_normalized().unwrap_or_default();
+                    let friction_magnitude = friction * normal_velocity.abs();
+                    linear_velocity.value += friction_direction * friction_magnitude;
+                    // TODO: angular velocity.
                 }
 
-                let relative_velocity =
-                    linear_velocity.value - contact.movement_since_last_step * inverse_delta_time;
-                let normal_velocity = relative_velocity.dot(contact_normal);
-                let tangent_velocity = relative_velocity - contact_normal * normal_velocity;
-
-                let restitution = material[body_index].restitution;
-                let impulse = -normal_velocity * (1.0 - restitution);
-                linear_velocity.value += contact_normal * impulse;
-                // TODO: angular velocity.
-
-                let friction = material[body_index].friction;
-                let friction_direction = -tangent_velocity.try_normalized().unwrap_or_default();
-                let friction_magnitude = friction * normal_velocity.abs();
-                linear_velocity.value += friction_direction * friction_magnitude;
-                // TODO: angular velocity.
+                for (body_index, body) in contact.bodies.into_iter().enumerate() {
+                    let displacement = mean_corrections[body_index] * factor;
+                    applied[contact_index][body_index] += displacement;
+                    if iterations > 1 || warm_starting {
+                        *displacements.entry(body).or_default() += displacement;
+                    }
+                }
             }
         }
+
+        drop(particle_lookup_access);
+        let resolved_displacements = if warm_starting {
+            blocking_contacts
+                .iter()
+                .zip(applied)
+                .map(|(contact, applied)| {
+                    (
+                        EntityPair::new(contact.density_fields[0], contact.density_fields[1]),
+                        [
+                            (contact.bodies[0], applied[0]),
+                            (contact.bodies[1], applied[1]),
+                        ],
+                    )
+                })
+                .collect()
+        } else {
+            Default::default()
+        };
+        contacts.resolved_displacements = resolved_displacements;
         Ok(())
     }
 }
@@ -1211,11 +1802,39 @@ mod tests {
     use crate::{
         PhysicsPlugin,
         components::{BodyDensityFieldRelation, ExternalForces, LinearVelocity, PhysicsBody},
-        density_fields::{aabb::AabbDensityField, sphere::SphereDensityField},
+        density_fields::{
+            DensityRange, aabb::AabbDensityField, material::MaterialDensityField,
+            sphere::SphereDensityField,
+        },
+    };
+    use anput::{
+        bundle::Bundle, scheduler::GraphScheduler, third_party::moirai::jobs::Jobs,
+        universe::Universe,
     };
-    use anput::{scheduler::GraphScheduler, third_party::moirai::jobs::Jobs, universe::Universe};
     use vek::Vec3;
 
+    // spawns body being its own density field, parent and particle if it is one.
+    fn spawn_body(world: &mut World, bundle: impl Bundle) -> Result<Entity, Box<dyn Error>> {
+        let entity = world.spawn(bundle)?;
+        if world.has_entity_component::<PhysicsParticle>(entity) {
+            world.relate::<true, _>(BodyParticleRelation, entity, entity)?;
+        }
+        world.relate::<true, _>(BodyDensityFieldRelation, entity, entity)?;
+        world.relate::<true, _>(BodyParentRelation, entity, entity)?;
+        Ok(entity)
+    }
+
+    #[test]
+    fn test_collision_mask_iter_flags() {
+        let mask = CollisionMask::flag(0).with(5).with(64);
+        assert_eq!(mask.iter_flags().collect::<Vec<_>>(), vec![0, 5, 64]);
+        assert_eq!(CollisionMask::default().iter_flags().count(), 0);
+        assert_eq!(
+            CollisionMask::flag(127).iter_flags().collect::<Vec<_>>(),
+            vec![127]
+        );
+    }
+
     #[test]
     fn test_entity_pair() {
         let a = Entity::new(0, 0).unwrap();
@@ -1276,55 +1895,41 @@ mod tests {
         let jobs = Jobs::default();
         let scheduler = GraphScheduler::<true>;
 
-        let a = universe.simulation.spawn((
-            PhysicsBody,
-            DensityFieldBox::new(AabbDensityField {
-                aabb: Aabb {
-                    min: Vec3::new(-100.0, -100.0, 0.0),
-                    max: Vec3::new(100.0, 0.0, 0.0),
-                },
-                density: 1.0,
-            }),
-            CollisionProfile::default().with_block(CollisionMask::flag(0)),
-            ContactDetection::default(),
-        ))?;
-        universe
-            .simulation
-            .relate::<true, _>(BodyDensityFieldRelation, a, a)
-            .unwrap();
-        universe
-            .simulation
-            .relate::<true, _>(BodyParentRelation, a, a)
-            .unwrap();
+        spawn_body(
+            &mut universe.simulation,
+            (
+                PhysicsBody,
+                DensityFieldBox::new(AabbDensityField {
+                    aabb: Aabb {
+                        min: Vec3::new(-100.0, -100.0, 0.0),
+                        max: Vec3::new(100.0, 0.0, 0.0),
+                    },
+                    density: 1.0,
+                }),
+                CollisionProfile::default().with_block(CollisionMask::flag(0)),
+                ContactDetection::default(),
+            ),
+        )?;
 
-        let b = universe.simulation.spawn((
-            PhysicsBody,
-            PhysicsParticle,
-            DensityFieldBox::new(SphereDensityField::<true>::new_hard(1.0, 10.0)),
-            CollisionProfile::default().with_block(CollisionMask::flag(0)),
-            ContactDetection {
-                depth_limit: 0,
-                ..Default::default()
-            },
-            Mass::new(1.0),
-            Position::new(Vec3::new(0.0, 10.0, 0.0)),
-            LinearVelocity {
-                value: Vec3::new(-5.0, -5.0, 0.0),
-            },
-            ExternalForces::default(),
-        ))?;
-        universe
-            .simulation
-            .relate::<true, _>(BodyParticleRelation, b, b)
-            .unwrap();
-        universe
-            .simulation
-            .relate::<true, _>(BodyDensityFieldRelation, b, b)
-            .unwrap();
-        universe
-            .simulation
-            .relate::<true, _>(BodyParentRelation, b, b)
-            .unwrap();
+        let b = spawn_body(
+            &mut universe.simulation,
+            (
+                PhysicsBody,
+                PhysicsParticle,
+                DensityFieldBox::new(SphereDensityField::<true>::new_hard(1.0, 10.0)),
+                CollisionProfile::default().with_block(CollisionMask::flag(0)),
+                ContactDetection {
+                    depth_limit: 0,
+                    ..Default::default()
+                },
+                Mass::new(1.0),
+                Position::new(Vec3::new(0.0, 10.0, 0.0)),
+                LinearVelocity {
+                    value: Vec3::new(-5.0, -5.0, 0.0),
+                },
+                ExternalForces::default(),
+            ),
+        )?;
 
         scheduler.run(&jobs, &mut universe)?;
 
@@ -1348,4 +1953,871 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_density_field_collision_profiles() -> Result<(), Box<dyn Error>> {
+        let mut universe = Universe::default().with_plugin(
+            PhysicsPlugin::<true>::barebones()
+                .install_collect_contacts(true)
+                .make(),
+        );
+        let jobs = Jobs::default();
+        let scheduler = GraphScheduler::<true>;
+
+        let parent = universe.simulation.spawn((
+            PhysicsBody,
+            PhysicsParticle,
+            Position::new(Vec3::zero()),
+        ))?;
+        universe
+            .simulation
+            .relate::<true, _>(BodyParticleRelation, parent, parent)
+            .unwrap();
+        let mut fields = Vec::new();
+        for profile in [
+            CollisionProfile::default().with_block(CollisionMask::flag(0)),
+            CollisionProfile::default().with_overlap(CollisionMask::flag(0)),
+        ] {
+            let field = universe.simulation.spawn((
+                DensityFieldBox::new(SphereDensityField::<true>::new_hard(1.0, 10.0)),
+                profile,
+                ContactDetection {
+                    depth_limit: 0,
+                    ..Default::default()
+                },
+            ))?;
+            universe
+                .simulation
+                .relate::<true, _>(BodyDensityFieldRelation, parent, field)
+                .unwrap();
+            universe
+                .simulation
+                .relate::<true, _>(BodyParentRelation, field, parent)
+                .unwrap();
+            fields.push(field);
+        }
+
+        let probe = spawn_body(
+            &mut universe.simulation,
+            (
+                PhysicsBody,
+                PhysicsParticle,
+                DensityFieldBox::new(SphereDensityField::<true>::new_hard(1.0, 10.0)),
+                CollisionProfile::default().with_block(CollisionMask::flag(0)),
+                ContactDetection {
+                    depth_limit: 0,
+                    ..Default::default()
+                },
+                Position::new(Vec3::new(5.0, 0.0, 0.0)),
+            ),
+        )?;
+
+        scheduler.run(&jobs, &mut universe)?;
+
+        let contacts = universe.resources.get::<true, ContactsCache>()?;
+        assert!(contacts.does_block(fields[0], probe));
+        assert!(!contacts.does_overlap(fields[0], probe));
+        assert!(contacts.does_overlap(fields[1], probe));
+        assert!(!contacts.does_block(fields[1], probe));
+        for contact in contacts.any_contacts_of(probe) {
+            assert!(contact.bodies.contains(&parent));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_global_contact_events() -> Result<(), Box<dyn Error>> {
+        let mut universe = Universe::default().with_plugin(
+            PhysicsPlugin::<true>::default()
+                .install_contact_events_queue(true)
+                .make(),
+        );
+        let jobs = Jobs::default();
+        let scheduler = GraphScheduler::<true>;
+
+        let mut sinks = Vec::new();
+        for x in [0.0, 5.0] {
+            let mut events = EventDispatcher::<ContactEvent>::default();
+            let (_, sink) = events.bind_sink_make();
+            sinks.push(sink);
+
+            spawn_body(
+                &mut universe.simulation,
+                (
+                    PhysicsBody,
+                    PhysicsParticle,
+                    DensityFieldBox::new(SphereDensityField::<true>::new_hard(1.0, 10.0)),
+                    CollisionProfile::default().with_overlap(CollisionMask::flag(0)),
+                    ContactDetection {
+                        depth_limit: 0,
+                        ..Default::default()
+                    },
+                    Position::new(Vec3::new(x, 0.0, 0.0)),
+                    events,
+                ),
+            )?;
+        }
+
+        // two substeps per frame, consumed once after both.
+        for _ in 0..2 {
+            scheduler.run(&jobs, &mut universe)?;
+        }
+
+        let body_events = sinks
+            .iter()
+            .flat_map(|sink| sink.iter())
+            .collect::<Vec<_>>();
+        let global_events = universe
+            .resources
+            .get_mut::<true, ContactEvents>()?
+            .drain()
+            .collect::<Vec<_>>();
+        assert_eq!(body_events.len(), 4);
+        assert_eq!(global_events.len(), body_events.len());
+        for event in &global_events {
+            assert!(body_events.contains(event));
+        }
+        assert_eq!(
+            global_events
+                .iter()
+                .filter(|event| event.kind == ContactEventKind::Began)
+                .count(),
+            2
+        );
+        assert!(universe.resources.get::<true, ContactEvents>()?.is_empty());
+
+        scheduler.run(&jobs, &mut universe)?;
+
+        let global_events = universe.resources.get::<true, ContactEvents>()?;
+        assert_eq!(global_events.len(), 2);
+        for event in global_events.iter() {
+            assert_eq!(event.kind, ContactEventKind::Continue);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_density_field_aabb_cache() -> Result<(), Box<dyn Error>> {
+        use std::sync::{
+            Arc,
+            atomic::{AtomicUsize, Ordering},
+        };
+
+        struct CountingField {
+            calls: Arc<AtomicUsize>,
+            inner: SphereDensityField<true>,
+        }
+
+        impl DensityField for CountingField {
+            fn aabb(&self, info: &BodyAccessInfo) -> Aabb<Scalar> {
+                self.calls.fetch_add(1, Ordering::SeqCst);
+                self.inner.aabb(info)
+            }
+
+            fn density_at_point(&self, point: Vec3<Scalar>, info: &BodyAccessInfo) -> Scalar {
+                self.inner.density_at_point(point, info)
+            }
+        }
+
+        let mut world = World::default();
+        let calls = Arc::new(AtomicUsize::new(0));
+        let entity = spawn_body(
+            &mut world,
+            (
+                PhysicsBody,
+                PhysicsParticle,
+                Position::new(Vec3::new(0.0, 0.0, 0.0)),
+                DensityFieldBox::new(CountingField {
+                    calls: calls.clone(),
+                    inner: SphereDensityField::<true>::new_hard(1.0, 10.0),
+                }),
+                DensityFieldAabbCache::default(),
+            ),
+        )?;
+        world.clear_changes();
+
+        let mut spatial = SpatialPartitioning::<DensityFieldSpatialExtractor>::default();
+        spatial.rebuild::<true>(&world);
+        spatial.rebuild::<true>(&world);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert_eq!(
+            world
+                .component::<true, DensityFieldAabbCache>(entity)?
+                .cached(entity),
+            Some(spatial.iter().next().unwrap().geom().aabb)
+        );
+
+        world.component_mut::<true, Position>(entity)?.current = Vec3::new(5.0, 0.0, 0.0);
+        spatial.rebuild::<true>(&world);
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+        assert_eq!(spatial.iter().next().unwrap().geom().aabb.min.x, -5.0);
+
+        world.update::<DensityFieldBox>(entity);
+        spatial.rebuild::<true>(&world);
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+        world.clear_changes();
+
+        world
+            .component_mut::<true, DensityFieldAabbCache>(entity)?
+            .invalidate();
+        spatial.rebuild::<true>(&world);
+        spatial.rebuild::<true>(&world);
+        assert_eq!(calls.load(Ordering::SeqCst), 4);
+
+        let mut world = World::default();
+        let calls = Arc::new(AtomicUsize::new(0));
+        let body = world.spawn((PhysicsBody, Position::new(Vec3::new(0.0, 0.0, 0.0))))?;
+        let particle = world.spawn((PhysicsParticle, Position::new(Vec3::new(0.0, 0.0, 0.0))))?;
+        world.relate::<true, _>(BodyParticleRelation, body, particle)?;
+        let field = world.spawn((
+            DensityFieldBox::new(CountingField {
+                calls: calls.clone(),
+                inner: SphereDensityField::<true>::new_hard(1.0, 10.0),
+            }),
+            DensityFieldAabbCache::default(),
+        ))?;
+        world.relate::<true, _>(BodyDensityFieldRelation, body, field)?;
+        world.relate::<true, _>(BodyParentRelation, field, body)?;
+        world.clear_changes();
+
+        let mut spatial = SpatialPartitioning::<DensityFieldSpatialExtractor>::default();
+        spatial.rebuild::<true>(&world);
+        spatial.rebuild::<true>(&world);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        world.component_mut::<true, Position>(particle)?.current = Vec3::new(5.0, 0.0, 0.0);
+        spatial.rebuild::<true>(&world);
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+        assert_eq!(spatial.iter().next().unwrap().geom().aabb.min.x, -5.0);
+
+        world.update::<Position>(particle);
+        spatial.rebuild::<true>(&world);
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+        world.clear_changes();
+        spatial.rebuild::<true>(&world);
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+
+        let other = world.spawn((PhysicsParticle, Position::new(Vec3::new(20.0, 0.0, 0.0))))?;
+        world.relate::<true, _>(BodyParticleRelation, body, other)?;
+        world.clear_changes();
+        spatial.rebuild::<true>(&world);
+        assert_eq!(calls.load(Ordering::SeqCst), 4);
+        assert_eq!(spatial.iter().next().unwrap().geom().aabb.max.x, 30.0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_oriented_contact_between() -> Result<(), Box<dyn Error>> {
+        let mut universe = Universe::default().with_plugin(
+            PhysicsPlugin::<true>::barebones()
+                .install_collect_contacts(true)
+                .make(),
+        );
+        let jobs = Jobs::default();
+        let scheduler = GraphScheduler::<true>;
+
+        let mut entities = Vec::new();
+        for x in [0.0, 5.0] {
+            let body = universe.simulation.spawn((
+                PhysicsBody,
+                PhysicsParticle,
+                Position::new(Vec3::new(x, 0.0, 0.0)),
+            ))?;
+            universe
+                .simulation
+                .relate::<true, _>(BodyParticleRelation, body, body)?;
+            let field = universe.simulation.spawn((
+                DensityFieldBox::new(SphereDensityField::<true>::new_hard(1.0, 10.0)),
+                CollisionProfile::default().with_overlap(CollisionMask::flag(0)),
+                ContactDetection {
+                    depth_limit: 0,
+                    ..Default::default()
+                },
+            ))?;
+            universe
+                .simulation
+                .relate::<true, _>(BodyDensityFieldRelation, body, field)?;
+            universe
+                .simulation
+                .relate::<true, _>(BodyParentRelation, field, body)?;
+            entities.push((body, field));
+        }
+        let [(body_a, field_a), (body_b, field_b)] = [entities[0], entities[1]];
+
+        scheduler.run(&jobs, &mut universe)?;
+
+        let contacts = universe.resources.get::<true, ContactsCache>()?;
+        let ab = contacts.oriented_contact_between(field_a, field_b).unwrap();
+        let ba = contacts.oriented_contact_between(field_b, field_a).unwrap();
+        assert_eq!(ab.self_body(), body_a);
+        assert_eq!(ab.other_body(), body_b);
+        assert_eq!(ab.self_density_field(), field_a);
+        assert_eq!(ab.other_density_field(), field_b);
+        assert_eq!(ba.self_body(), body_b);
+        assert_eq!(ba.other_body(), body_a);
+        assert_eq!(ba.self_density_field(), field_b);
+        assert_eq!(ba.other_density_field(), field_a);
+        assert_eq!(ab.self_index(), ba.other_index());
+        assert!(ab.contact.oriented(body_a).is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_deterministic_accumulation() {
+        let cells = (0..64)
+            .map(|index| {
+                let scale = (10.0 as Scalar).powi(index % 7 - 3);
+                let min = Vec3::new(index as Scalar, (index * 7 % 13) as Scalar, 0.0);
+                ShapeOverlapCell {
+                    region: Aabb {
+                        min,
+                        max: min + Vec3::new(scale, 1.0, 1.0 / scale),
+                    },
+                    density: [DensityRange::converged(1.0); 2],
+                    normal: [
+                        Vec3::new(scale, 1.0, -scale),
+                        Vec3::new(-scale, -1.0, scale),
+                    ],
+                }
+            })
+            .collect::<Vec<_>>();
+        let position = Position::new(Vec3::zero());
+        let callbacks = RepulsiveCollisionCallbacks::default();
+        let correct = |cells: &[ShapeOverlapCell]| {
+            let mut sorted = cells.to_vec();
+            sorted.sort_by(ShapeOverlapCell::stable_cmp);
+            let mut linear_correction = Vec3::zero();
+            let mut angular_correction = Vec3::zero();
+            callbacks.run_corrections(RepulsiveCollisionCorrection {
+                linear_correction: &mut linear_correction,
+                angular_correction: &mut angular_correction,
+                contact_normal: Vec3::unit_x(),
+                position: &position,
+                rotation: None,
+                contact: DensityFieldContact {
+                    cells: &sorted,
+                    bodies: [Entity::INVALID; 2],
+                    density_fields: [Entity::INVALID; 2],
+                    overlap_region: Aabb::default(),
+                    movement_since_last_step: Vec3::zero(),
+                },
+                body_index: 0,
+                weight: [0.5; 2],
+                inverse_mass: [1.0; 2],
+                callbacks: &callbacks,
+            });
+            linear_correction.map(|value| value.to_bits())
+        };
+
+        let mut shuffled = cells.clone();
+        shuffled.reverse();
+        shuffled.rotate_left(17);
+        assert_eq!(correct(&cells), correct(&shuffled));
+    }
+
+    #[test]
+    fn test_contact_detection_hysteresis() -> Result<(), Box<dyn Error>> {
+        let mut began_counts = Vec::new();
+        for hysteresis in [None, Some(ContactHysteresis::new(0.6, 0.4))] {
+            let mut universe =
+                Universe::default().with_plugin(PhysicsPlugin::<true>::default().make());
+            let jobs = Jobs::default();
+            let scheduler = GraphScheduler::<true>;
+
+            let mut entities = Vec::new();
+            for x in [0.0, 2.5] {
+                let entity = spawn_body(
+                    &mut universe.simulation,
+                    (
+                        PhysicsBody,
+                        PhysicsParticle,
+                        DensityFieldBox::new(SphereDensityField::<true>::new_soft_edge(
+                            1.0, 1.0, 1.0,
+                        )),
+                        CollisionProfile::default().with_overlap(CollisionMask::flag(0)),
+                        ContactDetection {
+                            depth_limit: 0,
+                            hysteresis,
+                            ..Default::default()
+                        },
+                        Position::new(Vec3::new(x, 0.0, 0.0)),
+                    ),
+                )?;
+                entities.push(entity);
+            }
+
+            let mut began = 0;
+            for x in [2.5, 2.9, 3.1, 2.9, 3.1, 2.9, 3.1, 3.5] {
+                universe
+                    .simulation
+                    .component_mut::<true, Position>(entities[1])?
+                    .current = Vec3::new(x, 0.0, 0.0);
+                scheduler.run(&jobs, &mut universe)?;
+                began += universe
+                    .resources
+                    .get::<true, ContactsCache>()?
+                    .contacts_began()
+                    .count();
+            }
+            assert!(
+                !universe
+                    .resources
+                    .get::<true, ContactsCache>()?
+                    .has_contact_between(entities[0], entities[1])
+            );
+            began_counts.push(began);
+        }
+
+        assert_eq!(began_counts, vec![3, 1]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_contact_detection_adaptive_depth() -> Result<(), Box<dyn Error>> {
+        let adaptive = AdaptiveContactDepth::new(2.0, 6);
+        assert_eq!(adaptive.depth_limit(1, 0.0), 1);
+        assert_eq!(adaptive.depth_limit(1, 4.5), 3);
+        assert_eq!(adaptive.depth_limit(1, 100.0), 6);
+        assert_eq!(adaptive.depth_limit(8, 100.0), 8);
+
+        let mut universe = Universe::default().with_plugin(
+            PhysicsPlugin::<true>::barebones()
+                .install_collect_contacts(true)
+                .make(),
+        );
+        let jobs = Jobs::default();
+        let scheduler = GraphScheduler::<true>;
+
+        let mut pairs = Vec::new();
+        for (offset, speed) in [(0.0, 0.0), (100.0, 5.0)] {
+            let mut pair = Vec::new();
+            for (x, direction) in [(offset, 1.0), (offset + 1.5, -1.0)] {
+                let entity = spawn_body(
+                    &mut universe.simulation,
+                    (
+                        PhysicsBody,
+                        PhysicsParticle,
+                        DensityFieldBox::new(SphereDensityField::<true>::new_hard(1.0, 1.0)),
+                        CollisionProfile::default().with_block(CollisionMask::flag(0)),
+                        ContactDetection {
+                            depth_limit: 1,
+                            adaptive_depth: Some(adaptive),
+                            ..Default::default()
+                        },
+                        Position::new(Vec3::new(x, 0.0, 0.0)),
+                        LinearVelocity {
+                            value: Vec3::new(speed * direction, 0.0, 0.0),
+                        },
+                    ),
+                )?;
+                pair.push(entity);
+            }
+            pairs.push(pair);
+        }
+
+        scheduler.run(&jobs, &mut universe)?;
+
+        let contacts = universe.resources.get::<true, ContactsCache>()?;
+        let [slow, fast] = [&pairs[0], &pairs[1]].map(|pair| {
+            contacts
+                .blocking_contact_between(pair[0], pair[1])
+                .unwrap()
+                .cells
+                .len()
+        });
+        assert!(fast > slow);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_density_field_contact_material() -> Result<(), Box<dyn Error>> {
+        let mut universe = Universe::default().with_plugin(
+            PhysicsPlugin::<true>::default()
+                .simulation(PhysicsSimulation {
+                    delta_time: 1.0,
+                    ..Default::default()
+                })
+                .make(),
+        );
+        let jobs = Jobs::default();
+        let scheduler = GraphScheduler::<true>;
+
+        spawn_body(
+            &mut universe.simulation,
+            (
+                PhysicsBody,
+                DensityFieldBox::new(AabbDensityField {
+                    aabb: Aabb {
+                        min: Vec3::new(-100.0, -100.0, 0.0),
+                        max: Vec3::new(100.0, 0.0, 0.0),
+                    },
+                    density: 1.0,
+                }),
+                CollisionProfile::default().with_block(CollisionMask::flag(0)),
+                ContactDetection {
+                    depth_limit: 0,
+                    ..Default::default()
+                },
+            ),
+        )?;
+
+        let mut balls = Vec::new();
+        for (x, friction) in [(-50.0, 0.0), (50.0, 1.0)] {
+            let ball = spawn_body(
+                &mut universe.simulation,
+                (
+                    PhysicsBody,
+                    PhysicsParticle,
+                    DensityFieldBox::new(MaterialDensityField::new(
+                        SphereDensityField::<true>::new_hard(1.0, 10.0),
+                        BodyMaterial {
+                            friction,
+                            restitution: 0.0,
+                        },
+                    )),
+                    CollisionProfile::default().with_block(CollisionMask::flag(0)),
+                    ContactDetection {
+                        depth_limit: 0,
+                        ..Default::default()
+                    },
+                    Mass::new(1.0),
+                    BodyMaterial::default(),
+                    Position::new(Vec3::new(x, 10.0, 0.0)),
+                    LinearVelocity {
+                        value: Vec3::new(5.0, -5.0, 0.0),
+                    },
+                    ExternalForces::default(),
+                ),
+            )?;
+            balls.push(ball);
+        }
+
+        for _ in 0..2 {
+            scheduler.run(&jobs, &mut universe)?;
+        }
+
+        let [slippery, sticky] = [balls[0], balls[1]].map(|ball| {
+            universe
+                .simulation
+                .component::<true, LinearVelocity>(ball)
+                .unwrap()
+                .value
+        });
+        assert!(slippery.x > sticky.x);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_contact_filter_one_way_platform() -> Result<(), Box<dyn Error>> {
+        fn run(y: Scalar, velocity: Scalar, one_way: bool) -> Result<Scalar, Box<dyn Error>> {
+            let mut plugin = PhysicsPlugin::<true>::default().simulation(PhysicsSimulation {
+                delta_time: 1.0,
+                ..Default::default()
+            });
+            if one_way {
+                plugin = plugin.contact_filter(ContactFilter::new(|world, _, contact| {
+                    !contact.bodies.iter().any(|body| {
+                        world
+                            .component::<true, LinearVelocity>(*body)
+                            .map(|velocity| velocity.value.y > 0.0)
+                            .unwrap_or_default()
+                    })
+                }));
+            }
+            let mut universe = Universe::default().with_plugin(plugin.make());
+            let jobs = Jobs::default();
+            let scheduler = GraphScheduler::<true>;
+
+            spawn_body(
+                &mut universe.simulation,
+                (
+                    PhysicsBody,
+                    DensityFieldBox::new(AabbDensityField {
+                        aabb: Aabb {
+                            min: Vec3::new(-100.0, -1.0, 0.0),
+                            max: Vec3::new(100.0, 0.0, 0.0),
+                        },
+                        density: 1.0,
+                    }),
+                    CollisionProfile::default().with_block(CollisionMask::flag(0)),
+                    ContactDetection {
+                        depth_limit: 0,
+                        ..Default::default()
+                    },
+                ),
+            )?;
+
+            let ball = spawn_body(
+                &mut universe.simulation,
+                (
+                    PhysicsBody,
+                    PhysicsParticle,
+                    DensityFieldBox::new(SphereDensityField::<true>::new_hard(1.0, 1.0)),
+                    CollisionProfile::default().with_block(CollisionMask::flag(0)),
+                    ContactDetection {
+                        depth_limit: 0,
+                        ..Default::default()
+                    },
+                    Mass::new(1.0),
+                    Position::new(Vec3::new(0.0, y, 0.0)),
+                    LinearVelocity {
+                        value: Vec3::new(0.0, velocity, 0.0),
+                    },
+                ),
+            )?;
+
+            for _ in 0..10 {
+                scheduler.run(&jobs, &mut universe)?;
+            }
+
+            Ok(universe
+                .simulation
+                .component::<true, Position>(ball)?
+                .current
+                .y)
+        }
+
+        // body moving upward passes through platform only when filtered.
+        assert!(run(-3.0, 0.5, false)? < -1.0);
+        assert!(run(-3.0, 0.5, true)? > 0.0);
+        // body moving downward gets blocked either way.
+        assert!(run(3.0, -0.5, false)? > 0.0);
+        assert!(run(3.0, -0.5, true)? > 0.0);
+
+        Ok(())
+    }
+
+    // penetration of two balls stacked on ground after each simulation step.
+    fn stack_penetration(
+        simulation: PhysicsSimulation,
+        heights: [Scalar; 2],
+        steps: usize,
+    ) -> Result<Vec<Scalar>, Box<dyn Error>> {
+        let mut universe = Universe::default().with_plugin(
+            PhysicsPlugin::<true>::default()
+                .simulation(PhysicsSimulation {
+                    delta_time: 1.0,
+                    deterministic_accumulation: true,
+                    ..simulation
+                })
+                .make(),
+        );
+        let jobs = Jobs::default();
+        let scheduler = GraphScheduler::<true>;
+
+        spawn_body(
+            &mut universe.simulation,
+            (
+                PhysicsBody,
+                DensityFieldBox::new(AabbDensityField {
+                    aabb: Aabb {
+                        min: Vec3::new(-100.0, -100.0, 0.0),
+                        max: Vec3::new(100.0, 0.0, 0.0),
+                    },
+                    density: 1.0,
+                }),
+                CollisionProfile::default().with_block(CollisionMask::flag(0)),
+                ContactDetection {
+                    depth_limit: 0,
+                    ..Default::default()
+                },
+            ),
+        )?;
+
+        let mut balls = Vec::new();
+        for y in heights {
+            let ball = spawn_body(
+                &mut universe.simulation,
+                (
+                    PhysicsBody,
+                    PhysicsParticle,
+                    DensityFieldBox::new(SphereDensityField::<true>::new_hard(1.0, 10.0)),
+                    CollisionProfile::default().with_block(CollisionMask::flag(0)),
+                    ContactDetection {
+                        depth_limit: 0,
+                        ..Default::default()
+                    },
+                    Mass::new(1.0),
+                    Position::new(Vec3::new(0.0, y, 0.0)),
+                    LinearVelocity::default(),
+                    ExternalForces::default(),
+                ),
+            )?;
+            balls.push(ball);
+        }
+
+        (0..steps)
+            .map(|_| {
+                scheduler.run(&jobs, &mut universe)?;
+                let bottom = universe.simulation.component::<true, Position>(balls[0])?;
+                let top = universe.simulation.component::<true, Position>(balls[1])?;
+                let [bottom, top] = [bottom.current.y, top.current.y];
+                Ok((10.0 - bottom).max(0.0) + (20.0 - (top - bottom)).max(0.0))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_solver_iterations_stacking() -> Result<(), Box<dyn Error>> {
+        let penetration = [1, 2, 4, 8]
+            .into_iter()
+            .map(|solver_iterations| {
+                stack_penetration(
+                    PhysicsSimulation {
+                        solver_iterations,
+                        ..Default::default()
+                    },
+                    [8.0, 24.0],
+                    2,
+                )
+                .map(|penetration| penetration[1])
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // single pass leaves bottom ball pushed into ground by top one, while
+        // every extra pass resolves more of what is left.
+        assert!(penetration[0] > 1.0);
+        assert!(penetration[1] < penetration[0]);
+        assert!(penetration[2] < penetration[1]);
+        assert!(penetration[3] <= penetration[2]);
+        assert!(penetration[3] < 0.01);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_warm_starting_stacking() -> Result<(), Box<dyn Error>> {
+        let penetration = [0.0, 1.0]
+            .into_iter()
+            .map(|warm_starting| {
+                stack_penetration(
+                    PhysicsSimulation {
+                        gravity: Vec3::new(0.0, -0.1, 0.0),
+                        warm_starting,
+                        ..Default::default()
+                    },
+                    [10.0, 30.0],
+                    60,
+                )
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let [cold, warm] = [&penetration[0], &penetration[1]];
+
+        // resting stack sinks less when solver is seeded from last step.
+        let cold = cold[30..].iter().sum::<Scalar>();
+        let warm = warm[30..].iter().sum::<Scalar>();
+        assert!(warm < cold);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_contact_detection_self_collision() -> Result<(), Box<dyn Error>> {
+        for self_collision in [false, true] {
+            let mut universe = Universe::default().with_plugin(
+                PhysicsPlugin::<true>::barebones()
+                    .install_collect_contacts(true)
+                    .make(),
+            );
+            let jobs = Jobs::default();
+            let scheduler = GraphScheduler::<true>;
+
+            let body = universe.simulation.spawn((
+                PhysicsBody,
+                PhysicsParticle,
+                Position::new(Vec3::zero()),
+            ))?;
+            universe
+                .simulation
+                .relate::<true, _>(BodyParticleRelation, body, body)?;
+            let mut fields = Vec::new();
+            for x in [-0.5, 0.5] {
+                let field = universe.simulation.spawn((
+                    DensityFieldBox::new(AabbDensityField {
+                        aabb: Aabb {
+                            min: Vec3::new(x - 1.0, -1.0, -1.0),
+                            max: Vec3::new(x + 1.0, 1.0, 1.0),
+                        },
+                        density: 1.0,
+                    }),
+                    CollisionProfile::default().with_block(CollisionMask::flag(0)),
+                    ContactDetection {
+                        depth_limit: 0,
+                        self_collision,
+                        ..Default::default()
+                    },
+                ))?;
+                universe
+                    .simulation
+                    .relate::<true, _>(BodyDensityFieldRelation, body, field)?;
+                universe
+                    .simulation
+                    .relate::<true, _>(BodyParentRelation, field, body)?;
+                fields.push(field);
+            }
+
+            scheduler.run(&jobs, &mut universe)?;
+
+            let contacts = universe.resources.get::<true, ContactsCache>()?;
+            assert_eq!(contacts.does_block(fields[0], fields[1]), self_collision);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_contacts_cache_generation() -> Result<(), Box<dyn Error>> {
+        let mut universe = Universe::default().with_plugin(
+            PhysicsPlugin::<true>::barebones()
+                .install_collect_contacts(true)
+                .make(),
+        );
+        let jobs = Jobs::default();
+        let scheduler = GraphScheduler::<true>;
+
+        let mut entities = Vec::new();
+        for x in [0.0, 5.0] {
+            let entity = spawn_body(
+                &mut universe.simulation,
+                (
+                    PhysicsBody,
+                    PhysicsParticle,
+                    DensityFieldBox::new(SphereDensityField::<true>::new_hard(1.0, 1.0)),
+                    CollisionProfile::default().with_overlap(CollisionMask::flag(0)),
+                    ContactDetection {
+                        depth_limit: 0,
+                        ..Default::default()
+                    },
+                    Position::new(Vec3::new(x, 0.0, 0.0)),
+                ),
+            )?;
+            entities.push(entity);
+        }
+
+        let mut generations = Vec::new();
+        for x in [5.0, 5.0, 1.5, 1.5, 1.5, 5.0, 5.0] {
+            universe
+                .simulation
+                .component_mut::<true, Position>(entities[1])?
+                .current = Vec3::new(x, 0.0, 0.0);
+            scheduler.run(&jobs, &mut universe)?;
+            generations.push(
+                universe
+                    .resources
+                    .get::<true, ContactsCache>()?
+                    .generation(),
+            );
+        }
+
+        assert_eq!(generations, vec![0, 0, 1, 1, 1, 2, 2]);
+
+        Ok(())
+    }
 }
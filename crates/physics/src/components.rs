@@ -45,10 +45,10 @@ impl BodyAccessInfo {
                 self.view
                     .lookup::<LOCKING, (&Position, Option<&Rotation>, Fetch)>(relations.entities())
                     .map(|(position, rotation, value)| {
-                        let matrix = rotation
-                            .map(|rotation| Mat4::from(rotation.current))
-                            .unwrap_or_default()
-                            * Mat4::translation_3d(position.current);
+                        let matrix = Mat4::<Scalar>::translation_3d(position.current)
+                            * rotation
+                                .map(|rotation| Mat4::from(rotation.current))
+                                .unwrap_or_default();
                         (matrix, value)
                     })
             })
@@ -67,13 +67,32 @@ impl BodyAccessInfo {
     }
 }
 
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub struct PhysicsBody;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub struct PhysicsParticle;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub struct BodyParticleRelation;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub struct BodyDensityFieldRelation;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ParticleConstraintRelation;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub struct BodyParentRelation;
 
+/// Marks body or particle as kinematic: it is moved only by its own velocity,
+/// unaffected by gravity, forces, dampening and contact responses, while it
+/// still pushes dynamic bodies away as if it had infinite mass.
+///
+/// When body and its particles are separate entities, mark all of them.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Kinematic;
+
 #[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
 pub struct Mass {
     value: Scalar,
@@ -172,6 +191,53 @@ impl Rotation {
     }
 }
 
+/// Transform to render between fixed simulation steps.
+///
+/// Every step pushes current `Position` and `Rotation` as new target, while
+/// previous target becomes interpolation start. `position` and `rotation` are
+/// the interpolated output - call [`crate::solvers::interpolate_transforms`]
+/// every rendered frame with alpha being fraction of fixed step time elapsed
+/// since last step, where 0 shows previous step and 1 shows latest step. Until
+/// then output stays at previous step, so rendering lags one step behind.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct InterpolatedTransform {
+    pub position: Vec3<Scalar>,
+    pub rotation: Quaternion<Scalar>,
+    from_position: Vec3<Scalar>,
+    from_rotation: Quaternion<Scalar>,
+    to_position: Vec3<Scalar>,
+    to_rotation: Quaternion<Scalar>,
+}
+
+impl InterpolatedTransform {
+    pub fn new(position: impl Into<Vec3<Scalar>>, rotation: impl Into<Quaternion<Scalar>>) -> Self {
+        let position = position.into();
+        let rotation = rotation.into();
+        Self {
+            position,
+            rotation,
+            from_position: position,
+            from_rotation: rotation,
+            to_position: position,
+            to_rotation: rotation,
+        }
+    }
+
+    pub fn push_target(&mut self, position: Vec3<Scalar>, rotation: Quaternion<Scalar>) {
+        self.from_position = self.to_position;
+        self.from_rotation = self.to_rotation;
+        self.to_position = position;
+        self.to_rotation = rotation;
+        self.position = self.from_position;
+        self.rotation = self.from_rotation;
+    }
+
+    pub fn interpolate(&mut self, alpha: Scalar) {
+        self.position = Vec3::lerp(self.from_position, self.to_position, alpha);
+        self.rotation = Quaternion::slerp(self.from_rotation, self.to_rotation, alpha);
+    }
+}
+
 #[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
 #[repr(transparent)]
 pub struct LinearVelocity {
@@ -246,6 +312,21 @@ pub struct Gravity {
     pub value: Vec3<Scalar>,
 }
 
+/// Environmental drag spatially bounded by density field of the same entity.
+///
+/// Particles inside the field get velocity-opposing force proportional to
+/// field density at their position, `coefficient` and their speed squared.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct DragField {
+    pub coefficient: Scalar,
+}
+
+impl DragField {
+    pub fn new(coefficient: Scalar) -> Self {
+        Self { coefficient }
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
 pub struct BodyMaterial {
     pub friction: Scalar,
@@ -1 +1,2 @@
 pub mod distance;
+pub mod volume;
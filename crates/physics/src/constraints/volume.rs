@@ -0,0 +1,195 @@
+use crate::{
+    Scalar,
+    components::{Mass, PhysicsParticle, Position},
+};
+use anput::{
+    entity::Entity,
+    query::{Include, Lookup, Query},
+    systems::SystemContext,
+    world::{Relation, World},
+};
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use vek::Vec3;
+
+/// Relation between volume constraint entity and particles forming closed shape.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct VolumeConstraintParticleRelation;
+
+/// Preserves volume enclosed by related particles.
+///
+/// Faces index into particles in the order they were related to constraint
+/// entity, and their winding has to be counter-clockwise when looking at the
+/// shape from outside.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VolumeConstraint {
+    pub rest_volume: Scalar,
+    pub stiffness: Scalar,
+    pub faces: Vec<[usize; 3]>,
+}
+
+impl VolumeConstraint {
+    pub fn new(rest_volume: Scalar, stiffness: Scalar, faces: Vec<[usize; 3]>) -> Self {
+        Self {
+            rest_volume,
+            stiffness,
+            faces,
+        }
+    }
+
+    pub fn volume(&self, positions: &[Vec3<Scalar>]) -> Scalar {
+        self.faces
+            .iter()
+            .filter_map(|[a, b, c]| {
+                Some(
+                    positions
+                        .get(*a)?
+                        .dot(positions.get(*b)?.cross(*positions.get(*c)?)),
+                )
+            })
+            .sum::<Scalar>()
+            / 6.0
+    }
+}
+
+pub fn solve_volume_constraint<const LOCKING: bool>(
+    context: SystemContext,
+) -> Result<(), Box<dyn Error>> {
+    let (world, constraint_query, particle_lookup) = context.fetch::<(
+        &World,
+        Query<
+            LOCKING,
+            (
+                &VolumeConstraint,
+                &Relation<VolumeConstraintParticleRelation>,
+            ),
+        >,
+        Lookup<LOCKING, (&mut Position, &Mass, Include<PhysicsParticle>)>,
+    )>()?;
+
+    let mut particle_lookup = particle_lookup.lookup_access(world);
+    let mut particles = Vec::<Entity>::new();
+    let mut positions = Vec::<Vec3<Scalar>>::new();
+    let mut weights = Vec::<Scalar>::new();
+    let mut gradients = Vec::<Vec3<Scalar>>::new();
+
+    for (constraint, relation) in constraint_query.query(world) {
+        particles.clear();
+        positions.clear();
+        weights.clear();
+        for entity in relation.entities() {
+            let Some((position, mass, _)) = particle_lookup.access(entity) else {
+                continue;
+            };
+            particles.push(entity);
+            positions.push(position.current);
+            weights.push(mass.inverse());
+        }
+        if particles.len() < 4 {
+            continue;
+        }
+
+        gradients.clear();
+        gradients.resize(particles.len(), Vec3::zero());
+        for [a, b, c] in constraint.faces.iter().copied() {
+            if a >= positions.len() || b >= positions.len() || c >= positions.len() {
+                continue;
+            }
+            gradients[a] += positions[b].cross(positions[c]) / 6.0;
+            gradients[b] += positions[c].cross(positions[a]) / 6.0;
+            gradients[c] += positions[a].cross(positions[b]) / 6.0;
+        }
+
+        let denominator = gradients
+            .iter()
+            .zip(weights.iter())
+            .map(|(gradient, weight)| gradient.magnitude_squared() * weight)
+            .sum::<Scalar>();
+        if denominator < Scalar::EPSILON {
+            continue;
+        }
+        let error = constraint.volume(&positions) - constraint.rest_volume;
+        let lambda = -error * constraint.stiffness / denominator;
+
+        for ((entity, gradient), weight) in
+            particles.iter().zip(gradients.iter()).zip(weights.iter())
+        {
+            if let Some((position, _, _)) = particle_lookup.access(*entity) {
+                position.current += *gradient * (lambda * weight);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PhysicsPlugin;
+    use anput::{scheduler::GraphScheduler, third_party::moirai::jobs::Jobs, universe::Universe};
+
+    fn tetrahedron_volume(world: &World, constraint: Entity) -> Scalar {
+        let positions = world
+            .relations_outgoing::<true, VolumeConstraintParticleRelation>(constraint)
+            .map(|(_, _, particle)| world.component::<true, Position>(particle).unwrap().current)
+            .collect::<Vec<_>>();
+        world
+            .component::<true, VolumeConstraint>(constraint)
+            .unwrap()
+            .volume(&positions)
+    }
+
+    #[test]
+    fn test_volume_constraint() -> Result<(), Box<dyn Error>> {
+        let faces = vec![[0, 2, 1], [0, 1, 3], [0, 3, 2], [1, 2, 3]];
+
+        for rest_volume in [0.25, 0.1] {
+            let mut universe = Universe::default().with_plugin(
+                PhysicsPlugin::<true>::barebones()
+                    .install_volume_constraints_solver(true)
+                    .make(),
+            );
+            let jobs = Jobs::default();
+            let scheduler = GraphScheduler::<true>;
+
+            let constraint = universe.simulation.spawn((VolumeConstraint::new(
+                rest_volume,
+                1.0,
+                faces.clone(),
+            ),))?;
+            for position in [
+                Vec3::new(0.0, 0.0, 0.0),
+                Vec3::new(1.0, 0.0, 0.0),
+                Vec3::new(0.0, 1.0, 0.0),
+                Vec3::new(0.0, 0.0, 1.0),
+            ] {
+                let particle = universe.simulation.spawn((
+                    PhysicsParticle,
+                    Position::new(position),
+                    Mass::new(1.0),
+                ))?;
+                universe.simulation.relate::<true, _>(
+                    VolumeConstraintParticleRelation,
+                    constraint,
+                    particle,
+                )?;
+            }
+
+            let initial_volume = tetrahedron_volume(&universe.simulation, constraint);
+            assert!((initial_volume - 1.0 / 6.0).abs() < 1.0e-5);
+
+            scheduler.run(&jobs, &mut universe)?;
+            let volume = tetrahedron_volume(&universe.simulation, constraint);
+            assert!((volume - rest_volume).abs() < (initial_volume - rest_volume).abs());
+
+            for _ in 0..20 {
+                scheduler.run(&jobs, &mut universe)?;
+            }
+            let volume = tetrahedron_volume(&universe.simulation, constraint);
+            assert!((volume - rest_volume).abs() < 1.0e-3);
+        }
+
+        Ok(())
+    }
+}
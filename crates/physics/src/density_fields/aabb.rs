@@ -3,9 +3,11 @@ use crate::{
     components::BodyAccessInfo,
     density_fields::{DensityField, DensityRange},
 };
+use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
 use vek::{Aabb, Vec3};
 
+#[derive(Serialize, Deserialize)]
 pub struct AabbDensityField {
     pub aabb: Aabb<Scalar>,
     pub density: Scalar,
@@ -82,4 +84,32 @@ impl DensityField for AabbDensityField {
         .map(|(_, _, normal)| normal)
         .unwrap_or_default()
     }
+
+    fn is_convex(&self) -> bool {
+        true
+    }
+
+    fn support_point(&self, direction: Vec3<Scalar>, _: &BodyAccessInfo) -> Option<Vec3<Scalar>> {
+        Some(Vec3::new(
+            if direction.x < 0.0 {
+                self.aabb.min.x
+            } else {
+                self.aabb.max.x
+            },
+            if direction.y < 0.0 {
+                self.aabb.min.y
+            } else {
+                self.aabb.max.y
+            },
+            if direction.z < 0.0 {
+                self.aabb.min.z
+            } else {
+                self.aabb.max.z
+            },
+        ))
+    }
+
+    fn face_axes(&self, _: &BodyAccessInfo) -> Vec<Vec3<Scalar>> {
+        vec![Vec3::unit_x(), Vec3::unit_y(), Vec3::unit_z()]
+    }
 }
@@ -1,6 +1,6 @@
 use crate::{
     Scalar,
-    components::BodyAccessInfo,
+    components::{BodyAccessInfo, BodyMaterial},
     density_fields::{DensityField, DensityFieldBox, DensityRange},
 };
 use vek::{Aabb, Vec3};
@@ -47,4 +47,24 @@ impl DensityField for AdditionDensityField {
             .and_then(|normal| normal.try_normalized())
             .unwrap_or_default()
     }
+
+    fn material_at_point(
+        &self,
+        point: Vec3<Scalar>,
+        info: &BodyAccessInfo,
+    ) -> Option<BodyMaterial> {
+        // densest field reporting material wins, later fields win ties.
+        self.fields
+            .iter()
+            .filter_map(|field| {
+                let density = field.density_at_point(point, info);
+                if density > 0.0 {
+                    Some((density, field.material_at_point(point, info)?))
+                } else {
+                    None
+                }
+            })
+            .reduce(|accum, item| if item.0 >= accum.0 { item } else { accum })
+            .map(|(_, material)| material)
+    }
 }
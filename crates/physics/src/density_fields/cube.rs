@@ -1,8 +1,10 @@
 use std::cmp::Ordering;
 
 use crate::{Scalar, components::BodyAccessInfo, density_fields::DensityField};
+use serde::{Deserialize, Serialize};
 use vek::{Aabb, Vec3};
 
+#[derive(Serialize, Deserialize)]
 pub struct CubeDensityField<const LOCKING: bool> {
     pub density: Scalar,
     pub extents: Vec3<Scalar>,
@@ -157,6 +159,39 @@ impl<const LOCKING: bool> DensityField for CubeDensityField<LOCKING> {
             .and_then(|normal| normal.try_normalized())
             .unwrap_or_default()
     }
+
+    fn is_convex(&self) -> bool {
+        true
+    }
+
+    fn support_point(
+        &self,
+        direction: Vec3<Scalar>,
+        info: &BodyAccessInfo,
+    ) -> Option<Vec3<Scalar>> {
+        let mut particles = info.world_space_particles::<LOCKING, ()>();
+        let (matrix, _) = particles.next()?;
+        if particles.next().is_some() {
+            return None;
+        }
+        let direction = matrix.inverted().mul_direction(direction);
+        let extents = self.total_extents();
+        let corner = Vec3::new(
+            extents.x.copysign(direction.x),
+            extents.y.copysign(direction.y),
+            extents.z.copysign(direction.z),
+        );
+        Some(matrix.mul_point(corner))
+    }
+
+    fn face_axes(&self, info: &BodyAccessInfo) -> Vec<Vec3<Scalar>> {
+        info.world_space_particles::<LOCKING, ()>()
+            .flat_map(|(matrix, _)| {
+                [Vec3::unit_x(), Vec3::unit_y(), Vec3::unit_z()]
+                    .map(|axis| matrix.mul_direction(axis))
+            })
+            .collect()
+    }
 }
 
 #[cfg(test)]
@@ -165,11 +200,12 @@ mod tests {
     use crate::{
         components::{
             BodyDensityFieldRelation, BodyParentRelation, BodyParticleRelation, PhysicsBody,
-            PhysicsParticle, Position,
+            PhysicsParticle, Position, Rotation,
         },
         density_fields::{DensityFieldBox, DensityRange},
     };
     use anput::world::World;
+    use vek::Quaternion;
 
     #[test]
     fn test_cube_density_field() {
@@ -195,7 +231,6 @@ mod tests {
         let cube = world
             .entity::<true, &DensityFieldBox>(object)
             .unwrap()
-            .as_any()
             .downcast_ref::<CubeDensityField<true>>()
             .unwrap();
         let info = BodyAccessInfo::of_world(object, &world);
@@ -278,4 +313,42 @@ mod tests {
             Vec3::new(0.0, -1.0, 0.0)
         );
     }
+
+    #[test]
+    fn test_cube_density_field_rotated_aabb() {
+        let mut world = World::default();
+        let object = world
+            .spawn((
+                PhysicsBody,
+                PhysicsParticle,
+                Position::new(Vec3::new(1.0, 2.0, 3.0)),
+                Rotation::new(Quaternion::rotation_z(
+                    std::f32::consts::FRAC_PI_4 as Scalar,
+                )),
+            ))
+            .unwrap();
+        world
+            .relate::<true, _>(BodyParticleRelation, object, object)
+            .unwrap();
+        world
+            .relate::<true, _>(BodyParentRelation, object, object)
+            .unwrap();
+        let info = BodyAccessInfo::of_world(object, &world);
+
+        let extents = Vec3::new(10.0, 0.5, 0.5);
+        let cube = CubeDensityField::<true>::new_hard(1.0, extents);
+        let aabb = cube.aabb(&info);
+
+        // bounding rotated corners is tighter than bounding extents sphere.
+        let tight = (extents.x + extents.y) * std::f32::consts::FRAC_1_SQRT_2 as Scalar;
+        let loose = extents.magnitude();
+        let center = Vec3::new(1.0, 2.0, 3.0);
+        for axis in 0..2 {
+            assert!((aabb.max[axis] - center[axis] - tight).abs() < 1.0e-4);
+            assert!((aabb.min[axis] - center[axis] + tight).abs() < 1.0e-4);
+            assert!(aabb.max[axis] - center[axis] < loose);
+        }
+        assert!((aabb.max.z - center.z - extents.z).abs() < 1.0e-4);
+        assert!((aabb.min.z - center.z + extents.z).abs() < 1.0e-4);
+    }
 }
@@ -0,0 +1,132 @@
+use crate::{
+    Scalar,
+    components::{BodyAccessInfo, BodyMaterial},
+    density_fields::{DensityField, DensityFieldBox, DensityRange},
+};
+use vek::{Aabb, Vec3};
+
+/// Reports material wherever wrapped field has any density.
+pub struct MaterialDensityField {
+    pub field: DensityFieldBox,
+    pub material: BodyMaterial,
+}
+
+impl MaterialDensityField {
+    pub fn new(field: impl Into<DensityFieldBox>, material: BodyMaterial) -> Self {
+        Self {
+            field: field.into(),
+            material,
+        }
+    }
+}
+
+impl DensityField for MaterialDensityField {
+    fn aabb(&self, info: &BodyAccessInfo) -> Aabb<Scalar> {
+        self.field.aabb(info)
+    }
+
+    fn density_at_point(&self, point: Vec3<Scalar>, info: &BodyAccessInfo) -> Scalar {
+        self.field.density_at_point(point, info)
+    }
+
+    fn density_at_region(&self, region: Aabb<Scalar>, info: &BodyAccessInfo) -> DensityRange {
+        self.field.density_at_region(region, info)
+    }
+
+    fn normal_at_point(
+        &self,
+        point: Vec3<Scalar>,
+        resolution: Vec3<Scalar>,
+        info: &BodyAccessInfo,
+    ) -> Vec3<Scalar> {
+        self.field.normal_at_point(point, resolution, info)
+    }
+
+    fn is_convex(&self) -> bool {
+        self.field.is_convex()
+    }
+
+    fn support_point(
+        &self,
+        direction: Vec3<Scalar>,
+        info: &BodyAccessInfo,
+    ) -> Option<Vec3<Scalar>> {
+        self.field.support_point(direction, info)
+    }
+
+    fn face_axes(&self, info: &BodyAccessInfo) -> Vec<Vec3<Scalar>> {
+        self.field.face_axes(info)
+    }
+
+    fn material_at_point(
+        &self,
+        point: Vec3<Scalar>,
+        info: &BodyAccessInfo,
+    ) -> Option<BodyMaterial> {
+        if self.field.density_at_point(point, info) > 0.0 {
+            self.field
+                .material_at_point(point, info)
+                .or(Some(self.material))
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        components::{
+            BodyParentRelation, BodyParticleRelation, PhysicsBody, PhysicsParticle, Position,
+        },
+        density_fields::sphere::SphereDensityField,
+    };
+    use anput::world::World;
+
+    #[test]
+    fn test_material_density_field_regions() {
+        let mut world = World::default();
+        let object = world
+            .spawn((PhysicsBody, PhysicsParticle, Position::new(Vec3::zero())))
+            .unwrap();
+        world
+            .relate::<true, _>(BodyParticleRelation, object, object)
+            .unwrap();
+        world
+            .relate::<true, _>(BodyParentRelation, object, object)
+            .unwrap();
+        let info = BodyAccessInfo::of_world(object, &world);
+
+        let rock = BodyMaterial {
+            friction: 0.9,
+            restitution: 0.1,
+        };
+        let metal = BodyMaterial {
+            friction: 0.2,
+            restitution: 0.8,
+        };
+        let field = DensityFieldBox::new(MaterialDensityField::new(
+            SphereDensityField::<true>::new_hard(1.0, 5.0),
+            rock,
+        ))
+        .union(MaterialDensityField::new(
+            SphereDensityField::<true>::new_hard(1.0, 2.0),
+            metal,
+        ));
+
+        assert_eq!(field.material_at_point(Vec3::zero(), &info), Some(metal));
+        assert_eq!(
+            field.material_at_point(Vec3::new(4.0, 0.0, 0.0), &info),
+            Some(rock)
+        );
+        assert_eq!(
+            field.material_at_point(Vec3::new(6.0, 0.0, 0.0), &info),
+            None
+        );
+        assert_eq!(
+            SphereDensityField::<true>::new_hard(1.0, 5.0).material_at_point(Vec3::zero(), &info),
+            None
+        );
+    }
+}
@@ -1,11 +1,19 @@
 pub mod aabb;
 pub mod addition;
 pub mod cube;
+pub mod material;
 pub mod multiplication;
 pub mod sphere;
 pub mod subtraction;
 
-use crate::{Scalar, components::BodyAccessInfo};
+use crate::{
+    Scalar,
+    components::{BodyAccessInfo, BodyMaterial},
+    density_fields::{
+        addition::AdditionDensityField, multiplication::MultiplicationDensityField,
+        subtraction::SubtractionDensityField,
+    },
+};
 use std::{
     any::Any,
     ops::{Add, AddAssign, Deref, DerefMut, Div, DivAssign, Mul, MulAssign, Sub, SubAssign},
@@ -26,6 +34,41 @@ impl DensityFieldBox {
     pub fn as_any_mut(&mut self) -> &mut dyn Any {
         &mut *self.0
     }
+
+    pub fn downcast_ref<F: DensityField>(&self) -> Option<&F> {
+        self.as_any().downcast_ref::<F>()
+    }
+
+    pub fn downcast_mut<F: DensityField>(&mut self) -> Option<&mut F> {
+        self.as_any_mut().downcast_mut::<F>()
+    }
+
+    /// Wraps this and other field in [`AdditionDensityField`].
+    pub fn union(self, other: impl Into<Self>) -> Self {
+        Self::new(AdditionDensityField {
+            fields: vec![self, other.into()],
+        })
+    }
+
+    /// Wraps this and other field in [`SubtractionDensityField`].
+    pub fn subtract(self, other: impl Into<Self>) -> Self {
+        Self::new(SubtractionDensityField {
+            fields: vec![self, other.into()],
+        })
+    }
+
+    /// Wraps this and other field in [`MultiplicationDensityField`].
+    pub fn intersect(self, other: impl Into<Self>) -> Self {
+        Self::new(MultiplicationDensityField {
+            fields: vec![self, other.into()],
+        })
+    }
+}
+
+impl<T: DensityField> From<T> for DensityFieldBox {
+    fn from(field: T) -> Self {
+        Self::new(field)
+    }
 }
 
 impl Deref for DensityFieldBox {
@@ -111,6 +154,131 @@ pub trait DensityField: Send + Sync + Any {
     ) -> Vec3<Scalar> {
         Default::default()
     }
+
+    /// Marches ray through the field and returns first point where density
+    /// reaches `density_threshold`, along with surface normal at that point.
+    ///
+    /// The default implementation samples densities at fixed `step` along
+    /// the ray clipped to field AABB, then refines the hit by bisecting the
+    /// last step. You should implement this method if field can provide an
+    /// analytic ray intersection.
+    fn raymarch(
+        &self,
+        origin: Vec3<Scalar>,
+        direction: Vec3<Scalar>,
+        max_distance: Scalar,
+        step: Scalar,
+        info: &BodyAccessInfo,
+        density_threshold: Scalar,
+    ) -> Option<(Vec3<Scalar>, Vec3<Scalar>)> {
+        let direction = direction.try_normalized()?;
+        let (from, to) = ray_aabb_range(origin, direction, self.aabb(info))?;
+        let to = to.min(max_distance);
+        let step = step.max(Scalar::EPSILON);
+        let mut distance = from.max(0.0);
+        let mut previous = None;
+        while distance <= to {
+            if self.density_at_point(origin + direction * distance, info) >= density_threshold {
+                if let Some(mut miss) = previous {
+                    let mut hit = distance;
+                    for _ in 0..8 {
+                        let middle = (miss + hit) * 0.5;
+                        if self.density_at_point(origin + direction * middle, info)
+                            >= density_threshold
+                        {
+                            hit = middle;
+                        } else {
+                            miss = middle;
+                        }
+                    }
+                    distance = hit;
+                }
+                let point = origin + direction * distance;
+                return Some((
+                    point,
+                    self.normal_at_point(point, Vec3::broadcast(step), info),
+                ));
+            }
+            previous = Some(distance);
+            distance += step;
+        }
+        None
+    }
+
+    /// Tells if field occupies convex volume.
+    ///
+    /// Convex fields reporting support points can be handled by analytic
+    /// narrowphase instead of voxelization. The default implementation
+    /// returns `false`.
+    fn is_convex(&self) -> bool {
+        false
+    }
+
+    /// Returns the farthest point of field outer boundary along direction.
+    ///
+    /// Used by analytic narrowphase of convex fields. The default
+    /// implementation returns `None`, meaning there is no support point
+    /// available, e.g. when body has multiple particles forming the field.
+    #[allow(unused_variables)]
+    fn support_point(
+        &self,
+        direction: Vec3<Scalar>,
+        info: &BodyAccessInfo,
+    ) -> Option<Vec3<Scalar>> {
+        None
+    }
+
+    /// Returns world space directions of field flat faces.
+    ///
+    /// Used by analytic narrowphase of convex fields as candidate separating
+    /// axes, together with cross products of both fields face directions
+    /// standing for their edges. The default implementation returns no axes,
+    /// which is enough for fields without flat faces, e.g. spheres.
+    #[allow(unused_variables)]
+    fn face_axes(&self, info: &BodyAccessInfo) -> Vec<Vec3<Scalar>> {
+        vec![]
+    }
+
+    /// Returns the material at the given point.
+    ///
+    /// Heterogeneous fields can report different materials per region, which
+    /// contact response prefers over body material. The default
+    /// implementation returns `None`, meaning body material should be used.
+    #[allow(unused_variables)]
+    fn material_at_point(
+        &self,
+        point: Vec3<Scalar>,
+        info: &BodyAccessInfo,
+    ) -> Option<BodyMaterial> {
+        None
+    }
+}
+
+fn ray_aabb_range(
+    origin: Vec3<Scalar>,
+    direction: Vec3<Scalar>,
+    aabb: Aabb<Scalar>,
+) -> Option<(Scalar, Scalar)> {
+    let mut from = Scalar::NEG_INFINITY;
+    let mut to = Scalar::INFINITY;
+    for axis in 0..3 {
+        if direction[axis].abs() < Scalar::EPSILON {
+            if origin[axis] < aabb.min[axis] || origin[axis] > aabb.max[axis] {
+                return None;
+            }
+            continue;
+        }
+        let inverse = 1.0 / direction[axis];
+        let a = (aabb.min[axis] - origin[axis]) * inverse;
+        let b = (aabb.max[axis] - origin[axis]) * inverse;
+        from = from.max(a.min(b));
+        to = to.min(a.max(b));
+    }
+    if from <= to && to >= 0.0 {
+        Some((from, to))
+    } else {
+        None
+    }
 }
 
 #[derive(Debug, Default, Clone, Copy, PartialEq)]
@@ -132,13 +300,21 @@ impl DensityRange {
     }
 
     pub fn has_converged(&self) -> bool {
-        self.separation() < Scalar::EPSILON
+        self.has_converged_within(Scalar::EPSILON)
+    }
+
+    pub fn has_converged_within(&self, tolerance: Scalar) -> bool {
+        self.separation() < tolerance
     }
 
     pub fn has_separation(&self) -> bool {
         !self.has_converged()
     }
 
+    pub fn has_separation_within(&self, tolerance: Scalar) -> bool {
+        !self.has_converged_within(tolerance)
+    }
+
     pub fn average(&self) -> Scalar {
         (self.min + self.max) * 0.5
     }
@@ -265,3 +441,148 @@ impl DivAssign<Scalar> for DensityRange {
         self.max /= scalar;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        components::{
+            BodyParentRelation, BodyParticleRelation, PhysicsBody, PhysicsParticle, Position,
+        },
+        density_fields::{cube::CubeDensityField, sphere::SphereDensityField},
+    };
+    use anput::world::World;
+
+    #[test]
+    fn test_density_field_box_downcast() {
+        let mut field = DensityFieldBox::new(SphereDensityField::<true>::new_hard(1.0, 10.0));
+
+        assert!(field.downcast_ref::<SphereDensityField<true>>().is_some());
+        assert!(field.downcast_ref::<SphereDensityField<false>>().is_none());
+        assert!(field.downcast_ref::<CubeDensityField<true>>().is_none());
+
+        field
+            .downcast_mut::<SphereDensityField<true>>()
+            .unwrap()
+            .radius = 5.0;
+        assert_eq!(
+            field
+                .downcast_ref::<SphereDensityField<true>>()
+                .unwrap()
+                .radius,
+            5.0
+        );
+        assert!(field.downcast_mut::<CubeDensityField<true>>().is_none());
+    }
+
+    #[test]
+    fn test_density_field_box_combinators() {
+        let mut world = World::default();
+        let object = world
+            .spawn((PhysicsBody, PhysicsParticle, Position::new(Vec3::zero())))
+            .unwrap();
+        world
+            .relate::<true, _>(BodyParticleRelation, object, object)
+            .unwrap();
+        world
+            .relate::<true, _>(BodyParentRelation, object, object)
+            .unwrap();
+        let info = BodyAccessInfo::of_world(object, &world);
+
+        let sphere = || SphereDensityField::<true>::new_hard(1.0, 2.0);
+        let cube = || CubeDensityField::<true>::new_hard(1.0, Vec3::new(3.0, 1.0, 1.0));
+        let hole = || SphereDensityField::<true>::new_hard(1.0, 0.5);
+
+        let nested = DensityFieldBox::new(SubtractionDensityField {
+            fields: vec![
+                DensityFieldBox::new(MultiplicationDensityField {
+                    fields: vec![
+                        DensityFieldBox::new(AdditionDensityField {
+                            fields: vec![
+                                DensityFieldBox::new(sphere()),
+                                DensityFieldBox::new(cube()),
+                            ],
+                        }),
+                        DensityFieldBox::new(cube()),
+                    ],
+                }),
+                DensityFieldBox::new(hole()),
+            ],
+        });
+        let fluent = DensityFieldBox::new(sphere())
+            .union(cube())
+            .intersect(cube())
+            .subtract(hole());
+
+        assert_eq!(fluent.aabb(&info), nested.aabb(&info));
+        for x in -8..=8 {
+            for y in -4..=4 {
+                let point = Vec3::new(x as Scalar * 0.5, y as Scalar * 0.5, 0.25);
+                assert_eq!(
+                    fluent.density_at_point(point, &info),
+                    nested.density_at_point(point, &info)
+                );
+            }
+        }
+        assert_eq!(fluent.density_at_point(Vec3::zero(), &info), 1.0);
+        assert_eq!(
+            fluent.density_at_point(Vec3::new(2.5, 0.0, 0.0), &info),
+            1.0
+        );
+    }
+
+    #[test]
+    fn test_density_field_raymarch() {
+        let mut world = World::default();
+        let object = world
+            .spawn((PhysicsBody, PhysicsParticle, Position::new(Vec3::zero())))
+            .unwrap();
+        world
+            .relate::<true, _>(BodyParticleRelation, object, object)
+            .unwrap();
+        world
+            .relate::<true, _>(BodyParentRelation, object, object)
+            .unwrap();
+        let info = BodyAccessInfo::of_world(object, &world);
+        let field = SphereDensityField::<true>::new_hard(1.0, 5.0);
+
+        let (point, normal) = field
+            .raymarch(
+                Vec3::new(-20.0, 0.0, 0.0),
+                Vec3::unit_x(),
+                100.0,
+                0.5,
+                &info,
+                0.5,
+            )
+            .unwrap();
+        assert!((point.x + 5.0).abs() < 0.01);
+        assert!(point.y.abs() < 1.0e-4 && point.z.abs() < 1.0e-4);
+        assert!(normal.dot(-Vec3::unit_x()) > 0.9);
+
+        assert!(
+            field
+                .raymarch(
+                    Vec3::new(-20.0, 6.0, 0.0),
+                    Vec3::unit_x(),
+                    100.0,
+                    0.5,
+                    &info,
+                    0.5,
+                )
+                .is_none()
+        );
+        assert!(
+            field
+                .raymarch(
+                    Vec3::new(-20.0, 0.0, 0.0),
+                    Vec3::unit_x(),
+                    10.0,
+                    0.5,
+                    &info,
+                    0.5,
+                )
+                .is_none()
+        );
+    }
+}
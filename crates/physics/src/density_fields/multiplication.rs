@@ -1,6 +1,6 @@
 use crate::{
     Scalar,
-    components::BodyAccessInfo,
+    components::{BodyAccessInfo, BodyMaterial},
     density_fields::{DensityField, DensityFieldBox, DensityRange},
 };
 use vek::{Aabb, Vec3};
@@ -47,4 +47,14 @@ impl DensityField for MultiplicationDensityField {
             .and_then(|normal| normal.try_normalized())
             .unwrap_or_default()
     }
+
+    fn material_at_point(
+        &self,
+        point: Vec3<Scalar>,
+        info: &BodyAccessInfo,
+    ) -> Option<BodyMaterial> {
+        self.fields
+            .iter()
+            .find_map(|field| field.material_at_point(point, info))
+    }
 }
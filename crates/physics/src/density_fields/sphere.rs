@@ -3,8 +3,10 @@ use crate::{
     components::Position,
     density_fields::{BodyAccessInfo, DensityField},
 };
+use serde::{Deserialize, Serialize};
 use vek::{Aabb, Vec3};
 
+#[derive(Serialize, Deserialize)]
 pub struct SphereDensityField<const LOCKING: bool> {
     pub density: Scalar,
     pub radius: Scalar,
@@ -90,6 +92,23 @@ impl<const LOCKING: bool> DensityField for SphereDensityField<LOCKING> {
             .and_then(|normal| normal.try_normalized())
             .unwrap_or_default()
     }
+
+    fn is_convex(&self) -> bool {
+        true
+    }
+
+    fn support_point(
+        &self,
+        direction: Vec3<Scalar>,
+        info: &BodyAccessInfo,
+    ) -> Option<Vec3<Scalar>> {
+        let mut particles = info.particles::<LOCKING, &Position>();
+        let position = particles.next()?;
+        if particles.next().is_some() {
+            return None;
+        }
+        Some(position.current + direction.try_normalized()? * self.total_radius())
+    }
 }
 
 #[cfg(test)]
@@ -128,7 +147,6 @@ mod tests {
         let sphere = world
             .entity::<true, &DensityFieldBox>(object)
             .unwrap()
-            .as_any()
             .downcast_ref::<SphereDensityField<true>>()
             .unwrap();
         let info = BodyAccessInfo::of_world(object, &world);
@@ -1,6 +1,6 @@
 use crate::{
     Scalar,
-    components::BodyAccessInfo,
+    components::{BodyAccessInfo, BodyMaterial},
     density_fields::{DensityField, DensityFieldBox, DensityRange},
 };
 use vek::{Aabb, Vec3};
@@ -30,7 +30,11 @@ impl DensityField for SubtractionDensityField {
         self.fields
             .iter()
             .map(|field| field.density_at_region(region, info))
-            .reduce(|accum, range| accum - range)
+            // interval subtraction, so range still bounds point densities.
+            .reduce(|accum, range| DensityRange {
+                min: accum.min - range.max,
+                max: accum.max - range.min,
+            })
             .unwrap_or_default()
     }
 
@@ -47,4 +51,103 @@ impl DensityField for SubtractionDensityField {
             .and_then(|normal| normal.try_normalized())
             .unwrap_or_default()
     }
+
+    fn material_at_point(
+        &self,
+        point: Vec3<Scalar>,
+        info: &BodyAccessInfo,
+    ) -> Option<BodyMaterial> {
+        self.fields.first()?.material_at_point(point, info)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        components::{
+            BodyDensityFieldRelation, BodyParentRelation, BodyParticleRelation, PhysicsBody,
+            PhysicsParticle, Position,
+        },
+        density_fields::{aabb::AabbDensityField, sphere::SphereDensityField},
+    };
+    use anput::world::World;
+
+    #[test]
+    fn test_subtraction_density_at_region() {
+        let mut world = World::default();
+        let object = world
+            .spawn((
+                PhysicsBody,
+                PhysicsParticle,
+                Position::new(Vec3::zero()),
+                DensityFieldBox::new(SubtractionDensityField {
+                    fields: vec![
+                        DensityFieldBox::new(AabbDensityField {
+                            aabb: Aabb {
+                                min: Vec3::broadcast(-2.0),
+                                max: Vec3::broadcast(2.0),
+                            },
+                            density: 1.0,
+                        }),
+                        DensityFieldBox::new(SphereDensityField::<true>::new_hard(1.0, 1.0)),
+                    ],
+                }),
+            ))
+            .unwrap();
+        world
+            .relate::<true, _>(BodyParticleRelation, object, object)
+            .unwrap();
+        world
+            .relate::<true, _>(BodyDensityFieldRelation, object, object)
+            .unwrap();
+        world
+            .relate::<true, _>(BodyParentRelation, object, object)
+            .unwrap();
+
+        let field = world.entity::<true, &DensityFieldBox>(object).unwrap();
+        let info = BodyAccessInfo::of_world(object, &world);
+
+        for (min, max) in [
+            (-0.1, 0.1),
+            (0.5, 1.5),
+            (1.5, 1.9),
+            (1.5, 2.5),
+            (-3.0, 3.0),
+            (3.0, 4.0),
+        ] {
+            let region = Aabb {
+                min: Vec3::broadcast(min),
+                max: Vec3::broadcast(max),
+            };
+            let analytic = field.density_at_region(region, &info);
+            let sampled = [
+                region.center(),
+                Vec3::new(region.min.x, region.min.y, region.min.z),
+                Vec3::new(region.max.x, region.min.y, region.min.z),
+                Vec3::new(region.min.x, region.max.y, region.min.z),
+                Vec3::new(region.max.x, region.max.y, region.min.z),
+                Vec3::new(region.min.x, region.min.y, region.max.z),
+                Vec3::new(region.max.x, region.min.y, region.max.z),
+                Vec3::new(region.min.x, region.max.y, region.max.z),
+                Vec3::new(region.max.x, region.max.y, region.max.z),
+            ]
+            .into_iter()
+            .map(|point| DensityRange::converged(field.density_at_point(point, &info)))
+            .reduce(|accum, density| accum.min_max(&density))
+            .unwrap();
+            assert!(analytic.min <= analytic.max);
+            assert!(analytic.min <= sampled.min);
+            assert!(analytic.max >= sampled.max);
+        }
+
+        let inside_box_only = field.density_at_region(
+            Aabb {
+                min: Vec3::broadcast(1.5),
+                max: Vec3::broadcast(1.9),
+            },
+            &info,
+        );
+        assert_eq!(inside_box_only, DensityRange::converged(1.0));
+    }
 }
@@ -3,6 +3,7 @@ pub mod components;
 pub mod constraints;
 pub mod density_fields;
 pub mod queries;
+pub mod serialization;
 pub mod solvers;
 pub mod utils;
 
@@ -12,21 +13,22 @@ pub mod third_party {
 
 use crate::{
     collisions::{
-        CollisionProfile, ContactDetection, ContactsCache, DensityFieldSpatialExtractor,
-        RepulsiveCollisionCallbacks, RepulsiveCollisionSolver, collect_contacts,
-        dispatch_contact_events,
+        CollisionProfile, ContactDetection, ContactEvents, ContactFilter, ContactsCache,
+        DensityFieldSpatialExtractor, RepulsiveCollisionCallbacks, RepulsiveCollisionSolver,
+        collect_contacts, dispatch_contact_events,
     },
     components::{
         AngularVelocity, BodyDensityFieldRelation, BodyMaterial, BodyParentRelation,
         BodyParticleRelation, ExternalForces, LinearVelocity, Mass, ParticleConstraintRelation,
-        PhysicsBody, PhysicsParticle, Position,
+        PhysicsBody, PhysicsParticle, Position, Rotation,
     },
-    constraints::distance::solve_distance_constraint,
+    constraints::{distance::solve_distance_constraint, volume::solve_volume_constraint},
     density_fields::DensityFieldBox,
     queries::shape::ShapeOverlapQuery,
     solvers::{
-        apply_external_forces, apply_gravity, cache_current_as_previous_state, dampening_solver,
-        integrate_velocities, recalculate_velocities,
+        apply_drag_fields, apply_external_forces, apply_gravity, cache_current_as_previous_state,
+        cache_interpolation_targets, dampening_solver, integrate_velocities,
+        recalculate_velocities,
     },
 };
 use anput::{scheduler::GraphSchedulerPlugin, view::TypedWorldView, world::Relation};
@@ -42,6 +44,7 @@ pub type PhysicsAccessBundleColumns = (
     BodyMaterial,
     Mass,
     Position,
+    Rotation,
     LinearVelocity,
     AngularVelocity,
     ExternalForces,
@@ -60,6 +63,24 @@ pub type PhysicsAccessView = TypedWorldView<PhysicsAccessBundleColumns>;
 pub struct PhysicsSimulation {
     pub delta_time: Scalar,
     pub gravity: Vec3<Scalar>,
+    /// Sorts blocking contacts by their density fields and contact cells by
+    /// their region before accumulating collision corrections, so results do
+    /// not depend on contacts and cells collection order.
+    #[serde(default)]
+    pub deterministic_accumulation: bool,
+    /// Number of contact resolution passes per step. Every pass after first
+    /// one resolves only penetration left after bodies got displaced by
+    /// previous passes, which helps stacked bodies settle.
+    #[serde(default = "default_solver_iterations")]
+    pub solver_iterations: usize,
+    /// Fraction of displacement persisting contacts resolved during previous
+    /// step, applied upfront to seed solver. Zero disables warm-starting.
+    #[serde(default)]
+    pub warm_starting: Scalar,
+}
+
+fn default_solver_iterations() -> usize {
+    1
 }
 
 impl Default for PhysicsSimulation {
@@ -67,6 +88,9 @@ impl Default for PhysicsSimulation {
         Self {
             delta_time: 1.0 / 20.0,
             gravity: Default::default(),
+            deterministic_accumulation: false,
+            solver_iterations: default_solver_iterations(),
+            warm_starting: 0.0,
         }
     }
 }
@@ -86,13 +110,18 @@ pub struct PhysicsPlugin<const LOCKING: bool> {
     shape_overlap_query: ShapeOverlapQuery,
     install_repulsive_collision: bool,
     install_apply_gravity: bool,
+    install_apply_drag_fields: bool,
     install_apply_external_forces: bool,
     install_integrate_velocities: bool,
     install_collect_contacts: bool,
     install_dispatch_contact_events: bool,
+    install_contact_events_queue: bool,
     repulsive_collision_callbacks: RepulsiveCollisionCallbacks,
+    contact_filter: Option<ContactFilter>,
     install_dampening_solver: bool,
     install_distance_constraints_solver: bool,
+    install_volume_constraints_solver: bool,
+    install_interpolated_transforms: bool,
 }
 
 impl<const LOCKING: bool> Default for PhysicsPlugin<LOCKING> {
@@ -102,13 +131,18 @@ impl<const LOCKING: bool> Default for PhysicsPlugin<LOCKING> {
             shape_overlap_query: Default::default(),
             install_repulsive_collision: true,
             install_apply_gravity: true,
+            install_apply_drag_fields: true,
             install_apply_external_forces: true,
             install_integrate_velocities: true,
             install_collect_contacts: true,
             install_dispatch_contact_events: true,
+            install_contact_events_queue: false,
             repulsive_collision_callbacks: Default::default(),
+            contact_filter: None,
             install_dampening_solver: true,
             install_distance_constraints_solver: true,
+            install_volume_constraints_solver: true,
+            install_interpolated_transforms: true,
         }
     }
 }
@@ -120,13 +154,18 @@ impl<const LOCKING: bool> PhysicsPlugin<LOCKING> {
             shape_overlap_query: Default::default(),
             install_repulsive_collision: false,
             install_apply_gravity: false,
+            install_apply_drag_fields: false,
             install_apply_external_forces: false,
             install_integrate_velocities: false,
             install_collect_contacts: false,
             install_dispatch_contact_events: false,
+            install_contact_events_queue: false,
             repulsive_collision_callbacks: Default::default(),
+            contact_filter: None,
             install_dampening_solver: false,
             install_distance_constraints_solver: false,
+            install_volume_constraints_solver: false,
+            install_interpolated_transforms: false,
         }
     }
 
@@ -150,6 +189,11 @@ impl<const LOCKING: bool> PhysicsPlugin<LOCKING> {
         self
     }
 
+    pub fn install_apply_drag_fields(mut self, install: bool) -> Self {
+        self.install_apply_drag_fields = install;
+        self
+    }
+
     pub fn install_apply_external_forces(mut self, install: bool) -> Self {
         self.install_apply_external_forces = install;
         self
@@ -165,11 +209,21 @@ impl<const LOCKING: bool> PhysicsPlugin<LOCKING> {
         self
     }
 
+    pub fn install_contact_events_queue(mut self, install: bool) -> Self {
+        self.install_contact_events_queue = install;
+        self
+    }
+
     pub fn repulsive_collision_callbacks(mut self, callbacks: RepulsiveCollisionCallbacks) -> Self {
         self.repulsive_collision_callbacks = callbacks;
         self
     }
 
+    pub fn contact_filter(mut self, filter: ContactFilter) -> Self {
+        self.contact_filter = Some(filter);
+        self
+    }
+
     pub fn install_dampening_solver(mut self, install: bool) -> Self {
         self.install_dampening_solver = install;
         self
@@ -180,25 +234,48 @@ impl<const LOCKING: bool> PhysicsPlugin<LOCKING> {
         self
     }
 
+    pub fn install_volume_constraints_solver(mut self, install: bool) -> Self {
+        self.install_volume_constraints_solver = install;
+        self
+    }
+
+    pub fn install_interpolated_transforms(mut self, install: bool) -> Self {
+        self.install_interpolated_transforms = install;
+        self
+    }
+
     pub fn make(self) -> GraphSchedulerPlugin<LOCKING> {
         let Self {
             simulation,
             shape_overlap_query,
             install_repulsive_collision,
             install_apply_gravity,
+            install_apply_drag_fields,
             install_apply_external_forces,
             install_integrate_velocities,
             install_collect_contacts,
             install_dispatch_contact_events,
+            install_contact_events_queue,
             repulsive_collision_callbacks,
+            contact_filter,
             install_dampening_solver,
             install_distance_constraints_solver,
+            install_volume_constraints_solver,
+            install_interpolated_transforms,
         } = self;
 
         GraphSchedulerPlugin::<LOCKING>::default()
             .name("physics_simulation")
             .resource(simulation)
             .resource(ContactsCache::default())
+            .maybe_setup(|plugin| {
+                if install_contact_events_queue {
+                    Some(plugin.resource(ContactEvents::default()))
+                } else {
+                    None
+                }
+            })
+            .maybe_setup(|plugin| contact_filter.map(|filter| plugin.resource(filter)))
             .plugin_setup(|plugin| {
                 plugin
                     .name("pre_simulation")
@@ -211,6 +288,15 @@ impl<const LOCKING: bool> PhysicsPlugin<LOCKING> {
                             None
                         }
                     })
+                    .maybe_setup(|plugin| {
+                        if install_apply_drag_fields {
+                            Some(plugin.system_setup(apply_drag_fields::<LOCKING>, |system| {
+                                system.name("apply_drag_fields")
+                            }))
+                        } else {
+                            None
+                        }
+                    })
                     .maybe_setup(|plugin| {
                         if install_apply_external_forces {
                             Some(
@@ -289,17 +375,31 @@ impl<const LOCKING: bool> PhysicsPlugin<LOCKING> {
                     })
             })
             .plugin_setup(|plugin| {
-                plugin.name("solvers").maybe_setup(|plugin| {
-                    if install_distance_constraints_solver {
-                        Some(
-                            plugin.system_setup(solve_distance_constraint::<LOCKING>, |system| {
-                                system.name("solve_distance_constraint")
-                            }),
-                        )
-                    } else {
-                        None
-                    }
-                })
+                plugin
+                    .name("solvers")
+                    .maybe_setup(|plugin| {
+                        if install_distance_constraints_solver {
+                            Some(
+                                plugin
+                                    .system_setup(solve_distance_constraint::<LOCKING>, |system| {
+                                        system.name("solve_distance_constraint")
+                                    }),
+                            )
+                        } else {
+                            None
+                        }
+                    })
+                    .maybe_setup(|plugin| {
+                        if install_volume_constraints_solver {
+                            Some(
+                                plugin.system_setup(solve_volume_constraint::<LOCKING>, |system| {
+                                    system.name("solve_volume_constraint")
+                                }),
+                            )
+                        } else {
+                            None
+                        }
+                    })
             })
             .plugin_setup(|plugin| {
                 plugin
@@ -308,6 +408,18 @@ impl<const LOCKING: bool> PhysicsPlugin<LOCKING> {
                         system.name("recalculate_velocities")
                     })
             })
-            .plugin_setup(|plugin| plugin.name("post_simulation"))
+            .plugin_setup(|plugin| {
+                plugin.name("post_simulation").maybe_setup(|plugin| {
+                    if install_interpolated_transforms {
+                        Some(
+                            plugin.system_setup(cache_interpolation_targets::<LOCKING>, |system| {
+                                system.name("cache_interpolation_targets")
+                            }),
+                        )
+                    } else {
+                        None
+                    }
+                })
+            })
     }
 }
@@ -1,2 +1,3 @@
 pub mod point;
+pub mod ray;
 pub mod shape;
@@ -0,0 +1,209 @@
+use crate::{
+    Scalar,
+    collisions::{CollisionProfile, DensityFieldSpatialExtractor},
+    components::BodyAccessInfo,
+    density_fields::{DensityField, DensityFieldBox},
+};
+use anput::{entity::Entity, world::World};
+use anput_spatial::{SpatialPartitioning, third_party::rstar::AABB};
+use std::cmp::Ordering;
+use vek::Vec3;
+
+/// Ray cast against density fields.
+///
+/// When casting through spatial partitioning, ray `profile` decides how
+/// each density field collision profile is treated: blocking fields stop the
+/// ray, tracing fields register a hit and let the ray continue, and fields
+/// matching only overlap channel (or none at all) are passed through.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RayQuery {
+    pub origin: Vec3<Scalar>,
+    pub direction: Vec3<Scalar>,
+    pub max_distance: Scalar,
+    pub step: Scalar,
+    pub density_threshold: Scalar,
+    pub profile: CollisionProfile,
+}
+
+impl Default for RayQuery {
+    fn default() -> Self {
+        Self {
+            origin: Default::default(),
+            direction: Vec3::unit_z(),
+            max_distance: 100.0,
+            step: 0.1,
+            density_threshold: 0.5,
+            profile: Default::default(),
+        }
+    }
+}
+
+impl RayQuery {
+    pub fn new(origin: Vec3<Scalar>, direction: Vec3<Scalar>, max_distance: Scalar) -> Self {
+        Self {
+            origin,
+            direction,
+            max_distance,
+            ..Default::default()
+        }
+    }
+
+    pub fn query_field(&self, field: &dyn DensityField, info: &BodyAccessInfo) -> Option<RayHit> {
+        let (point, normal) = field.raymarch(
+            self.origin,
+            self.direction,
+            self.max_distance,
+            self.step,
+            info,
+            self.density_threshold,
+        )?;
+        Some(RayHit {
+            density_field: Default::default(),
+            body: info.entity,
+            distance: self.origin.distance(point),
+            point,
+            density: field.density_at_point(point, info),
+            normal,
+            blocking: false,
+        })
+    }
+
+    /// Casts ray through density fields found in spatial partitioning,
+    /// returning hits sorted by distance, ending at first blocking hit.
+    pub fn cast<const LOCKING: bool>(
+        &self,
+        world: &World,
+        spatial: &SpatialPartitioning<DensityFieldSpatialExtractor>,
+    ) -> Vec<RayHit> {
+        let Some(direction) = self.direction.try_normalized() else {
+            return Default::default();
+        };
+        let end = self.origin + direction * self.max_distance;
+        let envelope = AABB::from_corners(
+            Vec3::partial_min(self.origin, end).into_array(),
+            Vec3::partial_max(self.origin, end).into_array(),
+        );
+        let mut lookup = world.lookup_access::<LOCKING, &DensityFieldBox>();
+        let mut hits = spatial
+            .tree()
+            .locate_in_envelope_intersecting(&envelope)
+            .filter_map(|geom| {
+                let object = geom.geom();
+                let blocking = self.profile.does_block(&object.collision_profile);
+                if !blocking && !self.profile.does_trace(&object.collision_profile) {
+                    return None;
+                }
+                let field = lookup.access(geom.data)?;
+                let info = BodyAccessInfo::of_world(object.body_entity, world);
+                let mut hit = self.query_field(&**field, &info)?;
+                hit.density_field = geom.data;
+                hit.blocking = blocking;
+                Some(hit)
+            })
+            .collect::<Vec<_>>();
+        hits.sort_by(|a, b| {
+            a.distance
+                .partial_cmp(&b.distance)
+                .unwrap_or(Ordering::Equal)
+        });
+        if let Some(index) = hits.iter().position(|hit| hit.blocking) {
+            hits.truncate(index + 1);
+        }
+        hits
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RayHit {
+    pub density_field: Entity,
+    pub body: Entity,
+    pub distance: Scalar,
+    pub point: Vec3<Scalar>,
+    pub density: Scalar,
+    pub normal: Vec3<Scalar>,
+    pub blocking: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        collisions::CollisionMask,
+        components::{
+            BodyDensityFieldRelation, BodyParentRelation, BodyParticleRelation, PhysicsBody,
+            PhysicsParticle, Position,
+        },
+        density_fields::sphere::SphereDensityField,
+    };
+
+    #[test]
+    fn test_ray_query_channels() {
+        let mut world = World::default();
+        let mut spawn = |x: Scalar, profile: CollisionProfile| {
+            let entity = world
+                .spawn((
+                    PhysicsBody,
+                    PhysicsParticle,
+                    Position::new(Vec3::new(x, 0.0, 0.0)),
+                    DensityFieldBox::new(SphereDensityField::<true>::new_hard(1.0, 1.0)),
+                    profile,
+                ))
+                .unwrap();
+            world
+                .relate::<true, _>(BodyParticleRelation, entity, entity)
+                .unwrap();
+            world
+                .relate::<true, _>(BodyDensityFieldRelation, entity, entity)
+                .unwrap();
+            world
+                .relate::<true, _>(BodyParentRelation, entity, entity)
+                .unwrap();
+            entity
+        };
+        let overlap = spawn(
+            3.0,
+            CollisionProfile::default().with_overlap(CollisionMask::flag(0)),
+        );
+        let trace = spawn(
+            6.0,
+            CollisionProfile::default().with_trace(CollisionMask::flag(0)),
+        );
+        let block = spawn(
+            9.0,
+            CollisionProfile::default().with_block(CollisionMask::flag(0)),
+        );
+        let behind = spawn(
+            12.0,
+            CollisionProfile::default().with_block(CollisionMask::flag(0)),
+        );
+
+        let mut spatial = SpatialPartitioning::<DensityFieldSpatialExtractor>::default();
+        spatial.rebuild::<true>(&world);
+
+        let query = RayQuery {
+            profile: CollisionProfile::default()
+                .with_block(CollisionMask::flag(0))
+                .with_trace(CollisionMask::flag(0)),
+            ..RayQuery::new(Vec3::zero(), Vec3::unit_x(), 20.0)
+        };
+        let hits = query.cast::<true>(&world, &spatial);
+        assert_eq!(
+            hits.iter()
+                .map(|hit| (hit.density_field, hit.blocking))
+                .collect::<Vec<_>>(),
+            vec![(trace, false), (block, true)]
+        );
+        assert!((hits[0].distance - 5.0).abs() < 0.2);
+        assert!((hits[1].distance - 8.0).abs() < 0.2);
+        assert!(hits.iter().all(|hit| hit.density_field != overlap));
+        assert!(hits.iter().all(|hit| hit.density_field != behind));
+
+        let query = RayQuery {
+            profile: CollisionProfile::default().with_trace(CollisionMask::flag(0)),
+            ..RayQuery::new(Vec3::zero(), Vec3::unit_x(), 20.0)
+        };
+        let hits = query.cast::<true>(&world, &spatial);
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].density_field, trace);
+    }
+}
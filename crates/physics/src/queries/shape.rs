@@ -13,6 +13,12 @@ pub struct ShapeOverlapQuery {
     pub voxelization_size_limit: Scalar,
     pub region_limit: Option<Aabb<Scalar>>,
     pub depth_limit: usize,
+    /// Density range separation below which region is considered converged
+    /// and won't be subdivided further.
+    pub density_convergence_tolerance: Scalar,
+    /// Resolves pairs of convex fields reporting support points with single
+    /// analytic cell instead of voxelizing their overlap region.
+    pub analytic_convex: bool,
 }
 
 impl Default for ShapeOverlapQuery {
@@ -22,6 +28,8 @@ impl Default for ShapeOverlapQuery {
             voxelization_size_limit: 1.0,
             region_limit: None,
             depth_limit: usize::MAX,
+            density_convergence_tolerance: 1.0e-3,
+            analytic_convex: false,
         }
     }
 }
@@ -43,6 +51,26 @@ impl ShapeOverlapQuery {
         result: &mut Vec<T>,
         converter: impl Fn(ShapeOverlapCell) -> T,
     ) -> Option<Aabb<Scalar>> {
+        self.query_field_pair_each(field, info, |cell| result.push(converter(cell)))
+    }
+
+    /// Streams overlapping cells into callback instead of collecting them,
+    /// so callers can fold cells without allocating storage for all of them.
+    pub fn query_field_pair_each(
+        &self,
+        field: [&dyn DensityField; 2],
+        info: [&BodyAccessInfo; 2],
+        mut f: impl FnMut(ShapeOverlapCell),
+    ) -> Option<Aabb<Scalar>> {
+        if self.analytic_convex
+            && field[0].is_convex()
+            && field[1].is_convex()
+            && let Some(result) = self.query_convex_field_pair(field, info)
+        {
+            let (region, cell) = result?;
+            f(cell);
+            return Some(region);
+        }
         let mut a = field[0].aabb(info[0]);
         let mut b = field[1].aabb(info[1]);
         if let Some(region_limit) = self.region_limit {
@@ -61,7 +89,8 @@ impl ShapeOverlapQuery {
                 .size()
                 .into_iter()
                 .any(|v| v > self.voxelization_size_limit)
-                && (a.has_separation() || b.has_separation())
+                && (a.has_separation_within(self.density_convergence_tolerance)
+                    || b.has_separation_within(self.density_convergence_tolerance))
                 && depth < self.depth_limit
             {
                 stack.extend(aabb_cell_subdivide(region).map(|region| (region, depth + 1)));
@@ -79,14 +108,111 @@ impl ShapeOverlapQuery {
                 [true, false] => [-normal[1], normal[1]],
                 [false, true] => [normal[0], -normal[0]],
             };
-            result.push(converter(ShapeOverlapCell {
+            f(ShapeOverlapCell {
                 region,
                 density,
                 normal,
-            }));
+            });
         }
         Some(aabb)
     }
+
+    /// SAT-style narrowphase of convex fields, testing separation along
+    /// axis between fields centers, world axes, fields normals facing each
+    /// other, fields face axes and cross products of face axes of both fields
+    /// standing for their edges. Returns `None` when any field can't report
+    /// support points, and `Some(None)` when fields are separated.
+    ///
+    /// Reported cell spans penetration depth along contact normal, starting
+    /// at deepest point of one field inside the other.
+    fn query_convex_field_pair(
+        &self,
+        field: [&dyn DensityField; 2],
+        info: [&BodyAccessInfo; 2],
+    ) -> Option<Option<(Aabb<Scalar>, ShapeOverlapCell)>> {
+        let mut aabb = [0, 1].map(|index| field[index].aabb(info[index]));
+        if let Some(region_limit) = self.region_limit {
+            aabb = aabb.map(|aabb| aabb.intersection(region_limit));
+        }
+        let support = |index: usize, direction: Vec3<Scalar>| {
+            field[index].support_point(direction, info[index])
+        };
+        let center = aabb.map(|aabb| aabb.center());
+        let mut axes = vec![
+            center[1] - center[0],
+            Vec3::unit_x(),
+            Vec3::unit_y(),
+            Vec3::unit_z(),
+        ];
+        for index in 0..2 {
+            let other = 1 - index;
+            let toward = center[index] - center[other];
+            axes.push(field[index].normal_at_point(
+                support(other, toward)?,
+                Vec3::broadcast(self.voxelization_size_limit),
+                info[index],
+            ));
+        }
+        let face_axes = [0, 1].map(|index| field[index].face_axes(info[index]));
+        for a in &face_axes[0] {
+            for b in &face_axes[1] {
+                axes.push(a.cross(*b));
+            }
+        }
+        axes.extend(face_axes.into_iter().flatten());
+
+        let mut contact = None::<(Scalar, Vec3<Scalar>)>;
+        for axis in axes {
+            let Some(mut axis) = axis.try_normalized() else {
+                continue;
+            };
+            let [min_a, max_a, min_b, max_b] = [(0, -axis), (0, axis), (1, -axis), (1, axis)]
+                .map(|(index, direction)| support(index, direction).map(|point| point.dot(axis)));
+            let depth = max_a?.min(max_b?) - min_a?.max(min_b?);
+            if depth <= 0.0 {
+                return Some(None);
+            }
+            if (center[1] - center[0]).dot(axis) < 0.0 {
+                axis = -axis;
+            }
+            if contact
+                .map(|(min_depth, _)| depth < min_depth)
+                .unwrap_or(true)
+            {
+                contact = Some((depth, axis));
+            }
+        }
+        let (depth, normal) = contact?;
+
+        let overlap_region = aabb[0].intersection(aabb[1]);
+        let overlap_center = overlap_region.center();
+        let deepest_b = support(1, -normal)?;
+        let deepest_a = support(0, normal)?;
+        let region = [
+            (deepest_b, deepest_b + normal * depth),
+            (deepest_a, deepest_a - normal * depth),
+        ]
+        .map(|(from, to)| Aabb::new_empty(from).expanded_to_contain_point(to))
+        .into_iter()
+        .min_by(|a, b| {
+            a.center()
+                .distance_squared(overlap_center)
+                .partial_cmp(&b.center().distance_squared(overlap_center))
+                .unwrap_or(Ordering::Equal)
+        })?;
+        let density = [0, 1].map(|index| field[index].density_at_region(region, info[index]));
+        if density[0].max.min(density[1].max) <= self.density_threshold {
+            return Some(None);
+        }
+        Some(Some((
+            overlap_region,
+            ShapeOverlapCell {
+                region,
+                density,
+                normal: [normal, -normal],
+            },
+        )))
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -97,6 +223,64 @@ pub struct ShapeOverlapCell {
 }
 
 impl ShapeOverlapCell {
+    /// Total ordering by region corners, stable regardless of cells order.
+    pub fn stable_cmp(&self, other: &Self) -> Ordering {
+        self.region
+            .min
+            .into_iter()
+            .chain(self.region.max)
+            .zip(other.region.min.into_iter().chain(other.region.max))
+            .map(|(a, b)| a.total_cmp(&b))
+            .find(|ordering| ordering.is_ne())
+            .unwrap_or(Ordering::Equal)
+    }
+
+    /// Region corners, where bits 0, 1 and 2 of corner index select max
+    /// instead of min along X, Y and Z axis respectively.
+    pub fn corners(&self) -> [Vec3<Scalar>; 8] {
+        std::array::from_fn(|index| {
+            Vec3::new(
+                if index & 1 == 0 {
+                    self.region.min.x
+                } else {
+                    self.region.max.x
+                },
+                if index & 2 == 0 {
+                    self.region.min.y
+                } else {
+                    self.region.max.y
+                },
+                if index & 4 == 0 {
+                    self.region.min.z
+                } else {
+                    self.region.max.z
+                },
+            )
+        })
+    }
+
+    /// Region faces in -X, +X, -Y, +Y, -Z, +Z order, each wound
+    /// counter-clockwise when looking at region from outside.
+    pub fn faces(&self) -> [[Vec3<Scalar>; 4]; 6] {
+        let corners = self.corners();
+        [
+            [0, 4, 6, 2],
+            [1, 3, 7, 5],
+            [0, 1, 5, 4],
+            [2, 6, 7, 3],
+            [0, 2, 3, 1],
+            [4, 5, 7, 6],
+        ]
+        .map(|face| face.map(|index| corners[index]))
+    }
+
+    /// Region faces split into triangles, keeping faces winding.
+    pub fn triangles(&self) -> impl Iterator<Item = [Vec3<Scalar>; 3]> + '_ {
+        self.faces()
+            .into_iter()
+            .flat_map(|[a, b, c, d]| [[a, b, c], [a, c, d]])
+    }
+
     pub fn area(&self) -> Scalar {
         self.region
             .size()
@@ -144,11 +328,48 @@ mod tests {
     use crate::{
         components::{
             BodyDensityFieldRelation, BodyParentRelation, BodyParticleRelation, PhysicsBody,
-            PhysicsParticle, Position,
+            PhysicsParticle, Position, Rotation,
+        },
+        density_fields::{
+            DensityFieldBox, aabb::AabbDensityField, cube::CubeDensityField,
+            sphere::SphereDensityField,
         },
-        density_fields::{DensityFieldBox, aabb::AabbDensityField, sphere::SphereDensityField},
     };
     use anput::world::World;
+    use vek::Quaternion;
+
+    #[test]
+    fn test_shape_overlap_cell_geometry() {
+        let cell = ShapeOverlapCell {
+            region: Aabb {
+                min: Vec3::zero(),
+                max: Vec3::one(),
+            },
+            density: Default::default(),
+            normal: Default::default(),
+        };
+
+        assert_eq!(
+            cell.corners(),
+            [
+                Vec3::new(0.0, 0.0, 0.0),
+                Vec3::new(1.0, 0.0, 0.0),
+                Vec3::new(0.0, 1.0, 0.0),
+                Vec3::new(1.0, 1.0, 0.0),
+                Vec3::new(0.0, 0.0, 1.0),
+                Vec3::new(1.0, 0.0, 1.0),
+                Vec3::new(0.0, 1.0, 1.0),
+                Vec3::new(1.0, 1.0, 1.0),
+            ]
+        );
+
+        let center = cell.region.center();
+        for [a, b, c, _] in cell.faces() {
+            let normal = (b - a).cross(c - b);
+            assert!(normal.dot(a - center) > 0.0);
+        }
+        assert_eq!(cell.triangles().count(), 12);
+    }
 
     #[test]
     fn test_aabb() {
@@ -388,7 +609,6 @@ mod tests {
         let field_a = world
             .entity::<true, &DensityFieldBox>(a)
             .unwrap()
-            .as_any()
             .downcast_ref::<AabbDensityField>()
             .unwrap();
         let info_a = BodyAccessInfo::of_world(a, &world);
@@ -396,7 +616,6 @@ mod tests {
         let field_b = world
             .entity::<true, &DensityFieldBox>(b)
             .unwrap()
-            .as_any()
             .downcast_ref::<SphereDensityField<true>>()
             .unwrap();
         let info_b = BodyAccessInfo::of_world(b, &world);
@@ -534,4 +753,255 @@ mod tests {
             ],
         );
     }
+
+    #[test]
+    fn test_shape_overlap_query_convergence_tolerance() {
+        let mut world = World::default();
+
+        let a = world
+            .spawn((
+                PhysicsBody,
+                DensityFieldBox::new(AabbDensityField {
+                    aabb: Aabb {
+                        min: Vec3::new(-2.0, -2.0, 0.0),
+                        max: Vec3::new(2.0, 2.0, 0.0),
+                    },
+                    density: 1.0,
+                }),
+            ))
+            .unwrap();
+        world
+            .relate::<true, _>(BodyDensityFieldRelation, a, a)
+            .unwrap();
+        world.relate::<true, _>(BodyParentRelation, a, a).unwrap();
+
+        let b = world
+            .spawn((
+                PhysicsBody,
+                PhysicsParticle,
+                Position::new(Vec3::new(0.0, 0.0, 0.0)),
+                DensityFieldBox::new(SphereDensityField::<true>::new_soft(1.0, 1.0)),
+            ))
+            .unwrap();
+        world.relate::<true, _>(BodyParticleRelation, b, b).unwrap();
+        world
+            .relate::<true, _>(BodyDensityFieldRelation, b, b)
+            .unwrap();
+        world.relate::<true, _>(BodyParentRelation, b, b).unwrap();
+
+        let field_a = &**world.entity::<true, &DensityFieldBox>(a).unwrap();
+        let info_a = BodyAccessInfo::of_world(a, &world);
+        let field_b = &**world.entity::<true, &DensityFieldBox>(b).unwrap();
+        let info_b = BodyAccessInfo::of_world(b, &world);
+
+        let count = |tolerance: Scalar| {
+            let mut cells = vec![];
+            ShapeOverlapQuery {
+                density_threshold: 0.1,
+                voxelization_size_limit: 0.05,
+                density_convergence_tolerance: tolerance,
+                ..Default::default()
+            }
+            .query_field_pair([field_a, field_b], [&info_a, &info_b], &mut cells);
+            cells.len()
+        };
+
+        let tight = count(1.0e-3);
+        let loose = count(0.5);
+        assert!(loose > 0);
+        assert!(loose < tight);
+    }
+
+    #[test]
+    fn test_shape_overlap_query_analytic_convex() {
+        let mut world = World::default();
+        let mut spawn = |position: Vec3<Scalar>| {
+            let entity = world
+                .spawn((
+                    PhysicsBody,
+                    PhysicsParticle,
+                    Position::new(position),
+                    DensityFieldBox::new(SphereDensityField::<true>::new_hard(1.0, 10.0)),
+                ))
+                .unwrap();
+            world
+                .relate::<true, _>(BodyParticleRelation, entity, entity)
+                .unwrap();
+            world
+                .relate::<true, _>(BodyDensityFieldRelation, entity, entity)
+                .unwrap();
+            world
+                .relate::<true, _>(BodyParentRelation, entity, entity)
+                .unwrap();
+            entity
+        };
+        let a = spawn(Vec3::new(0.0, 0.0, 0.0));
+        let b = spawn(Vec3::new(15.0, 0.0, 0.0));
+        let c = spawn(Vec3::new(15.0, 15.0, 15.0));
+
+        let fields =
+            [a, b, c].map(|entity| &**world.entity::<true, &DensityFieldBox>(entity).unwrap());
+        let infos = [a, b, c].map(|entity| BodyAccessInfo::of_world(entity, &world));
+        assert!(fields.iter().all(|field| field.is_convex()));
+        let query = |analytic_convex: bool, [first, second]: [usize; 2]| {
+            let mut cells = vec![];
+            ShapeOverlapQuery {
+                analytic_convex,
+                ..Default::default()
+            }
+            .query_field_pair(
+                [fields[first], fields[second]],
+                [&infos[first], &infos[second]],
+                &mut cells,
+            );
+            cells
+        };
+
+        let analytic = query(true, [0, 1]);
+        let voxelized = query(false, [0, 1]);
+        assert_eq!(analytic.len(), 1);
+        assert!(voxelized.len() > 1);
+        let normal = voxelized
+            .iter()
+            .map(|cell| cell.normal[0])
+            .sum::<Vec3<Scalar>>()
+            .normalized();
+        assert!(analytic[0].normal[0].distance(normal) < 1.0e-3);
+        assert!(analytic[0].normal[0].distance(Vec3::unit_x()) < 1.0e-3);
+        assert!((analytic[0].region.size().w - 5.0).abs() < 1.0e-3);
+        assert!(
+            voxelized
+                .iter()
+                .all(|cell| analytic[0].region.center().distance(cell.region.center()) < 10.0)
+        );
+
+        // bounding boxes overlap, but spheres don't.
+        assert!(query(true, [0, 2]).is_empty());
+        assert!(query(false, [0, 2]).is_empty());
+    }
+
+    #[test]
+    fn test_shape_overlap_query_analytic_convex_rotated_cubes() {
+        let mut world = World::default();
+        let mut spawn = |position: Vec3<Scalar>, rotation: Quaternion<Scalar>| {
+            let entity = world
+                .spawn((
+                    PhysicsBody,
+                    PhysicsParticle,
+                    Position::new(position),
+                    Rotation::new(rotation),
+                    DensityFieldBox::new(CubeDensityField::<true>::new_hard(1.0, Vec3::one())),
+                ))
+                .unwrap();
+            world
+                .relate::<true, _>(BodyParticleRelation, entity, entity)
+                .unwrap();
+            world
+                .relate::<true, _>(BodyDensityFieldRelation, entity, entity)
+                .unwrap();
+            world
+                .relate::<true, _>(BodyParentRelation, entity, entity)
+                .unwrap();
+            entity
+        };
+        let angle = std::f32::consts::FRAC_PI_4 as Scalar;
+        let a = spawn(Vec3::zero(), Quaternion::rotation_x(angle));
+        // separated along rotated face axis.
+        let b = spawn(Vec3::new(-1.5, -1.5, 1.5), Quaternion::rotation_x(angle));
+        // overlapping, rotated around other axis.
+        let c = spawn(Vec3::new(-1.25, -1.25, 1.25), Quaternion::rotation_y(angle));
+        let d = spawn(Vec3::zero(), Quaternion::rotation_z(angle));
+        // separated along cross product of both cubes edges.
+        let e = spawn(Vec3::new(2.5, 1.0, 1.5), Quaternion::rotation_y(angle));
+
+        let fields = [a, b, c, d, e]
+            .map(|entity| &**world.entity::<true, &DensityFieldBox>(entity).unwrap());
+        let infos = [a, b, c, d, e].map(|entity| BodyAccessInfo::of_world(entity, &world));
+        let query = ShapeOverlapQuery {
+            analytic_convex: true,
+            ..Default::default()
+        };
+        let test = |[first, second]: [usize; 2]| {
+            let field = [fields[first], fields[second]];
+            let info = [&infos[first], &infos[second]];
+            let aabb = [0, 1].map(|index| field[index].aabb(info[index]));
+            assert!(aabb[0].collides_with_aabb(aabb[1]));
+            let mut cells = vec![];
+            query.query_field_pair(field, info, &mut cells);
+            (
+                query
+                    .query_convex_field_pair(field, info)
+                    .unwrap()
+                    .is_some(),
+                cells.len(),
+            )
+        };
+
+        assert_eq!(test([0, 1]), (false, 0));
+        assert_eq!(test([0, 2]), (true, 1));
+        assert_eq!(test([3, 4]), (false, 0));
+    }
+
+    #[test]
+    fn test_shape_overlap_query_each() {
+        let mut world = World::default();
+
+        let a = world
+            .spawn((
+                PhysicsBody,
+                DensityFieldBox::new(AabbDensityField {
+                    aabb: Aabb {
+                        min: Vec3::new(-2.0, -2.0, 0.0),
+                        max: Vec3::new(0.0, 0.0, 0.0),
+                    },
+                    density: 1.0,
+                }),
+            ))
+            .unwrap();
+        world
+            .relate::<true, _>(BodyDensityFieldRelation, a, a)
+            .unwrap();
+        world.relate::<true, _>(BodyParentRelation, a, a).unwrap();
+
+        let b = world
+            .spawn((
+                PhysicsBody,
+                PhysicsParticle,
+                Position::new(Vec3::new(0.0, 0.0, 0.0)),
+                DensityFieldBox::new(SphereDensityField::<true>::new_soft(1.0, 1.0)),
+            ))
+            .unwrap();
+        world.relate::<true, _>(BodyParticleRelation, b, b).unwrap();
+        world
+            .relate::<true, _>(BodyDensityFieldRelation, b, b)
+            .unwrap();
+        world.relate::<true, _>(BodyParentRelation, b, b).unwrap();
+
+        let field_a = &**world.entity::<true, &DensityFieldBox>(a).unwrap();
+        let info_a = BodyAccessInfo::of_world(a, &world);
+        let field_b = &**world.entity::<true, &DensityFieldBox>(b).unwrap();
+        let info_b = BodyAccessInfo::of_world(b, &world);
+
+        let query = ShapeOverlapQuery {
+            density_threshold: 0.1,
+            voxelization_size_limit: 0.1,
+            ..Default::default()
+        };
+
+        let mut cells = vec![];
+        let aabb = query.query_field_pair([field_a, field_b], [&info_a, &info_b], &mut cells);
+
+        let mut streamed = vec![];
+        let mut area = 0.0;
+        let streamed_aabb =
+            query.query_field_pair_each([field_a, field_b], [&info_a, &info_b], |cell| {
+                area += cell.area();
+                streamed.push(cell);
+            });
+
+        assert!(!cells.is_empty());
+        assert_eq!(aabb, streamed_aabb);
+        assert_eq!(cells, streamed);
+        assert_eq!(area, cells.iter().map(|cell| cell.area()).sum::<Scalar>());
+    }
 }
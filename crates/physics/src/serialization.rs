@@ -0,0 +1,545 @@
+use crate::{
+    PhysicsSimulation,
+    collisions::{CollisionProfile, ContactDetection, DensityFieldAabbCache},
+    components::{
+        AngularVelocity, BodyDensityFieldRelation, BodyMaterial, BodyParentRelation,
+        BodyParticleRelation, DragField, ExternalForces, Gravity, InterpolatedTransform, Kinematic,
+        LinearVelocity, Mass, ParticleConstraintRelation, ParticleMaterial, PhysicsBody,
+        PhysicsParticle, Position, Rotation,
+    },
+    constraints::{
+        distance::DistanceConstraint,
+        volume::{VolumeConstraint, VolumeConstraintParticleRelation},
+    },
+    density_fields::{
+        DensityField, DensityFieldBox, aabb::AabbDensityField, addition::AdditionDensityField,
+        cube::CubeDensityField, material::MaterialDensityField,
+        multiplication::MultiplicationDensityField, sphere::SphereDensityField,
+        subtraction::SubtractionDensityField,
+    },
+};
+use anput::{
+    component::Component,
+    prefab::Prefab,
+    processor::WorldProcessor,
+    resources::Resources,
+    systems::Systems,
+    third_party::{
+        intuicio_core::{registry::Registry, types::struct_type::NativeStructBuilder},
+        intuicio_framework_serde::{
+            Intermediate, SerializationRegistry, from_intermediate, to_intermediate,
+        },
+    },
+    universe::Plugin,
+    world::{Relation, World},
+};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use std::{
+    any::{Any, TypeId},
+    collections::HashMap,
+    error::Error,
+    sync::Arc,
+};
+
+type DensityFieldSerializer = Box<
+    dyn Fn(&dyn Any, &DensityFieldSerializationRegistry) -> Result<Intermediate, Box<dyn Error>>
+        + Send
+        + Sync,
+>;
+
+type DensityFieldDeserializer = Box<
+    dyn Fn(
+            &Intermediate,
+            &DensityFieldSerializationRegistry,
+        ) -> Result<DensityFieldBox, Box<dyn Error>>
+        + Send
+        + Sync,
+>;
+
+#[derive(Serialize, Deserialize)]
+struct SerializedDensityField {
+    type_name: String,
+    data: Intermediate,
+}
+
+/// Registry of density field types, used to (de)serialize [`DensityFieldBox`]
+/// by name of its field type.
+///
+/// Fields wrapping other fields (de)serialize them through this registry too,
+/// so any registered field can be nested in them.
+#[derive(Default)]
+pub struct DensityFieldSerializationRegistry {
+    serializers: HashMap<TypeId, (String, DensityFieldSerializer)>,
+    deserializers: HashMap<String, DensityFieldDeserializer>,
+}
+
+impl DensityFieldSerializationRegistry {
+    pub fn with_basic_fields(mut self) -> Self {
+        self.register_serde::<AabbDensityField>();
+        self.register_serde::<CubeDensityField<true>>();
+        self.register_serde::<CubeDensityField<false>>();
+        self.register_serde::<SphereDensityField<true>>();
+        self.register_serde::<SphereDensityField<false>>();
+        self.register::<AdditionDensityField>(
+            |field, registry| registry.serialize_many(&field.fields),
+            |value, registry| {
+                Ok(AdditionDensityField {
+                    fields: registry.deserialize_many(value)?,
+                })
+            },
+        );
+        self.register::<SubtractionDensityField>(
+            |field, registry| registry.serialize_many(&field.fields),
+            |value, registry| {
+                Ok(SubtractionDensityField {
+                    fields: registry.deserialize_many(value)?,
+                })
+            },
+        );
+        self.register::<MultiplicationDensityField>(
+            |field, registry| registry.serialize_many(&field.fields),
+            |value, registry| {
+                Ok(MultiplicationDensityField {
+                    fields: registry.deserialize_many(value)?,
+                })
+            },
+        );
+        self.register::<MaterialDensityField>(
+            |field, registry| {
+                Ok(Intermediate::Tuple(vec![
+                    registry.serialize(&field.field)?,
+                    to_intermediate(&field.material)?,
+                ]))
+            },
+            |value, registry| {
+                let (Intermediate::Tuple(tuple) | Intermediate::Seq(tuple)) = value else {
+                    return Err("Expected intermediate tuple".into());
+                };
+                if tuple.len() != 2 {
+                    return Err("Expected tuple to have 2 items".into());
+                }
+                Ok(MaterialDensityField {
+                    field: registry.deserialize(&tuple[0])?,
+                    material: from_intermediate(&tuple[1])?,
+                })
+            },
+        );
+        self
+    }
+
+    pub fn with_serde<T: DensityField + Serialize + DeserializeOwned>(mut self) -> Self {
+        self.register_serde::<T>();
+        self
+    }
+
+    pub fn with<T: DensityField>(
+        mut self,
+        serialize: impl Fn(&T, &Self) -> Result<Intermediate, Box<dyn Error>> + Send + Sync + 'static,
+        deserialize: impl Fn(&Intermediate, &Self) -> Result<T, Box<dyn Error>> + Send + Sync + 'static,
+    ) -> Self {
+        self.register(serialize, deserialize);
+        self
+    }
+
+    pub fn register_serde<T: DensityField + Serialize + DeserializeOwned>(&mut self) {
+        self.register::<T>(
+            |field, _| Ok(to_intermediate(field)?),
+            |value, _| Ok(from_intermediate(value)?),
+        );
+    }
+
+    /// Registers field type under its type name.
+    pub fn register<T: DensityField>(
+        &mut self,
+        serialize: impl Fn(&T, &Self) -> Result<Intermediate, Box<dyn Error>> + Send + Sync + 'static,
+        deserialize: impl Fn(&Intermediate, &Self) -> Result<T, Box<dyn Error>> + Send + Sync + 'static,
+    ) {
+        let type_name = std::any::type_name::<T>().to_owned();
+        self.serializers.insert(
+            TypeId::of::<T>(),
+            (
+                type_name.clone(),
+                Box::new(move |field, registry| {
+                    serialize(field.downcast_ref::<T>().unwrap(), registry)
+                }),
+            ),
+        );
+        self.deserializers.insert(
+            type_name,
+            Box::new(move |value, registry| {
+                Ok(DensityFieldBox::new(deserialize(value, registry)?))
+            }),
+        );
+    }
+
+    pub fn unregister<T: DensityField>(&mut self) {
+        if let Some((type_name, _)) = self.serializers.remove(&TypeId::of::<T>()) {
+            self.deserializers.remove(&type_name);
+        }
+    }
+
+    pub fn serialize(&self, field: &DensityFieldBox) -> Result<Intermediate, Box<dyn Error>> {
+        let field = field.as_any();
+        let (type_name, serializer) = self
+            .serializers
+            .get(&field.type_id())
+            .ok_or("Density field type does not exist in serialization registry")?;
+        Ok(to_intermediate(&SerializedDensityField {
+            type_name: type_name.to_owned(),
+            data: serializer(field, self)?,
+        })?)
+    }
+
+    pub fn deserialize(&self, value: &Intermediate) -> Result<DensityFieldBox, Box<dyn Error>> {
+        let value = from_intermediate::<SerializedDensityField>(value)?;
+        let deserializer = self.deserializers.get(&value.type_name).ok_or_else(|| {
+            format!(
+                "Density field type does not exist in serialization registry: {}",
+                value.type_name
+            )
+        })?;
+        deserializer(&value.data, self)
+    }
+
+    pub fn serialize_many(
+        &self,
+        fields: &[DensityFieldBox],
+    ) -> Result<Intermediate, Box<dyn Error>> {
+        Ok(Intermediate::Seq(
+            fields
+                .iter()
+                .map(|field| self.serialize(field))
+                .collect::<Result<_, _>>()?,
+        ))
+    }
+
+    pub fn deserialize_many(
+        &self,
+        value: &Intermediate,
+    ) -> Result<Vec<DensityFieldBox>, Box<dyn Error>> {
+        let Intermediate::Seq(items) = value else {
+            return Err("Expected intermediate sequence".into());
+        };
+        items.iter().map(|item| self.deserialize(item)).collect()
+    }
+
+    /// Registers [`DensityFieldBox`] to serialization registry, using this
+    /// registry to (de)serialize its field.
+    pub fn install_to_serialization(self, serialization: &mut SerializationRegistry) {
+        let registry = Arc::new(self);
+        let registry_de = registry.clone();
+        serialization.register::<DensityFieldBox>(
+            move |data, _, _| registry.serialize(data),
+            move |data, value, _, initialized, _| {
+                let field = registry_de.deserialize(value)?;
+                if initialized {
+                    *data = field;
+                } else {
+                    unsafe { (data as *mut DensityFieldBox).write(field) };
+                }
+                Ok(())
+            },
+        );
+    }
+}
+
+/// Registers physics components, relations and resources to universe type,
+/// serialization and processor registries, so whole physics universe can be
+/// stored with [`anput::prefab::UniversePrefab`].
+///
+/// Installed as plugin it uses registries of universe basics, so these have to
+/// be installed first - otherwise it does nothing. Transient state, like
+/// contacts and density field AABB caches, is not stored.
+pub struct PhysicsSerializationPlugin {
+    density_fields: DensityFieldSerializationRegistry,
+}
+
+impl Default for PhysicsSerializationPlugin {
+    fn default() -> Self {
+        Self {
+            density_fields: DensityFieldSerializationRegistry::default().with_basic_fields(),
+        }
+    }
+}
+
+impl PhysicsSerializationPlugin {
+    pub fn density_fields(mut self, registry: DensityFieldSerializationRegistry) -> Self {
+        self.density_fields = registry;
+        self
+    }
+
+    pub fn install_to(
+        self,
+        registry: &mut Registry,
+        serialization: &mut SerializationRegistry,
+        processor: &mut WorldProcessor,
+    ) {
+        Self::register_serde::<PhysicsBody>(registry, serialization);
+        Self::register_serde::<PhysicsParticle>(registry, serialization);
+        Self::register_serde::<Kinematic>(registry, serialization);
+        Self::register_serde::<Mass>(registry, serialization);
+        Self::register_serde::<Position>(registry, serialization);
+        Self::register_serde::<Rotation>(registry, serialization);
+        Self::register_serde::<InterpolatedTransform>(registry, serialization);
+        Self::register_serde::<LinearVelocity>(registry, serialization);
+        Self::register_serde::<AngularVelocity>(registry, serialization);
+        Self::register_serde::<ExternalForces>(registry, serialization);
+        Self::register_serde::<Gravity>(registry, serialization);
+        Self::register_serde::<DragField>(registry, serialization);
+        Self::register_serde::<BodyMaterial>(registry, serialization);
+        Self::register_serde::<ParticleMaterial>(registry, serialization);
+        Self::register_serde::<CollisionProfile>(registry, serialization);
+        Self::register_serde::<ContactDetection>(registry, serialization);
+        Self::register_serde::<PhysicsSimulation>(registry, serialization);
+        registry.add_type(NativeStructBuilder::new_uninitialized::<VolumeConstraint>().build());
+        serialization.register_serde::<VolumeConstraint>();
+        registry.add_type(NativeStructBuilder::new_uninitialized::<DensityFieldBox>().build());
+        self.density_fields.install_to_serialization(serialization);
+        registry.add_type(NativeStructBuilder::new::<DensityFieldAabbCache>().build());
+        serialization.register::<DensityFieldAabbCache>(
+            |_, _, _| Ok(Intermediate::Unit),
+            |data, _, _, initialized, _| {
+                if initialized {
+                    data.invalidate();
+                } else {
+                    unsafe { (data as *mut DensityFieldAabbCache).write(Default::default()) };
+                }
+                Ok(())
+            },
+        );
+        Self::register_relation::<BodyParentRelation>(registry, serialization, processor);
+        Self::register_relation::<BodyParticleRelation>(registry, serialization, processor);
+        Self::register_relation::<BodyDensityFieldRelation>(registry, serialization, processor);
+        Self::register_relation::<ParticleConstraintRelation>(registry, serialization, processor);
+        Self::register_relation::<DistanceConstraint>(registry, serialization, processor);
+        Self::register_relation::<VolumeConstraintParticleRelation>(
+            registry,
+            serialization,
+            processor,
+        );
+    }
+
+    fn register_serde<T: Component + Default + Serialize + DeserializeOwned>(
+        registry: &mut Registry,
+        serialization: &mut SerializationRegistry,
+    ) {
+        registry.add_type(NativeStructBuilder::new::<T>().build());
+        serialization.register_serde::<T>();
+    }
+
+    fn register_relation<T: Component + Serialize + DeserializeOwned>(
+        registry: &mut Registry,
+        serialization: &mut SerializationRegistry,
+        processor: &mut WorldProcessor,
+    ) {
+        Relation::<T>::install_to_registry(registry);
+        Prefab::register_relation_serializer::<T>(serialization);
+        Relation::<T>::register_to_processor(processor);
+    }
+}
+
+impl Plugin for PhysicsSerializationPlugin {
+    fn install(self, _: &mut World, _: &mut Systems, resources: &mut Resources) {
+        if let (Ok(mut registry), Ok(mut serialization), Ok(mut processor)) = (
+            resources.get_mut::<true, Registry>(),
+            resources.get_mut::<true, SerializationRegistry>(),
+            resources.get_mut::<true, WorldProcessor>(),
+        ) {
+            self.install_to(&mut registry, &mut serialization, &mut processor);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{PhysicsPlugin, Scalar, collisions::CollisionMask, third_party::vek::Vec3};
+    use anput::{
+        bundle::Bundle,
+        entity::Entity,
+        prefab::UniversePrefab,
+        scheduler::GraphScheduler,
+        third_party::{intuicio_framework_serde, moirai::jobs::Jobs},
+        universe::Universe,
+    };
+    use vek::Aabb;
+
+    fn make() -> Universe {
+        Universe::default()
+            .with_basics(1024, 1024)
+            .unwrap()
+            .with_plugin(PhysicsSerializationPlugin::default())
+            .with_plugin(
+                PhysicsPlugin::<true>::default()
+                    .simulation(PhysicsSimulation {
+                        delta_time: 0.5,
+                        gravity: Vec3::new(0.0, -10.0, 0.0),
+                        deterministic_accumulation: true,
+                        ..Default::default()
+                    })
+                    .make(),
+            )
+    }
+
+    fn spawn_body(world: &mut World, bundle: impl Bundle) -> Result<Entity, Box<dyn Error>> {
+        let entity = world.spawn(bundle)?;
+        if world.has_entity_component::<PhysicsParticle>(entity) {
+            world.relate::<true, _>(BodyParticleRelation, entity, entity)?;
+        }
+        world.relate::<true, _>(BodyDensityFieldRelation, entity, entity)?;
+        world.relate::<true, _>(BodyParentRelation, entity, entity)?;
+        Ok(entity)
+    }
+
+    fn state(universe: &Universe, entities: &[Entity]) -> Vec<(Vec3<Scalar>, Vec3<Scalar>)> {
+        entities
+            .iter()
+            .map(|entity| {
+                (
+                    universe
+                        .simulation
+                        .component::<true, Position>(*entity)
+                        .unwrap()
+                        .current,
+                    universe
+                        .simulation
+                        .component::<true, LinearVelocity>(*entity)
+                        .unwrap()
+                        .value,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_density_field_serialization() -> Result<(), Box<dyn Error>> {
+        let registry = DensityFieldSerializationRegistry::default().with_basic_fields();
+        let field = DensityFieldBox::new(MaterialDensityField {
+            field: DensityFieldBox::new(SubtractionDensityField {
+                fields: vec![
+                    DensityFieldBox::new(CubeDensityField::<true>::new_hard(1.0, Vec3::one())),
+                    DensityFieldBox::new(SphereDensityField::<false>::new_hard(1.0, 0.5)),
+                ],
+            }),
+            material: BodyMaterial {
+                friction: 0.25,
+                restitution: 0.75,
+            },
+        });
+
+        let field = registry.deserialize(&registry.serialize(&field)?)?;
+        let field = field.downcast_ref::<MaterialDensityField>().unwrap();
+        assert_eq!(field.material.friction, 0.25);
+        assert_eq!(field.material.restitution, 0.75);
+        let field = field
+            .field
+            .downcast_ref::<SubtractionDensityField>()
+            .unwrap();
+        assert!(
+            field.fields[0]
+                .downcast_ref::<CubeDensityField<true>>()
+                .is_some()
+        );
+        assert!(
+            field.fields[1]
+                .downcast_ref::<SphereDensityField<false>>()
+                .is_some()
+        );
+
+        let registry = DensityFieldSerializationRegistry::default();
+        assert!(
+            registry
+                .serialize(&DensityFieldBox::new(AabbDensityField {
+                    aabb: Aabb::default(),
+                    density: 1.0,
+                }))
+                .is_err()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_universe_round_trip() -> Result<(), Box<dyn Error>> {
+        let jobs = Jobs::default();
+        let scheduler = GraphScheduler::<true>;
+        let mut universe = make();
+
+        spawn_body(
+            &mut universe.simulation,
+            (
+                PhysicsBody,
+                DensityFieldBox::new(AabbDensityField {
+                    aabb: Aabb {
+                        min: Vec3::new(-100.0, -100.0, -100.0),
+                        max: Vec3::new(100.0, 0.0, 100.0),
+                    },
+                    density: 1.0,
+                }),
+                CollisionProfile::default().with_block(CollisionMask::flag(0)),
+                ContactDetection::default(),
+            ),
+        )?;
+        let mut balls = Vec::new();
+        for (index, field) in [
+            DensityFieldBox::new(SphereDensityField::<true>::new_hard(1.0, 1.0)),
+            DensityFieldBox::new(MaterialDensityField {
+                field: DensityFieldBox::new(SphereDensityField::<true>::new_hard(1.0, 1.0)),
+                material: BodyMaterial {
+                    friction: 0.1,
+                    restitution: 0.5,
+                },
+            }),
+            DensityFieldBox::new(AdditionDensityField {
+                fields: vec![
+                    DensityFieldBox::new(SphereDensityField::<true>::new_hard(1.0, 1.0)),
+                    DensityFieldBox::new(CubeDensityField::<true>::new_hard(
+                        1.0,
+                        Vec3::new(0.5, 0.5, 0.5),
+                    )),
+                ],
+            }),
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            balls.push(spawn_body(
+                &mut universe.simulation,
+                (
+                    PhysicsBody,
+                    PhysicsParticle,
+                    field,
+                    CollisionProfile::default().with_block(CollisionMask::flag(0)),
+                    ContactDetection::default(),
+                    Mass::new(1.0),
+                    Position::new(Vec3::new(index as Scalar * 3.0, 2.0, 0.0)),
+                    LinearVelocity::default(),
+                    ExternalForces::default(),
+                ),
+            )?);
+        }
+        for _ in 0..3 {
+            scheduler.run(&jobs, &mut universe)?;
+        }
+
+        let prefab = UniversePrefab::from_universe::<true>(&universe)?;
+        let prefab = intuicio_framework_serde::from_str::<UniversePrefab>(
+            &intuicio_framework_serde::to_string_compact(&prefab)?,
+        )?;
+        let mut loaded = make();
+        let mappings = prefab.to_universe::<true>(&mut loaded)?;
+        let loaded_balls = balls
+            .iter()
+            .map(|entity| mappings[entity])
+            .collect::<Vec<_>>();
+        assert_eq!(state(&universe, &balls), state(&loaded, &loaded_balls));
+
+        for _ in 0..5 {
+            scheduler.run(&jobs, &mut universe)?;
+            scheduler.run(&jobs, &mut loaded)?;
+            assert_eq!(state(&universe, &balls), state(&loaded, &loaded_balls));
+        }
+
+        Ok(())
+    }
+}
@@ -1,12 +1,20 @@
 use crate::{
-    PhysicsSimulation, Scalar,
+    PhysicsAccessView, PhysicsSimulation, Scalar,
     components::{
-        AngularVelocity, ExternalForces, Gravity, LinearVelocity, Mass, ParticleMaterial, Position,
+        AngularVelocity, BodyAccessInfo, BodyParentRelation, DragField, ExternalForces, Gravity,
+        InterpolatedTransform, Kinematic, LinearVelocity, Mass, ParticleMaterial, Position,
         Rotation,
     },
+    density_fields::DensityFieldBox,
     utils::quat_from_axis_angle,
 };
-use anput::{query::Query, systems::SystemContext, universe::Res, world::World};
+use anput::{
+    entity::Entity,
+    query::{Exclude, Query},
+    systems::SystemContext,
+    universe::Res,
+    world::{Relation, World},
+};
 use std::error::Error;
 
 pub fn apply_external_forces<const LOCKING: bool>(
@@ -22,11 +30,18 @@ pub fn apply_external_forces<const LOCKING: bool>(
                 &Mass,
                 &mut LinearVelocity,
                 Option<&mut AngularVelocity>,
+                Option<&Kinematic>,
             ),
         >,
     )>()?;
 
-    for (external_forces, mass, linear_velocity, angular_velocity) in query.query(world) {
+    for (external_forces, mass, linear_velocity, angular_velocity, kinematic) in query.query(world)
+    {
+        if kinematic.is_some() {
+            external_forces.clear();
+            continue;
+        }
+
         linear_velocity.value += external_forces.force * mass.inverse() * simulation.delta_time;
         linear_velocity.value += external_forces.linear_impulse * mass.inverse();
 
@@ -95,6 +110,33 @@ pub fn cache_current_as_previous_state<const LOCKING: bool>(
     Ok(())
 }
 
+pub fn cache_interpolation_targets<const LOCKING: bool>(
+    context: SystemContext,
+) -> Result<(), Box<dyn Error>> {
+    let (world, query) = context.fetch::<(
+        &World,
+        Query<LOCKING, (&Position, Option<&Rotation>, &mut InterpolatedTransform)>,
+    )>()?;
+
+    for (position, rotation, transform) in query.query(world) {
+        transform.push_target(
+            position.current,
+            rotation
+                .map(|rotation| rotation.current)
+                .unwrap_or_default(),
+        );
+    }
+
+    Ok(())
+}
+
+pub fn interpolate_transforms<const LOCKING: bool>(world: &World, alpha: Scalar) {
+    let alpha = alpha.clamp(0.0, 1.0);
+    for transform in world.query::<LOCKING, &mut InterpolatedTransform>() {
+        transform.interpolate(alpha);
+    }
+}
+
 pub fn recalculate_velocities<const LOCKING: bool>(
     context: SystemContext,
 ) -> Result<(), Box<dyn Error>> {
@@ -132,10 +174,10 @@ pub fn apply_gravity<const LOCKING: bool>(context: SystemContext) -> Result<(),
     let (world, simulation, query) = context.fetch::<(
         &World,
         Res<LOCKING, &PhysicsSimulation>,
-        Query<LOCKING, (Option<&Gravity>, &mut ExternalForces)>,
+        Query<LOCKING, (Option<&Gravity>, &mut ExternalForces, Exclude<Kinematic>)>,
     )>()?;
 
-    for (gravity, external_forces) in query.query(world) {
+    for (gravity, external_forces, _) in query.query(world) {
         let gravity = gravity.map(|v| v.value).unwrap_or(simulation.gravity);
         external_forces.accumulate_linear_impulse(gravity * simulation.delta_time);
     }
@@ -143,13 +185,75 @@ pub fn apply_gravity<const LOCKING: bool>(context: SystemContext) -> Result<(),
     Ok(())
 }
 
+pub fn apply_drag_fields<const LOCKING: bool>(
+    context: SystemContext,
+) -> Result<(), Box<dyn Error>> {
+    let (world, simulation, field_query, particle_query) = context.fetch::<(
+        &World,
+        Res<LOCKING, &PhysicsSimulation>,
+        Query<
+            LOCKING,
+            (
+                Entity,
+                &DragField,
+                &DensityFieldBox,
+                Option<&Relation<BodyParentRelation>>,
+            ),
+        >,
+        Query<
+            LOCKING,
+            (
+                &Position,
+                &LinearVelocity,
+                &Mass,
+                &mut ExternalForces,
+                Exclude<Kinematic>,
+            ),
+        >,
+    )>()?;
+
+    let view = PhysicsAccessView::new(world);
+    let inverse_delta_time = simulation.inverse_delta_time();
+
+    for (position, linear_velocity, mass, external_forces, _) in particle_query.query(world) {
+        let speed = linear_velocity.value.magnitude();
+        if speed <= Scalar::EPSILON {
+            continue;
+        }
+        let mut magnitude = 0.0;
+        for (entity, drag, field, parent) in field_query.query(world) {
+            let body = parent
+                .and_then(|relation| relation.entities().next())
+                .unwrap_or(entity);
+            let info = BodyAccessInfo::new(body, view.clone());
+            let density = field.density_at_point(position.current, &info);
+            magnitude += drag.coefficient * density.max(0.0) * speed * speed;
+        }
+        // drag can stop particle within single step, but never reverse it.
+        let magnitude = magnitude.min(speed * mass.value() * inverse_delta_time);
+        if magnitude > Scalar::EPSILON {
+            external_forces.accumulate_force(-linear_velocity.value / speed * magnitude);
+        }
+    }
+
+    Ok(())
+}
+
 pub fn dampening_solver<const LOCKING: bool>(context: SystemContext) -> Result<(), Box<dyn Error>> {
     let (world, query) = context.fetch::<(
         &World,
-        Query<LOCKING, (&mut Position, Option<&mut Rotation>, &ParticleMaterial)>,
+        Query<
+            LOCKING,
+            (
+                &mut Position,
+                Option<&mut Rotation>,
+                &ParticleMaterial,
+                Exclude<Kinematic>,
+            ),
+        >,
     )>()?;
 
-    for (position, rotation, material) in query.query(world) {
+    for (position, rotation, material, _) in query.query(world) {
         let mut delta = position.change();
         delta *= material.linear_damping;
         if delta.magnitude_squared()
@@ -173,3 +277,175 @@ pub fn dampening_solver<const LOCKING: bool>(context: SystemContext) -> Result<(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        PhysicsPlugin,
+        collisions::{CollisionMask, CollisionProfile, ContactDetection},
+        components::{
+            BodyDensityFieldRelation, BodyParentRelation, BodyParticleRelation, PhysicsBody,
+            PhysicsParticle,
+        },
+        density_fields::{aabb::AabbDensityField, sphere::SphereDensityField},
+        utils::quat_from_axis_angle,
+    };
+    use anput::{scheduler::GraphScheduler, third_party::moirai::jobs::Jobs, universe::Universe};
+    use vek::{Aabb, Quaternion, Vec3};
+
+    #[test]
+    fn test_interpolated_transform() -> Result<(), Box<dyn Error>> {
+        let mut universe = Universe::default().with_plugin(
+            PhysicsPlugin::<true>::barebones()
+                .install_integrate_velocities(true)
+                .install_interpolated_transforms(true)
+                .make(),
+        );
+        let jobs = Jobs::default();
+        let scheduler = GraphScheduler::<true>;
+
+        let entity = universe.simulation.spawn((
+            Position::new(Vec3::zero()),
+            Rotation::new(Quaternion::identity()),
+            LinearVelocity::new(Vec3::new(20.0, 0.0, 0.0)),
+            AngularVelocity::new(Vec3::new(0.0, 10.0, 0.0)),
+            InterpolatedTransform::new(Vec3::zero(), Quaternion::identity()),
+        ))?;
+
+        scheduler.run(&jobs, &mut universe)?;
+        assert_eq!(
+            universe
+                .simulation
+                .component::<true, InterpolatedTransform>(entity)?
+                .position,
+            Vec3::zero()
+        );
+        interpolate_transforms::<true>(&universe.simulation, 0.5);
+
+        let transform = universe
+            .simulation
+            .component::<true, InterpolatedTransform>(entity)?;
+        let expected = quat_from_axis_angle(Vec3::unit_y(), 0.25);
+        assert!((transform.position - Vec3::new(0.5, 0.0, 0.0)).magnitude() < 1.0e-5);
+        assert!(transform.rotation.dot(expected).abs() > 1.0 - 1.0e-5);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_kinematic_body() -> Result<(), Box<dyn Error>> {
+        let mut universe = Universe::default().with_plugin(
+            PhysicsPlugin::<true>::default()
+                .simulation(PhysicsSimulation {
+                    delta_time: 1.0,
+                    gravity: Vec3::new(0.0, -1.0, 0.0),
+                    ..Default::default()
+                })
+                .make(),
+        );
+        let jobs = Jobs::default();
+        let scheduler = GraphScheduler::<true>;
+
+        let mut spawn = |kinematic: bool, position: Vec3<Scalar>, velocity: Vec3<Scalar>| {
+            let entity = universe.simulation.spawn((
+                PhysicsBody,
+                PhysicsParticle,
+                DensityFieldBox::new(SphereDensityField::<true>::new_hard(1.0, 10.0)),
+                CollisionProfile::default().with_block(CollisionMask::flag(0)),
+                ContactDetection {
+                    depth_limit: 0,
+                    ..Default::default()
+                },
+                Mass::new(1.0),
+                Position::new(position),
+                LinearVelocity { value: velocity },
+                ExternalForces::default(),
+            ))?;
+            if kinematic {
+                universe.simulation.insert(entity, (Kinematic,))?;
+            }
+            universe
+                .simulation
+                .relate::<true, _>(BodyParticleRelation, entity, entity)?;
+            universe
+                .simulation
+                .relate::<true, _>(BodyDensityFieldRelation, entity, entity)?;
+            universe
+                .simulation
+                .relate::<true, _>(BodyParentRelation, entity, entity)?;
+            Ok::<_, Box<dyn Error>>(entity)
+        };
+        let platform = spawn(true, Vec3::new(0.0, 0.0, 0.0), Vec3::new(0.0, 5.0, 0.0))?;
+        let dynamic = spawn(false, Vec3::new(0.0, 15.0, 0.0), Vec3::zero())?;
+
+        scheduler.run(&jobs, &mut universe)?;
+
+        let platform_position = universe.simulation.component::<true, Position>(platform)?;
+        let platform_velocity = universe
+            .simulation
+            .component::<true, LinearVelocity>(platform)?;
+        let dynamic_position = universe.simulation.component::<true, Position>(dynamic)?;
+        assert_eq!(platform_position.current, Vec3::new(0.0, 5.0, 0.0));
+        assert_eq!(platform_velocity.value, Vec3::new(0.0, 5.0, 0.0));
+        assert!(dynamic_position.current.y > 15.0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_drag_field() -> Result<(), Box<dyn Error>> {
+        let mut universe = Universe::default().with_plugin(
+            PhysicsPlugin::<true>::barebones()
+                .install_apply_drag_fields(true)
+                .install_apply_external_forces(true)
+                .install_integrate_velocities(true)
+                .make(),
+        );
+        let jobs = Jobs::default();
+        let scheduler = GraphScheduler::<true>;
+
+        universe.simulation.spawn((
+            DragField::new(0.5),
+            DensityFieldBox::new(AabbDensityField {
+                aabb: Aabb {
+                    min: Vec3::new(-10.0, -10.0, -10.0),
+                    max: Vec3::new(0.0, 10.0, 10.0),
+                },
+                density: 1.0,
+            }),
+        ))?;
+        let [inside, outside] = [-8.0, 20.0].map(|x| {
+            universe
+                .simulation
+                .spawn((
+                    PhysicsParticle,
+                    Mass::new(1.0),
+                    Position::new(Vec3::new(x, 0.0, 0.0)),
+                    LinearVelocity {
+                        value: Vec3::new(2.0, 0.0, 0.0),
+                    },
+                    ExternalForces::default(),
+                ))
+                .unwrap()
+        });
+
+        for _ in 0..5 {
+            scheduler.run(&jobs, &mut universe)?;
+        }
+
+        let inside = universe
+            .simulation
+            .component::<true, LinearVelocity>(inside)?
+            .value;
+        let outside = universe
+            .simulation
+            .component::<true, LinearVelocity>(outside)?
+            .value;
+        assert!(inside.x > 0.0 && inside.x < 2.0);
+        assert_eq!(inside.y, 0.0);
+        assert_eq!(outside, Vec3::new(2.0, 0.0, 0.0));
+
+        Ok(())
+    }
+}
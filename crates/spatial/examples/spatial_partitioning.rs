@@ -14,7 +14,7 @@ use vek::Vec2;
 fn main() -> Result<(), Box<dyn Error>> {
     // Setup universe with spatial partitioning plugin and game plugin.
     let mut universe = Universe::default()
-        .with_basics(10240, 10240)?
+        .with_basics_config(Default::default())?
         .with_plugin(anput_spatial::make_plugin::<true, MySpatialExtractor>())
         .with_plugin(
             GraphSchedulerPlugin::<true>::default()
@@ -6,7 +6,9 @@ use anput::{
     universe::Res,
     world::World,
 };
-use rstar::{Envelope, PointDistance, RTree, RTreeObject, primitives::GeomWithData};
+use rstar::{
+    Envelope, ParentNode, PointDistance, RTree, RTreeNode, RTreeObject, primitives::GeomWithData,
+};
 use std::error::Error;
 
 pub mod third_party {
@@ -25,6 +27,9 @@ pub fn make_plugin<const LOCKING: bool, Extractor: SpatialExtractor>()
         })
 }
 
+pub type SpatialGeom<Extractor> =
+    GeomWithData<<Extractor as SpatialExtractor>::SpatialObject, Entity>;
+
 pub struct SpatialPartitioning<Extractor: SpatialExtractor> {
     tree: RTree<GeomWithData<Extractor::SpatialObject, Entity>>,
 }
@@ -54,6 +59,42 @@ impl<Extractor: SpatialExtractor> SpatialPartitioning<Extractor> {
         self.tree.iter()
     }
 
+    pub fn overlapping_geom_pairs(
+        &self,
+    ) -> impl Iterator<Item = (&SpatialGeom<Extractor>, &SpatialGeom<Extractor>)> + '_ {
+        self.tree.iter().flat_map(move |a| {
+            self.tree
+                .locate_in_envelope_intersecting(&a.envelope())
+                .filter(move |b| a.data < b.data)
+                .map(move |b| (a, b))
+        })
+    }
+
+    pub fn overlapping_pairs(&self) -> impl Iterator<Item = (Entity, Entity)> + '_ {
+        self.overlapping_geom_pairs().map(|(a, b)| (a.data, b.data))
+    }
+
+    pub fn debug_node_envelopes(
+        &self,
+    ) -> Vec<(usize, <Extractor::SpatialObject as RTreeObject>::Envelope)> {
+        fn walk<T: RTreeObject>(
+            node: &ParentNode<T>,
+            depth: usize,
+            result: &mut Vec<(usize, T::Envelope)>,
+        ) {
+            result.push((depth, node.envelope()));
+            for child in node.children() {
+                if let RTreeNode::Parent(child) = child {
+                    walk(child, depth + 1, result);
+                }
+            }
+        }
+
+        let mut result = Vec::new();
+        walk(self.tree.root(), 0, &mut result);
+        result
+    }
+
     pub fn nearest_entities(
         &self,
         point: &<<Extractor::SpatialObject as RTreeObject>::Envelope as Envelope>::Point,
@@ -154,3 +195,83 @@ where
         world: &World,
     ) -> impl Iterator<Item = (Entity, Self::SpatialObject)>;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstar::{AABB, primitives::Rectangle};
+
+    struct PointExtractor;
+
+    impl SpatialExtractor for PointExtractor {
+        type SpatialObject = [f32; 2];
+
+        fn extract<const LOCKING: bool>(
+            world: &World,
+        ) -> impl Iterator<Item = (Entity, Self::SpatialObject)> {
+            world
+                .query::<LOCKING, (Entity, &[f32; 2])>()
+                .map(|(entity, point)| (entity, *point))
+        }
+    }
+
+    struct RectangleExtractor;
+
+    impl SpatialExtractor for RectangleExtractor {
+        type SpatialObject = Rectangle<[f32; 2]>;
+
+        fn extract<const LOCKING: bool>(
+            world: &World,
+        ) -> impl Iterator<Item = (Entity, Self::SpatialObject)> {
+            world
+                .query::<LOCKING, (Entity, &Rectangle<[f32; 2]>)>()
+                .map(|(entity, rectangle)| (entity, *rectangle))
+        }
+    }
+
+    #[test]
+    fn test_overlapping_pairs() {
+        fn rectangle(from: [f32; 2], to: [f32; 2]) -> Rectangle<[f32; 2]> {
+            Rectangle::from_corners(from, to)
+        }
+
+        let mut world = World::default();
+        let a = world.spawn((rectangle([0.0, 0.0], [2.0, 2.0]),)).unwrap();
+        let b = world.spawn((rectangle([1.0, 1.0], [3.0, 3.0]),)).unwrap();
+        let c = world.spawn((rectangle([1.5, 0.0], [2.5, 2.5]),)).unwrap();
+        world
+            .spawn((rectangle([10.0, 10.0], [11.0, 11.0]),))
+            .unwrap();
+        let mut partitioning = SpatialPartitioning::<RectangleExtractor>::default();
+        partitioning.rebuild::<true>(&world);
+
+        let mut pairs = partitioning.overlapping_pairs().collect::<Vec<_>>();
+        pairs.sort();
+        assert_eq!(pairs, vec![(a, b), (a, c), (b, c)]);
+    }
+
+    #[test]
+    fn test_debug_node_envelopes() {
+        let mut world = World::default();
+        for index in 0..100 {
+            world
+                .spawn(([(index % 10) as f32, (index / 10) as f32],))
+                .unwrap();
+        }
+        let mut partitioning = SpatialPartitioning::<PointExtractor>::default();
+        partitioning.rebuild::<true>(&world);
+
+        let envelopes = partitioning.debug_node_envelopes();
+        assert!(envelopes.len() > 1);
+        let (depth, root) = envelopes[0];
+        assert_eq!(depth, 0);
+        assert_eq!(root, AABB::from_corners([0.0, 0.0], [9.0, 9.0]));
+        for geom in partitioning.iter() {
+            assert!(root.contains_envelope(&geom.envelope()));
+        }
+        for (depth, envelope) in &envelopes[1..] {
+            assert!(*depth > 0);
+            assert!(root.contains_envelope(envelope));
+        }
+    }
+}
@@ -19,11 +19,13 @@ use anput_physics::{
     collisions::{CollisionMask, CollisionProfile, ContactDetection},
     components::{
         BodyDensityFieldRelation, BodyParentRelation, BodyParticleRelation, ExternalForces,
-        LinearVelocity, Mass, ParticleMaterial, PhysicsBody, PhysicsParticle, Position,
+        InterpolatedTransform, LinearVelocity, Mass, ParticleMaterial, PhysicsBody,
+        PhysicsParticle, Position,
     },
     density_fields::{DensityFieldBox, aabb::AabbDensityField, cube::CubeDensityField},
     queries::shape::ShapeOverlapQuery,
-    third_party::vek::{Aabb, Rgba, Vec3},
+    solvers::interpolate_transforms,
+    third_party::vek::{Aabb, Quaternion, Rgba, Vec3},
 };
 use glutin::{
     event::{Event, MouseButton, VirtualKeyCode},
@@ -51,6 +53,7 @@ use std::time::{Duration, Instant};
 
 pub const PIXEL_SIZE: u32 = 10;
 const MAX_FRAME_DURATION: Duration = Duration::from_millis(500);
+const FIXED_STEP_DURATION: Duration = Duration::from_millis(1000 / 30);
 
 pub struct Game {
     universe: Universe,
@@ -117,6 +120,7 @@ impl Game {
                 ContactDetection::default(),
                 Mass::new(1.0),
                 Position::new(Vec3::new(0.0, 0.0, 0.0)),
+                InterpolatedTransform::new(Vec3::new(0.0, 0.0, 0.0), Quaternion::identity()),
                 LinearVelocity::default(),
                 ExternalForces::default(),
                 ParticleMaterial::default(),
@@ -264,7 +268,7 @@ impl AppState<Vertex> for Game {
         );
 
         self.universe = Universe::default()
-            .with_basics(10240, 10240)
+            .with_basics_config(Default::default())
             .unwrap()
             .with_resource(Clock::default())
             .unwrap()
@@ -409,7 +413,7 @@ impl AppState<Vertex> for Game {
             )
             .unwrap();
 
-        if self.fixed_step_timer.elapsed().as_millis() > 1000 / 30 {
+        if self.fixed_step_timer.elapsed() > FIXED_STEP_DURATION {
             self.fixed_step_timer = Instant::now();
             self.universe
                 .resources
@@ -436,6 +440,19 @@ impl AppState<Vertex> for Game {
                 .unwrap();
         }
 
+        let paused_simulation = self
+            .universe
+            .resources
+            .get::<true, Globals>()
+            .unwrap()
+            .paused_simulation;
+        let alpha = if paused_simulation {
+            1.0
+        } else {
+            self.fixed_step_timer.elapsed().as_secs_f32() / FIXED_STEP_DURATION.as_secs_f32()
+        };
+        interpolate_transforms::<true>(&self.universe.simulation, alpha);
+
         {
             let draw = &mut **self
                 .universe
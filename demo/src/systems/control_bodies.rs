@@ -13,10 +13,11 @@ use anput_physics::{
     collisions::{CollisionMask, CollisionProfile, ContactDetection},
     components::{
         BodyDensityFieldRelation, BodyParentRelation, BodyParticleRelation, ExternalForces,
-        LinearVelocity, Mass, ParticleMaterial, PhysicsBody, PhysicsParticle, Position,
+        InterpolatedTransform, LinearVelocity, Mass, ParticleMaterial, PhysicsBody,
+        PhysicsParticle, Position,
     },
     density_fields::{DensityFieldBox, cube::CubeDensityField, sphere::SphereDensityField},
-    third_party::vek::{Rgba, Vec2},
+    third_party::vek::{Quaternion, Rgba, Vec2},
 };
 use send_wrapper::SendWrapper;
 use spitfire_draw::utils::Vertex;
@@ -77,6 +78,13 @@ pub fn control_bodies(context: SystemContext) -> Result<(), Box<dyn Error>> {
                 .add_component(Position::new(drag_position))
                 .ok()
                 .unwrap();
+            bundle
+                .add_component(InterpolatedTransform::new(
+                    drag_position,
+                    Quaternion::identity(),
+                ))
+                .ok()
+                .unwrap();
             bundle
                 .add_component(LinearVelocity::new(direction))
                 .ok()
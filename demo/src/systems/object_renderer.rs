@@ -12,7 +12,7 @@ use anput::{
     world::{Relation, World},
 };
 use anput_physics::{
-    components::{BodyParticleRelation, PhysicsParticle, Position},
+    components::{BodyParticleRelation, InterpolatedTransform, PhysicsParticle, Position},
     density_fields::{
         DensityFieldBox, aabb::AabbDensityField, cube::CubeDensityField, sphere::SphereDensityField,
     },
@@ -53,7 +53,14 @@ pub fn render_objects(context: SystemContext) -> Result<(), Box<dyn Error>> {
                 Include<Visible>,
             ),
         >,
-        Lookup<true, (&Position, Include<PhysicsParticle>)>,
+        Lookup<
+            true,
+            (
+                &Position,
+                Option<&InterpolatedTransform>,
+                Include<PhysicsParticle>,
+            ),
+        >,
     )>()?;
 
     let draw = &mut *draw;
@@ -66,7 +73,7 @@ pub fn render_objects(context: SystemContext) -> Result<(), Box<dyn Error>> {
             .flat_map(|particles| particles.entities());
         let color = color.copied().unwrap_or(Rgba::white());
 
-        if let Some(object) = density_field.as_any().downcast_ref::<AabbDensityField>() {
+        if let Some(object) = density_field.downcast_ref::<AabbDensityField>() {
             let color = color.into_array();
 
             primitives
@@ -93,58 +100,46 @@ pub fn render_objects(context: SystemContext) -> Result<(), Box<dyn Error>> {
                     },
                 ])
                 .draw(draw, graphics);
-        } else if let Some(object) = density_field
-            .as_any()
-            .downcast_ref::<CubeDensityField<true>>()
-        {
+        } else if let Some(object) = density_field.downcast_ref::<CubeDensityField<true>>() {
             let color = color.into_array();
 
-            for (position, _) in particle_lookup.lookup(world, particles) {
+            for (position, transform, _) in particle_lookup.lookup(world, particles) {
+                let position = transform
+                    .map(|transform| transform.position)
+                    .unwrap_or(position.current);
                 let extents = object.total_extents();
                 primitives
                     .emit_triangle_fan([
                         Vertex {
-                            position: [
-                                position.current.x - extents.x,
-                                position.current.y - extents.y,
-                            ],
+                            position: [position.x - extents.x, position.y - extents.y],
                             uv: [0.0, 0.0, 0.0],
                             color,
                         },
                         Vertex {
-                            position: [
-                                position.current.x + extents.x,
-                                position.current.y - extents.y,
-                            ],
+                            position: [position.x + extents.x, position.y - extents.y],
                             uv: [0.0, 0.0, 0.0],
                             color,
                         },
                         Vertex {
-                            position: [
-                                position.current.x + extents.x,
-                                position.current.y + extents.y,
-                            ],
+                            position: [position.x + extents.x, position.y + extents.y],
                             uv: [0.0, 0.0, 0.0],
                             color,
                         },
                         Vertex {
-                            position: [
-                                position.current.x - extents.x,
-                                position.current.y + extents.y,
-                            ],
+                            position: [position.x - extents.x, position.y + extents.y],
                             uv: [0.0, 0.0, 0.0],
                             color,
                         },
                     ])
                     .draw(draw, graphics);
             }
-        } else if let Some(object) = density_field
-            .as_any()
-            .downcast_ref::<SphereDensityField<true>>()
-        {
-            for (position, _) in particle_lookup.lookup(world, particles) {
+        } else if let Some(object) = density_field.downcast_ref::<SphereDensityField<true>>() {
+            for (position, transform, _) in particle_lookup.lookup(world, particles) {
+                let position = transform
+                    .map(|transform| transform.position)
+                    .unwrap_or(position.current);
                 primitives
-                    .emit_circle(position.current.into(), object.radius, 0.1)
+                    .emit_circle(position.into(), object.radius, 0.1)
                     .tint(color)
                     .draw(draw, graphics);
             }